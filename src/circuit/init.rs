@@ -0,0 +1,194 @@
+use std::cell::Cell;
+
+use crate::circuit::{Potential, Wire};
+
+/// the seed of the power-up state when [`seed`] is not called.
+pub const DEFAULT_SEED: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// a seedable random generator, by xorshift64.
+///
+/// the generator is a single `u64`, so it is copied into a snapshot or a save file
+/// with [`Rng::state`], and a run resumed from it draws the same bits.
+///
+/// # Examples
+///
+/// ```
+/// use simulation_computer_rust::circuit::init::Rng;
+///
+/// let mut rng = Rng::new(42);
+/// let saved = rng.state();
+/// let first = rng.next_u64();
+/// assert_eq!(Rng::from_state(saved).next_u64(), first);
+/// assert!(rng.below(6) < 6);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEED)
+    }
+}
+
+impl Rng {
+    /// Create a generator of the seed, the seed 0 is the [`DEFAULT_SEED`].
+    pub fn new(seed: u64) -> Self {
+        // xorshift never leaves the state 0
+        Self::from_state(if seed == 0 { DEFAULT_SEED } else { seed })
+    }
+
+    /// Get the state, which is resumed by [`Rng::from_state`].
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+
+    /// Resume a generator from its state.
+    pub fn from_state(state: u64) -> Self {
+        assert!(state != 0, "state must not be 0");
+        Self { state }
+    }
+
+    /// Get the next random number.
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    /// Get the next random bit.
+    pub fn bit(&mut self) -> Potential {
+        self.next_u64() >> 63 == 1
+    }
+
+    /// Get the next random bits.
+    pub fn bits(&mut self, width: usize) -> Vec<Potential> {
+        (0..width).map(|_| self.bit()).collect()
+    }
+
+    /// Get a random number less than `bound`.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "bound must be greater than 0");
+        self.next_u64() % bound
+    }
+}
+
+thread_local! {
+    static STATE: Cell<Rng> = Cell::new(Rng::default());
+}
+
+/// Seed the random generator of this thread, which powers up the registers and
+/// drives the random stimulus.
+///
+/// the same seed gives the same power-up state, so a failure is reproduced by
+/// running the test with the seed again.
+pub fn seed(seed: u64) {
+    STATE.with(|state| state.set(Rng::new(seed)));
+}
+
+/// Get a copy of the random generator of this thread, to save it with a snapshot.
+pub fn snapshot() -> Rng {
+    STATE.with(|state| state.get())
+}
+
+/// Restore the random generator of this thread from a snapshot.
+pub fn restore(rng: Rng) {
+    STATE.with(|state| state.set(rng));
+}
+
+/// Draw from the random generator of this thread, all the randomness of a run goes
+/// through it so the run is reproduced from its seed.
+pub fn with_rng<T>(f: impl FnOnce(&mut Rng) -> T) -> T {
+    STATE.with(|state| {
+        let mut rng = state.get();
+        let value = f(&mut rng);
+        state.set(rng);
+        value
+    })
+}
+
+/// Get the next random bit.
+fn random() -> Potential {
+    with_rng(Rng::bit)
+}
+
+/// Create the wires of a register as they power up.
+///
+/// the register powers up as all `false` by default. with the `strict-init`
+/// feature it powers up with the random bits of [`seed`], like the flip-flops of a
+/// real chip, so a design which reads a register before its reset fails the tests.
+///
+/// the registers built from latches, like the [`DFlipFlop`](crate::component::latch::DFlipFlop)s
+/// of a counter or a shift register, power up through their
+/// [`SRLatch`](crate::component::latch::SRLatch) instead.
+pub fn power_up(width: usize) -> Vec<Wire> {
+    (0..width).map(|_| power_up_wire()).collect()
+}
+
+/// Create a 1-bit register as it powers up, see [`power_up`].
+pub fn power_up_wire() -> Wire {
+    if cfg!(feature = "strict-init") {
+        Wire::new(random())
+    } else {
+        Wire::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_is_seeded() {
+        seed(42);
+        let first: Vec<Potential> = (0..64).map(|_| random()).collect();
+        seed(42);
+        let second: Vec<Potential> = (0..64).map(|_| random()).collect();
+        assert_eq!(first, second);
+        assert!(first.contains(&true) && first.contains(&false));
+        seed(43);
+        let third: Vec<Potential> = (0..64).map(|_| random()).collect();
+        assert_ne!(first, third);
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict-init"))]
+    fn test_power_up_zero() {
+        seed(7);
+        assert!(power_up(16).iter().all(|wire| !wire.output()));
+        assert!(!power_up_wire().output());
+    }
+
+    #[test]
+    #[cfg(feature = "strict-init")]
+    fn test_power_up_random() {
+        seed(7);
+        let first: Vec<Potential> = power_up(64).iter().map(|wire| wire.output()).collect();
+        seed(7);
+        let second: Vec<Potential> = power_up(64).iter().map(|wire| wire.output()).collect();
+        assert_eq!(first, second);
+        assert!(first.contains(&true));
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        seed(9);
+        with_rng(|rng| rng.bits(5));
+        let saved = snapshot();
+        let first = with_rng(|rng| rng.bits(64));
+        restore(saved);
+        assert_eq!(with_rng(|rng| rng.bits(64)), first);
+        assert_eq!(Rng::from_state(saved.state()), saved);
+    }
+
+    #[test]
+    fn test_rng_seed_zero() {
+        assert_eq!(Rng::new(0), Rng::default());
+        let mut rng = Rng::new(1);
+        assert!((0..100).all(|_| rng.below(3) < 3));
+    }
+}
@@ -0,0 +1,227 @@
+use std::ops::Range;
+
+use crate::circuit::{Endianness, Potential, Potentials};
+
+/// a named field of bits in a [`Layout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    pub name: String,
+    /// the position of the first bit of the field.
+    pub offset: usize,
+    pub width: usize,
+    /// the bit order inside the field, little endian puts the lowest bit first.
+    pub endianness: Endianness,
+}
+
+impl Field {
+    /// Get the positions of the bits of the field.
+    pub fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.width
+    }
+}
+
+/// a declarative layout of bit fields, e.g. the format of an instruction, a packet
+/// or the pins of a component.
+///
+/// the fields are laid out from the bit 0 in the order they are added, so the
+/// positions are not counted by hand. the layout is packed to and unpacked from
+/// little endian [`Potentials`], where the bit `i` is the pin `i`.
+///
+/// # Examples
+///
+/// ```
+/// use simulation_computer_rust::circuit::layout::Layout;
+///
+/// let layout = Layout::default().field("op", 4).field("rd", 3).field("imm", 9);
+/// assert_eq!(layout.width(), 16);
+/// assert_eq!(layout.range("rd"), 4..7);
+/// let word = layout.pack(&[("op", 0x3), ("rd", 5), ("imm", 300)]);
+/// assert_eq!(layout.get(&word, "imm"), 300);
+/// assert_eq!(layout.unpack(&word), vec![("op", 3), ("rd", 5), ("imm", 300)]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Layout {
+    fields: Vec<Field>,
+}
+
+impl Layout {
+    /// Add a little endian field after the last field.
+    pub fn field(self, name: &str, width: usize) -> Self {
+        self.field_with(name, width, Endianness::Little)
+    }
+
+    /// Add a field of the endianness after the last field.
+    pub fn field_with(mut self, name: &str, width: usize, endianness: Endianness) -> Self {
+        assert!(width > 0, "width must be greater than 0");
+        assert!(
+            self.find(name).is_none(),
+            "field `{}` is already in the layout",
+            name
+        );
+        self.fields.push(Field {
+            name: name.to_string(),
+            offset: self.width(),
+            width,
+            endianness,
+        });
+        self
+    }
+
+    fn find(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Get a field by its name.
+    pub fn get_field(&self, name: &str) -> &Field {
+        self.find(name)
+            .unwrap_or_else(|| panic!("field `{}` is not in the layout", name))
+    }
+
+    /// Get the positions of the bits of a field.
+    pub fn range(&self, name: &str) -> Range<usize> {
+        self.get_field(name).range()
+    }
+
+    /// Get the fields in the order of their positions.
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// Get the number of bits of the layout.
+    pub fn width(&self) -> usize {
+        self.fields.last().map_or(0, |field| field.range().end)
+    }
+
+    /// Pack the values of the fields, the fields not given are 0.
+    pub fn pack(&self, values: &[(&str, u64)]) -> Potentials {
+        let mut bits = vec![false; self.width()];
+        for (name, value) in values {
+            self.set(&mut bits, name, *value);
+        }
+        Potentials::new(bits, Endianness::Little)
+    }
+
+    /// Get a field by its name, whose value fits in a `u64`.
+    fn value_field(&self, name: &str) -> &Field {
+        let field = self.get_field(name);
+        assert!(field.width <= 64, "field `{}` is wider than 64 bits", name);
+        field
+    }
+
+    /// Set the value of a field in the little endian bits of the layout.
+    pub fn set(&self, bits: &mut [Potential], name: &str, value: u64) {
+        let field = self.value_field(name);
+        assert!(
+            field.width == 64 || value < 1 << field.width,
+            "value {} does not fit in field `{}`",
+            value,
+            name
+        );
+        for i in 0..field.width {
+            let position = match field.endianness {
+                Endianness::Little => field.offset + i,
+                Endianness::Big => field.offset + field.width - 1 - i,
+            };
+            bits[position] = (value >> i) & 1 == 1;
+        }
+    }
+
+    /// Get the value of a field.
+    pub fn get(&self, potentials: &Potentials, name: &str) -> u64 {
+        assert!(
+            potentials.len() == self.width(),
+            "potentials must be {} bits",
+            self.width()
+        );
+        self.read(&potentials.to_endian(Endianness::Little), name)
+    }
+
+    /// Get the value of a field from the little endian bits of the layout.
+    pub fn read(&self, bits: &[Potential], name: &str) -> u64 {
+        let field = self.value_field(name);
+        let mut value = 0;
+        for i in 0..field.width {
+            let position = match field.endianness {
+                Endianness::Little => field.offset + i,
+                Endianness::Big => field.offset + field.width - 1 - i,
+            };
+            value |= (bits[position] as u64) << i;
+        }
+        value
+    }
+
+    /// Unpack the values of all the fields.
+    pub fn unpack(&self, potentials: &Potentials) -> Vec<(&str, u64)> {
+        self.fields
+            .iter()
+            .map(|field| (field.name.as_str(), self.get(potentials, &field.name)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::Padding;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Endianness::Little, "1101 1000")]
+    #[case(Endianness::Big, "1101 0001")]
+    fn test_layout_field_endianness(#[case] endianness: Endianness, #[case] raw: &str) {
+        let layout = Layout::default()
+            .field("low", 4)
+            .field_with("high", 4, endianness);
+        let word = layout.pack(&[("low", 0b1011), ("high", 0b0001)]);
+        assert_eq!(word.to_little_endian(Padding::NIBBLE), raw);
+        assert_eq!(layout.get(&word, "high"), 1);
+        assert_eq!(layout.read(&word.get_data(true), "low"), 0b1011);
+    }
+
+    #[test]
+    fn test_layout_offsets() {
+        let layout = Layout::default()
+            .field("clock", 1)
+            .field("data", 8)
+            .field("address", 16);
+        assert_eq!(layout.width(), 25);
+        let offsets: Vec<usize> = layout.fields().iter().map(|f| f.offset).collect();
+        assert_eq!(offsets, vec![0, 1, 9]);
+        assert_eq!(layout.range("address"), 9..25);
+        assert_eq!(Layout::default().width(), 0);
+    }
+
+    #[test]
+    fn test_layout_unpack_missing() {
+        let layout = Layout::default().field("a", 2).field("b", 64);
+        let word = layout.pack(&[("b", u64::MAX)]);
+        assert_eq!(layout.unpack(&word), vec![("a", 0), ("b", u64::MAX)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "value 4 does not fit in field `a`")]
+    fn test_layout_pack_overflow() {
+        Layout::default().field("a", 2).pack(&[("a", 4)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "field `b` is wider than 64 bits")]
+    fn test_layout_wide_field() {
+        // a wide field is a range of pins, but not a value
+        let layout = Layout::default().field("a", 1).field("b", 65);
+        assert_eq!(layout.range("b"), 1..66);
+        layout.pack(&[("b", 0)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "field `a` is already in the layout")]
+    fn test_layout_duplicate() {
+        let _ = Layout::default().field("a", 2).field("a", 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "field `c` is not in the layout")]
+    fn test_layout_unknown() {
+        Layout::default().field("a", 2).range("c");
+    }
+}
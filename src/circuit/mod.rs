@@ -25,6 +25,10 @@
 //! assert_eq!(not_gate.output(), false);
 //! ```
 
+pub mod init;
+pub mod layout;
+pub mod word;
+
 /// Potential in circuit.
 pub type Potential = bool;
 
@@ -93,6 +97,80 @@ impl Wire {
     }
 }
 
+/// Padding policy of the high bits when [`Potentials`] are widened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pad {
+    /// The high bits are filled with `0`, the value is unsigned.
+    Zero,
+    /// The high bits are filled with the highest bit, the value is two's complement.
+    Sign,
+}
+
+/// Padding policy of the text form of [`Potentials`], used by both the parsers and
+/// the formatters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Padding {
+    /// Every bit is kept, and the bits are not grouped.
+    Keep,
+    /// The high `0` bits are stripped. use [`Potentials::pad`] to widen the result
+    /// to a fixed width again.
+    Strip,
+    /// The high bits are filled with `0` to a whole number of groups of the size,
+    /// and the groups are separated by a space.
+    Group(usize),
+}
+
+impl Padding {
+    /// 4 bits per group.
+    pub const NIBBLE: Self = Self::Group(4);
+    /// 8 bits per group.
+    pub const BYTE: Self = Self::Group(8);
+
+    /// Apply the policy to little endian bits.
+    fn apply(&self, mut bits: Vec<Potential>) -> Vec<Potential> {
+        match *self {
+            Self::Keep => {}
+            Self::Strip => {
+                while bits.last() == Some(&false) {
+                    bits.pop();
+                }
+            }
+            Self::Group(size) => {
+                assert!(size > 0, "group size must be greater than 0");
+                bits.resize(bits.len().next_multiple_of(size), false);
+            }
+        }
+        bits
+    }
+}
+
+/// The bit order of multi-bit values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    /// The first bit is the lowest bit.
+    Little,
+    /// The first bit is the highest bit.
+    Big,
+}
+
+impl Endianness {
+    /// The bit order of the buses of the components in this crate.
+    pub const BUS: Self = Self::Little;
+
+    /// Get the endianness of a `little_endian` flag.
+    pub fn of_little(little_endian: bool) -> Self {
+        if little_endian {
+            Self::Little
+        } else {
+            Self::Big
+        }
+    }
+
+    pub fn is_little(&self) -> bool {
+        *self == Self::Little
+    }
+}
+
 /// Potentials in circuit.
 
 #[derive(Debug, Clone)]
@@ -110,11 +188,55 @@ impl Potentials {
         }
     }
 
+    /// Create a new Potentials of the data in the endianness.
+    pub fn new(potentials: Vec<Potential>, endianness: Endianness) -> Self {
+        Self {
+            data: potentials,
+            little_endian: endianness.is_little(),
+        }
+    }
+
+    /// Get the endianness the data is stored in.
+    pub fn endianness(&self) -> Endianness {
+        Endianness::of_little(self.little_endian)
+    }
+
+    /// Get the data of the Potentials in the endianness.
+    pub fn to_endian(&self, endianness: Endianness) -> Vec<Potential> {
+        self.bits(endianness.is_little()).collect()
+    }
+
+    /// Convert the Potentials to be stored in the endianness, the value is kept.
+    pub fn into_endian(self, endianness: Endianness) -> Self {
+        if self.endianness() == endianness {
+            return self;
+        }
+        Self::new(self.to_endian(endianness), endianness)
+    }
+
     pub fn get_data(&self, little_endian: bool) -> Vec<Potential> {
-        if self.little_endian ^ little_endian {
-            self.data.iter().rev().cloned().collect()
-        } else {
-            self.data.iter().cloned().collect()
+        self.bits(little_endian).collect()
+    }
+
+    /// Borrow the data of the Potentials in the stored endian, without a copy.
+    pub fn as_bits(&self) -> &[Potential] {
+        &self.data
+    }
+
+    /// Whether the data of the Potentials is stored in little endian.
+    pub fn is_little_endian(&self) -> bool {
+        self.little_endian
+    }
+
+    /// Iterate the data of the Potentials in the given endian, without a copy.
+    ///
+    /// # Arguments
+    ///
+    /// * `little_endian` - The endian of the iteration.
+    pub fn bits(&self, little_endian: bool) -> Bits<'_> {
+        Bits {
+            iter: self.data.iter(),
+            reverse: self.little_endian ^ little_endian,
         }
     }
 
@@ -129,32 +251,14 @@ impl Potentials {
     ///
     /// # Arguments
     ///
-    /// * `little_endian` - The little endian string.
-    /// * `ignore_padding` - Whether to ignore the padding.
+    /// * `little_endian` - The little endian string, the spaces are ignored.
+    /// * `padding` - The policy of the high bits, which are at the end of the string.
     ///
     /// # Returns
     ///
     /// * `Self` - The new Potentials.
-    pub fn from_little_endian(little_endian: &str, ignore_padding: bool) -> Self {
-        let mut data = Vec::new();
-        let mut ignore = true;
-        for c in little_endian.chars().rev() {
-            match c {
-                '0' => {
-                    ignore = ignore && true;
-                    if !ignore_padding || (ignore_padding && !ignore) {
-                        data.push(false);
-                    }
-                }
-                '1' => {
-                    ignore = ignore && false;
-                    data.push(true);
-                }
-                ' ' => continue,
-                _ => panic!("Invalid character in little endian string"),
-            }
-        }
-        data.reverse();
+    pub fn from_little_endian(little_endian: &str, padding: Padding) -> Self {
+        let data = padding.apply(parse_bits(little_endian.chars()));
         Self {
             data,
             little_endian: true,
@@ -165,144 +269,208 @@ impl Potentials {
     ///
     /// # Arguments
     ///
-    /// * `big_endian` - The big endian string.
-    /// * `ignore_padding` - Whether to ignore the padding.
+    /// * `big_endian` - The big endian string, the spaces are ignored.
+    /// * `padding` - The policy of the high bits, which are at the beginning of the string.
     ///
     /// # Returns
     ///
     /// * `Self` - The new Potentials.
-    pub fn from_big_endian(big_endian: &str, ignore_padding: bool) -> Self {
-        let mut data = Vec::new();
-        let mut ignore = true;
-        for c in big_endian.chars() {
-            match c {
-                '0' => {
-                    ignore = ignore && true;
-                    if !ignore_padding {
-                        data.push(false);
-                    } else {
-                        if !ignore {
-                            data.push(false);
-                        }
-                    }
-                }
-                '1' => {
-                    ignore = ignore && false;
-                    data.push(true);
-                }
-                ' ' => continue,
-                _ => panic!("Invalid character in little endian string"),
-            }
-        }
+    pub fn from_big_endian(big_endian: &str, padding: Padding) -> Self {
+        let mut data = padding.apply(parse_bits(big_endian.chars().rev()));
+        data.reverse();
         Self {
             data,
             little_endian: false,
         }
     }
 
+    /// Create a new little endian Potentials of a value with a fixed width.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value, it must fit in the width under the padding policy.
+    /// * `width` - The number of bits.
+    /// * `pad` - Whether the value is unsigned or two's complement.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The new Potentials.
+    pub fn with_width(value: i64, width: usize, pad: Pad) -> Self {
+        assert!(width > 0, "width must be greater than 0");
+        let fits = match pad {
+            Pad::Zero => value >= 0 && (width >= 63 || value < 1 << width),
+            Pad::Sign => width >= 64 || (value >= -(1 << (width - 1)) && value < 1 << (width - 1)),
+        };
+        assert!(fits, "value {} does not fit in {} bits", value, width);
+        let data = (0..width).map(|i| (value >> i.min(63)) & 1 == 1).collect();
+        Self {
+            data,
+            little_endian: true,
+        }
+    }
+
+    /// Widen the Potentials to `width` bits, the high bits are filled by the padding policy.
+    /// the endian of the Potentials is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The number of bits, it must not be less than the length.
+    /// * `pad` - How to fill the high bits.
+    pub fn pad(&self, width: usize, pad: Pad) -> Self {
+        assert!(
+            width >= self.len(),
+            "width must not be less than {}, use truncate to narrow",
+            self.len()
+        );
+        let fill = match pad {
+            Pad::Zero => false,
+            Pad::Sign => self.bits(true).next_back().unwrap_or(false),
+        };
+        let mut data = self.get_data(true);
+        data.resize(width, fill);
+        self.with_endian(data)
+    }
+
+    /// Narrow the Potentials to `width` bits, the high bits are dropped.
+    /// the endian of the Potentials is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The number of bits, it must not be greater than the length.
+    pub fn truncate(&self, width: usize) -> Self {
+        assert!(
+            width <= self.len(),
+            "width must not be greater than {}, use pad to widen",
+            self.len()
+        );
+        let mut data = self.get_data(true);
+        data.truncate(width);
+        self.with_endian(data)
+    }
+
+    /// Create Potentials in the endian of self, from little endian data.
+    fn with_endian(&self, mut data: Vec<Potential>) -> Self {
+        if !self.little_endian {
+            data.reverse();
+        }
+        Self {
+            data,
+            little_endian: self.little_endian,
+        }
+    }
+
     pub fn len(&self) -> usize {
         self.data.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
     /// Get the raw data of the Potentials.
     ///
     /// # Arguments
     ///
     /// * `little_endian` - The endian of the Potentials.
-    /// * `format_type` - The format type of the Potentials.
-    ///     * `0` - No format.
-    ///     * `1` - 4 bits per group(Nibble).
-    ///     * `2` - 8 bits per group(Byte).
+    /// * `padding` - The policy of the high bits and the grouping.
     ///
     /// # Returns
     ///
     /// * `String` - The raw data of the Potentials.
-    pub fn to_raw(&self, little_endian: bool, format_type: usize) -> String {
-        assert!(format_type <= 2);
-        fn format(items: Vec<&Potential>, format_type: usize, little_endian: bool) -> String {
-            let mut s = String::with_capacity(items.len());
-            let length = items.len();
-            let padding: usize = if format_type == 1 && length % 4 != 0 {
-                4 - (length % 4)
-            } else if format_type == 2 && length % 8 != 0 {
-                8 - (length % 8)
-            } else {
-                0
-            };
-            let mut cursor = 0;
-            if !little_endian {
-                // big endian padding at the beginning
-                if padding > 0 {
-                    for _i in 0..padding {
-                        s.push('0');
-                    }
-                }
-                cursor += padding;
-            }
-            for p in items {
-                s.push(if *p { '1' } else { '0' });
-                cursor += 1;
-                if format_type == 1 && cursor % 4 == 0 && cursor != 0 {
-                    s.push(' ');
-                } else if format_type == 2 && cursor % 8 == 0 && cursor != 0 {
-                    s.push(' ');
-                }
-            }
-            if little_endian {
-                // little endian padding at the end
-                if padding > 0 {
-                    for _i in 0..padding {
-                        s.push('0');
-                    }
-                }
-            }
-            // may end With ''
-            s.trim_end().to_owned()
+    pub fn to_raw(&self, little_endian: bool, padding: Padding) -> String {
+        let mut bits = padding.apply(self.get_data(true));
+        if !little_endian {
+            bits.reverse();
         }
-        if self.little_endian ^ little_endian {
-            // target endian different to current endian
-            let items: Vec<&Potential> = self.data.iter().rev().collect();
-            format(items, format_type, little_endian)
-        } else {
-            // target endian same as current endian
-            let items: Vec<&Potential> = self.data.iter().collect();
-            format(items, format_type, little_endian)
+        let mut s = String::with_capacity(bits.len() * 2);
+        for (i, p) in bits.iter().enumerate() {
+            if let Padding::Group(size) = padding
+                && i > 0
+                && i % size == 0
+            {
+                s.push(' ');
+            }
+            s.push(if *p { '1' } else { '0' });
         }
+        s
     }
 
     /// Get the little endian raw data of the Potentials.
     ///
     /// # Arguments
     ///
-    /// * `format_type` - The format type of the Potentials. Default `1`
-    ///     * `0` - No format.
-    ///     * `1` - 4 bits per group(Nibble).
-    ///     * `2` - 8 bits per group(Byte).
+    /// * `padding` - The policy of the high bits and the grouping, usually
+    ///   [`Padding::NIBBLE`].
     ///
     /// # Returns
     ///
     /// * `String` - The little endian raw data of the Potentials.
-    pub fn to_little_endian(&self, format_type: Option<usize>) -> String {
-        self.to_raw(true, format_type.unwrap_or(1))
+    pub fn to_little_endian(&self, padding: Padding) -> String {
+        self.to_raw(true, padding)
     }
 
     /// Get the big endian raw data of the Potentials.
     ///
     /// # Arguments
     ///
-    /// * `format_type` - The format type of the Potentials.Default `1`
-    ///     * `0` - No format.
-    ///     * `1` - 4 bits per group(Nibble).
-    ///     * `2` - 8 bits per group(Byte).
+    /// * `padding` - The policy of the high bits and the grouping, usually
+    ///   [`Padding::NIBBLE`].
     ///
     /// # Returns
     ///
     /// * `String` - The big endian raw data of the Potentials.
-    pub fn to_big_endian(&self, format_type: Option<usize>) -> String {
-        self.to_raw(false, format_type.unwrap_or(1))
+    pub fn to_big_endian(&self, padding: Padding) -> String {
+        self.to_raw(false, padding)
+    }
+}
+
+/// Parse the bits of a string from the lowest bit, the spaces are ignored.
+fn parse_bits(chars: impl Iterator<Item = char>) -> Vec<Potential> {
+    chars
+        .filter(|c| *c != ' ')
+        .map(|c| match c {
+            '0' => false,
+            '1' => true,
+            _ => panic!("Invalid character in potentials string"),
+        })
+        .collect()
+}
+
+/// Iterator over the data of [`Potentials`] in a chosen endian.
+#[derive(Debug, Clone)]
+pub struct Bits<'a> {
+    iter: std::slice::Iter<'a, Potential>,
+    reverse: bool,
+}
+
+impl Iterator for Bits<'_> {
+    type Item = Potential;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.reverse {
+            self.iter.next_back().copied()
+        } else {
+            self.iter.next().copied()
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
     }
 }
 
+impl DoubleEndedIterator for Bits<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.reverse {
+            self.iter.next().copied()
+        } else {
+            self.iter.next_back().copied()
+        }
+    }
+}
+
+impl ExactSizeIterator for Bits<'_> {}
+
 /// AND gate in circuit.
 #[derive(Debug, Default, Clone)]
 pub struct ANDGate {
@@ -539,7 +707,7 @@ mod tests {
     #[case(vec![false,false,false], "000")]
     fn test_potentials_little_endian_2_little(#[case] data: Vec<Potential>, #[case] raw: String) {
         let potentials: Potentials = Potentials::of_little_endian(data);
-        assert_eq!(potentials.to_little_endian(Some(0)), raw);
+        assert_eq!(potentials.to_little_endian(Padding::Keep), raw);
     }
     #[rstest]
     #[case(vec![true,true,true],"111")]
@@ -552,7 +720,7 @@ mod tests {
     #[case(vec![false,false,false], "000")]
     fn test_potentials_big_endian_2_little(#[case] data: Vec<Potential>, #[case] raw: String) {
         let potentials: Potentials = Potentials::of_big_endian(data);
-        assert_eq!(potentials.to_little_endian(Some(0)), raw);
+        assert_eq!(potentials.to_little_endian(Padding::Keep), raw);
     }
     #[rstest]
     #[case(vec![true,true,true,true,false,false], "1111 0000")]
@@ -561,7 +729,7 @@ mod tests {
     #[case(vec![true,true,true,true,false,false,false,false,false], "1111 0000 0000")]
     fn test_potentials_little_endian_format_4(#[case] data: Vec<Potential>, #[case] raw: String) {
         let potentials: Potentials = Potentials::of_little_endian(data);
-        assert_eq!(potentials.to_little_endian(Some(1)), raw);
+        assert_eq!(potentials.to_little_endian(Padding::NIBBLE), raw);
     }
     #[rstest]
     #[case(vec![true,true,true,true,false,false], "0011 1100")]
@@ -570,7 +738,7 @@ mod tests {
     #[case(vec![true,true,true,true,false,false,false,false,false], "0001 1110 0000")]
     fn test_potentials_big_endian_format_4(#[case] data: Vec<Potential>, #[case] raw: String) {
         let potentials: Potentials = Potentials::of_big_endian(data);
-        assert_eq!(potentials.to_big_endian(Some(1)), raw);
+        assert_eq!(potentials.to_big_endian(Padding::NIBBLE), raw);
     }
 
     #[rstest]
@@ -580,7 +748,7 @@ mod tests {
         #[case] raw: String,
         #[case] data: Vec<Potential>,
     ) {
-        let potentials: Potentials = Potentials::from_little_endian(&raw, false);
+        let potentials: Potentials = Potentials::from_little_endian(&raw, Padding::Keep);
         assert_eq!(potentials.data, data);
     }
     #[rstest]
@@ -590,21 +758,171 @@ mod tests {
         #[case] raw: String,
         #[case] data: Vec<Potential>,
     ) {
-        let potentials: Potentials = Potentials::from_little_endian(&raw, true);
+        let potentials: Potentials = Potentials::from_little_endian(&raw, Padding::Strip);
         assert_eq!(potentials.data, data);
     }
     #[rstest]
     #[case("0011 0000",vec![false,false,true,true,false,false,false,false])]
     #[case("0011 1100",vec![false,false,true,true,true,true,false,false])]
     fn test_potentials_from_big_endian_str_01(#[case] raw: String, #[case] data: Vec<Potential>) {
-        let potentials: Potentials = Potentials::from_big_endian(&raw, false);
+        let potentials: Potentials = Potentials::from_big_endian(&raw, Padding::Keep);
         assert_eq!(potentials.data, data);
     }
     #[rstest]
     #[case("0011 0000",vec![true,true,false,false,false,false])]
     #[case("0011 1100",vec![true,true,true,true,false,false])]
     fn test_potentials_from_big_endian_str_02(#[case] raw: String, #[case] data: Vec<Potential>) {
-        let potentials: Potentials = Potentials::from_big_endian(&raw, true);
+        let potentials: Potentials = Potentials::from_big_endian(&raw, Padding::Strip);
         assert_eq!(potentials.data, data);
     }
+
+    #[rstest]
+    #[case(Padding::Keep, "0110 00", "011000")]
+    #[case(Padding::Strip, "0110 00", "011")]
+    #[case(Padding::NIBBLE, "0110 00", "0110 0000")]
+    #[case(Padding::BYTE, "11 0 1", "11010000")]
+    #[case(Padding::Group(3), "1 1", "110")]
+    fn test_potentials_padding(
+        #[case] padding: Padding,
+        #[case] raw: &str,
+        #[case] expected: &str,
+    ) {
+        let little = Potentials::from_little_endian(raw, padding);
+        assert_eq!(little.to_little_endian(padding), expected);
+        let big_raw: String = raw.chars().rev().collect();
+        let big = Potentials::from_big_endian(&big_raw, padding);
+        assert_eq!(big.to_little_endian(padding), expected);
+        // the formatter applies the same policy to the unpadded bits
+        let keep = Potentials::from_little_endian(raw, Padding::Keep);
+        assert_eq!(keep.to_little_endian(padding), expected);
+    }
+
+    #[test]
+    fn test_potentials_as_bits() {
+        let potentials = Potentials::of_big_endian(vec![true, false, false]);
+        assert_eq!(potentials.as_bits(), &[true, false, false]);
+        assert!(!potentials.is_little_endian());
+    }
+
+    #[rstest]
+    #[case(true, true, vec![true, true, false])]
+    #[case(true, false, vec![false, true, true])]
+    #[case(false, true, vec![false, true, true])]
+    #[case(false, false, vec![true, true, false])]
+    fn test_potentials_bits(
+        #[case] stored_little_endian: bool,
+        #[case] little_endian: bool,
+        #[case] expected: Vec<Potential>,
+    ) {
+        let data = vec![true, true, false];
+        let potentials = if stored_little_endian {
+            Potentials::of_little_endian(data)
+        } else {
+            Potentials::of_big_endian(data)
+        };
+        let bits = potentials.bits(little_endian);
+        assert_eq!(bits.len(), 3);
+        assert_eq!(bits.clone().collect::<Vec<_>>(), expected);
+        assert_eq!(
+            bits.rev().collect::<Vec<_>>(),
+            expected.iter().rev().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(potentials.get_data(little_endian), expected);
+    }
+
+    #[rstest]
+    #[case(5, 4, Pad::Zero, "1010")]
+    #[case(0, 3, Pad::Zero, "000")]
+    #[case(15, 4, Pad::Zero, "1111")]
+    #[case(-1, 4, Pad::Sign, "1111")]
+    #[case(-8, 4, Pad::Sign, "0001")]
+    #[case(7, 4, Pad::Sign, "1110")]
+    #[case(-2, 70, Pad::Sign, &format!("0{}", "1".repeat(69)))]
+    #[case(i64::MAX, 64, Pad::Zero, &format!("{}0", "1".repeat(63)))]
+    fn test_potentials_with_width(
+        #[case] value: i64,
+        #[case] width: usize,
+        #[case] pad: Pad,
+        #[case] raw: &str,
+    ) {
+        let potentials = Potentials::with_width(value, width, pad);
+        assert_eq!(potentials.to_little_endian(Padding::Keep), raw);
+    }
+
+    #[rstest]
+    #[case(16, 4, Pad::Zero)]
+    #[case(-1, 4, Pad::Zero)]
+    #[case(8, 4, Pad::Sign)]
+    #[case(-9, 4, Pad::Sign)]
+    #[should_panic(expected = "does not fit")]
+    fn test_potentials_with_width_overflow(
+        #[case] value: i64,
+        #[case] width: usize,
+        #[case] pad: Pad,
+    ) {
+        Potentials::with_width(value, width, pad);
+    }
+
+    #[rstest]
+    #[case("101", Pad::Zero, "10100")]
+    #[case("101", Pad::Sign, "10111")]
+    #[case("110", Pad::Sign, "11000")]
+    fn test_potentials_pad(#[case] raw: &str, #[case] pad: Pad, #[case] expected: &str) {
+        let little = Potentials::from_little_endian(raw, Padding::Keep).pad(5, pad);
+        assert!(little.is_little_endian());
+        assert_eq!(little.to_little_endian(Padding::Keep), expected);
+        let big_raw: String = raw.chars().rev().collect();
+        let big = Potentials::from_big_endian(&big_raw, Padding::Keep).pad(5, pad);
+        assert!(!big.is_little_endian());
+        assert_eq!(big.to_little_endian(Padding::Keep), expected);
+    }
+
+    #[test]
+    fn test_potentials_pad_after_strip() {
+        let potentials = Potentials::from_little_endian("1100 0000", Padding::Strip);
+        assert_eq!(potentials.len(), 2);
+        let potentials = potentials.pad(8, Pad::Zero);
+        assert_eq!(potentials.to_little_endian(Padding::NIBBLE), "1100 0000");
+    }
+
+    #[rstest]
+    #[case("10110", 3, "101")]
+    #[case("10110", 5, "10110")]
+    #[case("10110", 0, "")]
+    fn test_potentials_truncate(#[case] raw: &str, #[case] width: usize, #[case] expected: &str) {
+        let little = Potentials::from_little_endian(raw, Padding::Keep).truncate(width);
+        assert_eq!(little.to_little_endian(Padding::Keep), expected);
+        let big_raw: String = raw.chars().rev().collect();
+        let big = Potentials::from_big_endian(&big_raw, Padding::Keep).truncate(width);
+        assert_eq!(big.to_little_endian(Padding::Keep), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "use pad to widen")]
+    fn test_potentials_truncate_wider() {
+        Potentials::from_little_endian("101", Padding::Keep).truncate(4);
+    }
+
+    #[rstest]
+    #[case(Endianness::Little, "1100", "0011")]
+    #[case(Endianness::Big, "0011", "1100")]
+    fn test_potentials_endianness(
+        #[case] endianness: Endianness,
+        #[case] little: &str,
+        #[case] big: &str,
+    ) {
+        let potentials = Potentials::new(vec![true, true, false, false], endianness);
+        assert_eq!(potentials.endianness(), endianness);
+        assert_eq!(potentials.to_little_endian(Padding::Keep), little);
+        assert_eq!(
+            potentials.to_endian(Endianness::Big),
+            potentials.get_data(false)
+        );
+        for target in [Endianness::Little, Endianness::Big] {
+            let converted = potentials.clone().into_endian(target);
+            assert_eq!(converted.endianness(), target);
+            assert_eq!(converted.to_big_endian(Padding::Keep), big);
+        }
+        assert_eq!(Endianness::of_little(endianness.is_little()), endianness);
+    }
 }
@@ -0,0 +1,137 @@
+use crate::circuit::{Pad, Potential, Potentials};
+
+macro_rules! word {
+    ($(#[$meta:meta])* $name:ident, $int:ty, $width:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        pub struct $name(Potentials);
+
+        impl $name {
+            /// The number of bits.
+            pub const WIDTH: usize = $width;
+
+            /// Wrap the Potentials, the length must be the width.
+            pub fn new(potentials: Potentials) -> Self {
+                assert!(
+                    potentials.len() == Self::WIDTH,
+                    "potentials must be {} bits",
+                    Self::WIDTH
+                );
+                Self(potentials)
+            }
+
+            /// Borrow the wrapped Potentials.
+            pub fn potentials(&self) -> &Potentials {
+                &self.0
+            }
+
+            /// Unwrap the Potentials.
+            pub fn into_potentials(self) -> Potentials {
+                self.0
+            }
+
+            /// Get the bits, the first bit is the lowest bit.
+            pub fn to_bits(&self) -> Vec<Potential> {
+                self.0.get_data(true)
+            }
+        }
+
+        impl From<$int> for $name {
+            fn from(value: $int) -> Self {
+                Self(Potentials::with_width(value as i64, Self::WIDTH, Pad::Zero))
+            }
+        }
+
+        impl From<$name> for $int {
+            fn from(word: $name) -> Self {
+                word.0
+                    .bits(true)
+                    .enumerate()
+                    .fold(0, |acc, (i, bit)| acc | ((bit as $int) << i))
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.0.bits(true).eq(other.0.bits(true))
+            }
+        }
+
+        impl Eq for $name {}
+    };
+}
+
+word!(
+    /// 8 bits Potentials.
+    Byte,
+    u8,
+    8
+);
+word!(
+    /// 16 bits Potentials.
+    Word16,
+    u16,
+    16
+);
+word!(
+    /// 32 bits Potentials.
+    Word32,
+    u32,
+    32
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::Padding;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0x00, "0000 0000")]
+    #[case(0x5a, "0101 1010")]
+    #[case(0xff, "1111 1111")]
+    fn test_byte(#[case] value: u8, #[case] raw: &str) {
+        let byte = Byte::from(value);
+        assert_eq!(byte.potentials().to_big_endian(Padding::NIBBLE), raw);
+        assert_eq!(u8::from(byte), value);
+    }
+
+    #[rstest]
+    #[case(0x0000)]
+    #[case(0x1234)]
+    #[case(0xffff)]
+    fn test_word16(#[case] value: u16) {
+        let word = Word16::from(value);
+        assert_eq!(word.to_bits().len(), Word16::WIDTH);
+        assert_eq!(u16::from(word), value);
+    }
+
+    #[rstest]
+    #[case(0x0000_0000)]
+    #[case(0xdead_beef)]
+    #[case(0xffff_ffff)]
+    fn test_word32(#[case] value: u32) {
+        let word = Word32::from(value);
+        assert_eq!(word.potentials().len(), 32);
+        assert_eq!(u32::from(word), value);
+    }
+
+    #[test]
+    fn test_word_new() {
+        let byte = Byte::new(Potentials::from_big_endian("1000 0001", Padding::Keep));
+        assert_eq!(byte, Byte::from(0x81));
+        assert!(byte.to_bits()[0]);
+        let big = Potentials::from_big_endian("0000 0001 0000 0010", Padding::Keep);
+        assert_eq!(u16::from(Word16::new(big)), 0x0102);
+        assert_eq!(
+            Word16::new(Word16::from(7).into_potentials()),
+            Word16::from(7)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "potentials must be 8 bits")]
+    fn test_word_new_width() {
+        Byte::new(Potentials::from_big_endian("1 0000 0001", Padding::Keep));
+    }
+}
@@ -1,6 +1,10 @@
 use crate::{
-    circuit::{ANDGate, ORGate, Potential, Wire, XORGate},
-    component::Component,
+    circuit::{ANDGate, NOTGate, ORGate, Potential, Wire, XORGate, layout::Layout},
+    component::{
+        Component,
+        datasheet::{Metadata, Timing},
+        port::Port,
+    },
 };
 
 /// a half adder in circuite.
@@ -19,7 +23,7 @@ use crate::{
 /// the carry bit is the and of the two bits.
 ///
 #[derive(Debug, Default, Clone)]
-struct HalfAdder {
+pub struct HalfAdder {
     input: [Wire; 2],
     output: [Wire; 2],
     and_gate: ANDGate,
@@ -30,6 +34,13 @@ impl Component for HalfAdder {
     fn get_pin_count(&self) -> (usize, usize) {
         (2, 2)
     }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "half adder",
+            description: "adds two bits, to a sum and a carry.",
+            timing: Timing::Combinational,
+        })
+    }
     fn set_pin_input(&mut self, position: usize, value: &Potential) {
         assert!(
             position < self.get_pin_count().0,
@@ -48,6 +59,15 @@ impl Component for HalfAdder {
         self.output[position].output()
     }
 
+    fn ports(&self) -> Vec<Port> {
+        vec![
+            Port::input("a", 0..1),
+            Port::input("b", 1..2),
+            Port::output("sum", 0..1),
+            Port::output("carry_out", 1..2),
+        ]
+    }
+
     fn update_state(&mut self) {
         self.and_gate
             .input(&self.input[0].output(), &self.input[1].output());
@@ -85,7 +105,7 @@ impl Component for HalfAdder {
 /// the first bit is the sum bit, and the second bit is the carry bit.
 
 #[derive(Debug, Default, Clone)]
-struct FullAdder {
+pub struct FullAdder {
     half_adder: [HalfAdder; 2],
     or_gate: ORGate,
     input: [Wire; 3],
@@ -95,6 +115,13 @@ impl Component for FullAdder {
     fn get_pin_count(&self) -> (usize, usize) {
         (3, 2)
     }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "full adder",
+            description: "adds two bits and a carry in, to a sum and a carry out.",
+            timing: Timing::Combinational,
+        })
+    }
     fn set_pin_input(&mut self, position: usize, value: &Potential) {
         assert!(
             position < self.get_pin_count().0,
@@ -111,10 +138,19 @@ impl Component for FullAdder {
         );
         self.output[position].output()
     }
+    fn ports(&self) -> Vec<Port> {
+        vec![
+            Port::input("a", 0..1),
+            Port::input("b", 1..2),
+            Port::input("carry_in", 2..3),
+            Port::output("sum", 0..1),
+            Port::output("carry_out", 1..2),
+        ]
+    }
     fn update_state(&mut self) {
-        self.half_adder[0].input(&vec![self.input[0].output(), self.input[1].output()]);
+        self.half_adder[0].input(&[self.input[0].output(), self.input[1].output()]);
         let out1 = self.half_adder[0].output();
-        self.half_adder[1].input(&vec![out1[0], self.input[2].output()]);
+        self.half_adder[1].input(&[out1[0], self.input[2].output()]);
         let out2 = self.half_adder[1].output();
         self.or_gate.input(&out1[1], &out2[1]);
         // Little-Endian
@@ -131,7 +167,8 @@ impl Component for FullAdder {
 /// [4 bit ripple carry adder example](https://upload.wikimedia.org/wikipedia/commons/5/5d/4-bit_ripple_carry_adder.svg)
 ///
 /// # input
-/// the first 1 bit is Carry from another adder, the next n bit is A and the last N bit is B
+/// the first 1 bit is Carry from another adder, the next n bit is A and the last N bit is B,
+/// see [`adder_inputs`].
 ///
 /// ```mermaid
 ///  ---
@@ -163,18 +200,30 @@ impl Component for FullAdder {
 ///  4: "carry"
 /// ```
 #[derive(Debug, Clone)]
-struct RippleCarryAdderN {
+pub struct RippleCarryAdderN {
     n_way: usize,
+    layout: Layout,
     input: Vec<Wire>,
     full_adders: Vec<FullAdder>,
     output: Vec<Wire>,
 }
 
+/// Get the layout of the inputs of a n-bit adder, the carry in, a and b.
+pub fn adder_inputs(n_way: usize) -> Layout {
+    Layout::default()
+        .field("carry_in", 1)
+        .field("a", n_way)
+        .field("b", n_way)
+}
+
 impl RippleCarryAdderN {
-    fn new(n_way: usize) -> Self {
+    pub fn new(n_way: usize) -> Self {
+        assert!(n_way > 0, "n_way must be greater than 0");
+        let layout = adder_inputs(n_way);
         Self {
             n_way,
-            input: vec![Wire::default(); 2 * n_way + 1],
+            input: vec![Wire::default(); layout.width()],
+            layout,
             full_adders: vec![FullAdder::default(); n_way],
             output: vec![Wire::default(); n_way + 1],
         }
@@ -185,6 +234,13 @@ impl Component for RippleCarryAdderN {
     fn get_pin_count(&self) -> (usize, usize) {
         (2 * self.n_way + 1, self.n_way + 1)
     }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "ripple carry adder",
+            description: "adds two n-bit numbers and a carry in, with a chain of full adders.",
+            timing: Timing::Combinational,
+        })
+    }
     fn set_pin_input(&mut self, position: usize, value: &Potential) {
         assert!(
             position < self.get_pin_count().0,
@@ -202,23 +258,36 @@ impl Component for RippleCarryAdderN {
         self.output[position].output()
     }
 
+    fn ports(&self) -> Vec<Port> {
+        let n = self.n_way;
+        vec![
+            Port::input("carry_in", self.layout.range("carry_in")),
+            Port::input("a", self.layout.range("a")),
+            Port::input("b", self.layout.range("b")),
+            Port::output("sum", 0..n),
+            Port::output("carry_out", n..n + 1),
+        ]
+    }
+
     fn update_state(&mut self) {
+        let a = self.layout.range("a");
+        let b = self.layout.range("b");
         // the fist full adder's carry bit is the carry bit from another adder
-        self.full_adders[0].input(&vec![
+        self.full_adders[0].input(&[
             // first bit of A
-            self.input[1].output(),
+            self.input[a.start].output(),
             // first bit of B
-            self.input[1 + self.n_way].output(),
+            self.input[b.start].output(),
             // carry
-            self.input[0].output(),
+            self.input[self.layout.range("carry_in").start].output(),
         ]);
         // cursor = (sum,carry)
         let mut cursor = self.full_adders[0].output();
         for i in 1..self.n_way {
             self.output[i - 1].input(&cursor[0]);
-            self.full_adders[i].input(&vec![
-                self.input[1 + i].output(),
-                self.input[1 + self.n_way + i].output(),
+            self.full_adders[i].input(&[
+                self.input[a.start + i].output(),
+                self.input[b.start + i].output(),
                 // carry
                 cursor[1],
             ]);
@@ -238,7 +307,8 @@ impl Component for RippleCarryAdderN {
 /// [4 bit lookahead carry adder example](https://en.wikipedia.org/wiki/Carry-lookahead_adder#/media/File:Four_bit_adder_with_carry_lookahead.svg)
 ///
 /// # input
-/// the first 1 bit is Carry from another adder, the next n bit is A and the last N bit is B
+/// the first 1 bit is Carry from another adder, the next n bit is A and the last N bit is B,
+/// see [`adder_inputs`].
 ///
 /// ```mermaid
 ///  ---
@@ -270,8 +340,9 @@ impl Component for RippleCarryAdderN {
 ///  4: "carry"
 /// ```
 #[derive(Debug, Clone)]
-struct LookAheadCarryAdderN {
+pub struct LookAheadCarryAdderN {
     n_way: usize,
+    layout: Layout,
     input: Vec<Wire>,
     output: Vec<Wire>,
     p: Vec<XORGate>,
@@ -282,10 +353,13 @@ struct LookAheadCarryAdderN {
 }
 
 impl LookAheadCarryAdderN {
-    fn new(n_way: usize) -> Self {
+    pub fn new(n_way: usize) -> Self {
+        assert!(n_way > 0, "n_way must be greater than 0");
+        let layout = adder_inputs(n_way);
         Self {
             n_way,
-            input: vec![Wire::default(); 2 * n_way + 1],
+            input: vec![Wire::default(); layout.width()],
+            layout,
             output: vec![Wire::default(); n_way + 1],
             p: vec![XORGate::default(); n_way],
             g: vec![ANDGate::default(); n_way],
@@ -294,16 +368,20 @@ impl LookAheadCarryAdderN {
             or: vec![ORGate::default(); n_way * (n_way + 1) / 2],
         }
     }
+    fn carry_in(&self) -> Potential {
+        self.input[self.layout.range("carry_in").start].output()
+    }
+
     fn get_carry(&mut self, index: usize) -> Potential {
         if index == 0 {
-            return self.input[0].output();
+            return self.carry_in();
         }
-        return self.get_carry_recursion(index, index * (index - 1) / 2);
+        self.get_carry_recursion(index, index * (index - 1) / 2)
     }
 
     fn get_carry_recursion(&mut self, index: usize, start: usize) -> Potential {
         // c0
-        let mut carry = self.input[0].output();
+        let mut carry = self.carry_in();
 
         // ci+1 = gi and (pi or ci)
         // ci will use i andGate + i orGate. the total usage is i*(i-1)/2
@@ -313,7 +391,7 @@ impl LookAheadCarryAdderN {
             // ci+1
             carry = self.or[start + i].output()
         }
-        return carry;
+        carry
     }
 }
 
@@ -338,20 +416,29 @@ impl Component for LookAheadCarryAdderN {
         self.output[position].output()
     }
 
+    fn ports(&self) -> Vec<Port> {
+        let n = self.n_way;
+        vec![
+            Port::input("carry_in", self.layout.range("carry_in")),
+            Port::input("a", self.layout.range("a")),
+            Port::input("b", self.layout.range("b")),
+            Port::output("sum", 0..n),
+            Port::output("carry_out", n..n + 1),
+        ]
+    }
+
     fn update_state(&mut self) {
+        let a = self.layout.range("a");
+        let b = self.layout.range("b");
         // c0
-        let mut ci = self.input[0].output();
+        let mut ci = self.carry_in();
         for i in 0..self.n_way {
+            let ai = self.input[a.start + i].output();
+            let bi = self.input[b.start + i].output();
             // pi = ai xor bi
-            self.p[i].input(
-                &self.input[i + 1].output(),
-                &self.input[i + 1 + self.n_way].output(),
-            );
+            self.p[i].input(&ai, &bi);
             // gi = ai and bi
-            self.g[i].input(
-                &self.input[i + 1].output(),
-                &self.input[i + 1 + self.n_way].output(),
-            );
+            self.g[i].input(&ai, &bi);
             // si = pi xor ci
             self.s[i].input(&self.p[i].output(), &ci);
             // set output
@@ -363,12 +450,153 @@ impl Component for LookAheadCarryAdderN {
     }
 }
 
+/// a n-bit adder-subtractor in circuit, which adds b to a, or subtracts b from a
+/// while mode is high.
+/// the input is 2*n+1 bits, and the output is n+2 bits.
+///
+/// one [`RippleCarryAdderN`] serves both: every bit of b goes through a xor gate
+/// with the mode, and the mode is the carry in, so a subtraction is the addition of
+/// the two's complement `!b + 1`.
+///
+/// ```ascii
+///              ┌──────────────┐
+/// mode─────────┤              ├──────sum
+/// a────────────┤   Add / Sub  ├──────carry
+/// b────────────┤              ├──────overflow
+///              └──────────────┘
+/// ```
+///
+/// # input
+/// the first 1 bit is the mode, the next n bit is A and the last n bit is B (the
+/// first bit is the lowest bit).
+///
+/// # output
+/// the first n bit is the sum, then the carry and the overflow.
+/// the carry is the carry out of the adder, which is the not borrow when
+/// subtracting, and the overflow is high when the signed result does not fit in
+/// n bits.
+#[derive(Debug, Clone)]
+pub struct AddSubN {
+    n_way: usize,
+    layout: Layout,
+    input: Vec<Wire>,
+    output: Vec<Wire>,
+    invert_gates: Vec<XORGate>,
+    adder: RippleCarryAdderN,
+    // the sign of the sum differs from the sign of a, while a and b have one sign
+    sign_changed: XORGate,
+    sign_differs: XORGate,
+    same_sign: NOTGate,
+    overflow: ANDGate,
+}
+
+impl AddSubN {
+    pub fn new(n_way: usize) -> Self {
+        assert!(n_way > 0, "n_way must be greater than 0");
+        let layout = Layout::default()
+            .field("mode", 1)
+            .field("a", n_way)
+            .field("b", n_way);
+        Self {
+            n_way,
+            input: vec![Wire::default(); layout.width()],
+            layout,
+            output: vec![Wire::default(); n_way + 2],
+            invert_gates: vec![XORGate::default(); n_way],
+            adder: RippleCarryAdderN::new(n_way),
+            sign_changed: XORGate::default(),
+            sign_differs: XORGate::default(),
+            same_sign: NOTGate::default(),
+            overflow: ANDGate::default(),
+        }
+    }
+}
+
+impl Component for AddSubN {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (2 * self.n_way + 1, self.n_way + 2)
+    }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "adder-subtractor",
+            description: "adds two n-bit numbers, or subtracts them while mode is high.",
+            timing: Timing::Combinational,
+        })
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+
+    fn ports(&self) -> Vec<Port> {
+        let n = self.n_way;
+        vec![
+            Port::input("mode", self.layout.range("mode")),
+            Port::input("a", self.layout.range("a")),
+            Port::input("b", self.layout.range("b")),
+            Port::output("sum", 0..n),
+            Port::output("carry_out", n..n + 1),
+            Port::output("overflow", n + 1..n + 2),
+        ]
+    }
+
+    fn update_state(&mut self) {
+        let n = self.n_way;
+        let a = self.layout.range("a");
+        let b = self.layout.range("b");
+        let mode = self.input[self.layout.range("mode").start].output();
+        let mut adder_input = vec![mode];
+        adder_input.extend(self.input[a.clone()].iter().map(|wire| wire.output()));
+        for (i, invert_gate) in self.invert_gates.iter_mut().enumerate() {
+            invert_gate.input(&self.input[b.start + i].output(), &mode);
+            adder_input.push(invert_gate.output());
+        }
+        self.adder.input(&adder_input);
+        for i in 0..=n {
+            self.output[i].input(&self.adder.get_pin_output(i));
+        }
+        let a_sign = self.input[a.end - 1].output();
+        self.sign_changed
+            .input(&a_sign, &self.adder.get_pin_output(n - 1));
+        self.sign_differs
+            .input(&a_sign, &self.invert_gates[n - 1].output());
+        self.same_sign.input(&self.sign_differs.output());
+        self.overflow
+            .input(&self.sign_changed.output(), &self.same_sign.output());
+        self.output[n + 1].input(&self.overflow.output());
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{circuit::Potentials, component::adder};
+    use crate::circuit::{Endianness, Pad, Padding, Potentials};
 
     use super::*;
     use rstest::rstest;
+    #[test]
+    fn test_ripple_carry_adder_bus() {
+        let mut adder = RippleCarryAdderN::new(4);
+        // the buses convert from the endianness of the value
+        adder.set_bus_input(1, &Potentials::from_big_endian("0110", Padding::Keep));
+        adder.set_bus_input(5, &Potentials::from_little_endian("1100", Padding::Keep));
+        adder.update_state();
+        let sum = adder.get_bus_output(0, 5);
+        assert_eq!(sum.endianness(), Endianness::BUS);
+        assert_eq!(sum.to_big_endian(Padding::Keep), "01001");
+    }
+
     #[test]
     fn test_half_adder_default() {
         let half_adder = HalfAdder::default();
@@ -446,9 +674,9 @@ mod tests {
     #[case("1 11 11", "11 1")]
     fn test_ripple_carry_adder_input(#[case] input: String, #[case] output: String) {
         let mut adder_2 = RippleCarryAdderN::new(2);
-        let i: Potentials = Potentials::from_little_endian(&input, false);
+        let i: Potentials = Potentials::from_little_endian(&input, Padding::Keep);
         adder_2.input(&i.get_data(true));
-        let o = Potentials::from_little_endian(&output, false);
+        let o = Potentials::from_little_endian(&output, Padding::Keep);
         assert_eq!(adder_2.output(), o.get_data(true));
     }
 
@@ -466,9 +694,42 @@ mod tests {
     #[case("1 11 11", "11 1")]
     fn test_look_ahead_carry_adder_input(#[case] input: String, #[case] output: String) {
         let mut adder_2 = LookAheadCarryAdderN::new(2);
-        let i: Potentials = Potentials::from_little_endian(&input, false);
+        let i: Potentials = Potentials::from_little_endian(&input, Padding::Keep);
         adder_2.input(&i.get_data(true));
-        let o = Potentials::from_little_endian(&output, false);
+        let o = Potentials::from_little_endian(&output, Padding::Keep);
         assert_eq!(adder_2.output(), o.get_data(true));
     }
+
+    #[test]
+    fn test_add_sub_exhaustive() {
+        let mut add_sub = AddSubN::new(4);
+        for mode in [0, 1] {
+            for a in 0..16i64 {
+                for b in 0..16i64 {
+                    add_sub.set_port("mode", &Potentials::with_width(mode, 1, Pad::Zero));
+                    add_sub.set_port("a", &Potentials::with_width(a, 4, Pad::Zero));
+                    add_sub.set_port("b", &Potentials::with_width(b, 4, Pad::Zero));
+                    add_sub.update_state();
+                    let (result, carry) = if mode == 0 {
+                        (a + b, a + b > 15)
+                    } else {
+                        (a - b, a >= b)
+                    };
+                    // the signed values of a and b
+                    let signed = |value: i64| if value > 7 { value - 16 } else { value };
+                    let signed_result = if mode == 0 {
+                        signed(a) + signed(b)
+                    } else {
+                        signed(a) - signed(b)
+                    };
+                    assert_eq!(
+                        add_sub.get_port("sum").get_data(true),
+                        Potentials::with_width(result.rem_euclid(16), 4, Pad::Zero).get_data(true)
+                    );
+                    assert_eq!(add_sub.get_pin_output(4), carry);
+                    assert_eq!(add_sub.get_pin_output(5), !(-8..8).contains(&signed_result));
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,317 @@
+use crate::{
+    circuit::{ANDGate, NOTGate, ORGate, Potential, Wire, XORGate},
+    component::{
+        Component,
+        adder::AddSubN,
+        big_gates::ORGateN,
+        comparator::ComparatorN,
+        datasheet::{Metadata, Timing},
+        decoder::DecoderN,
+        port::Port,
+    },
+};
+
+/// the operations of an [`ALU`], which are the values of its three op pins.
+pub mod op {
+    pub const ADD: usize = 0;
+    pub const SUB: usize = 1;
+    pub const AND: usize = 2;
+    pub const OR: usize = 3;
+    pub const XOR: usize = 4;
+    /// a shifted to the higher bit, the carry is the highest bit of a.
+    pub const SHL: usize = 5;
+    /// a shifted to the lower bit, the carry is the lowest bit of a.
+    pub const SHR: usize = 6;
+    pub const NOT: usize = 7;
+}
+
+/// a n-bit arithmetic logic unit in circuit.
+/// the input is 2*n+3 bits, and the output is n+6 bits.
+///
+/// every operation is computed at once: an [`AddSubN`] for the add and the sub, a
+/// word of and, or, xor and not gates, and a shifter which is only wires. the op is
+/// decoded by a [`DecoderN`], and every bit of the result is the or of the and of
+/// a decoded line and the bit of its operation. a [`ComparatorN`] compares a and b
+/// whatever the op is.
+///
+/// ```ascii
+///              ┌─────────────┐
+/// a────────────┤             ├──────result
+/// b────────────┤     ALU     ├──────carry, zero, overflow
+/// op───────────┤             ├──────equal, greater, less
+///              └─────────────┘
+/// ```
+///
+/// # input
+/// the n bits of a, the n bits of b and the 3 bits of the op, see [`op`] (the first
+/// bit is the lowest bit).
+///
+/// # output
+/// the n bits of the result, then the flags:
+///
+/// | flag | |
+/// |---|---|
+/// | carry | the carry out of the add, the not borrow of the sub, or the bit shifted out |
+/// | zero | the result is 0 |
+/// | overflow | the signed result of the add or the sub does not fit in n bits |
+/// | equal | a = b |
+/// | greater | a > b, unsigned |
+/// | less | a < b, unsigned |
+#[derive(Debug, Clone)]
+pub struct ALU {
+    width: usize,
+    input: Vec<Wire>,
+    output: Vec<Wire>,
+    decoder: DecoderN,
+    add_sub: AddSubN,
+    arithmetic: ORGate,
+    and_gates: Vec<ANDGate>,
+    or_gates: Vec<ORGate>,
+    xor_gates: Vec<XORGate>,
+    not_gates: Vec<NOTGate>,
+    // the and of every decoded line and the bit of its operation
+    select_gates: Vec<[ANDGate; 8]>,
+    result_gates: Vec<ORGateN>,
+    carry_gates: [ANDGate; 3],
+    carry: ORGateN,
+    overflow: ANDGate,
+    not_zero: ORGateN,
+    zero: NOTGate,
+    comparator: ComparatorN,
+}
+
+impl ALU {
+    pub fn new(width: usize) -> Self {
+        assert!(width > 1, "width must be greater than 1");
+        Self {
+            width,
+            input: vec![Wire::default(); 2 * width + 3],
+            output: vec![Wire::default(); width + 6],
+            decoder: DecoderN::new(3),
+            add_sub: AddSubN::new(width),
+            arithmetic: ORGate::default(),
+            and_gates: vec![ANDGate::default(); width],
+            or_gates: vec![ORGate::default(); width],
+            xor_gates: vec![XORGate::default(); width],
+            not_gates: vec![NOTGate::default(); width],
+            select_gates: (0..width).map(|_| Default::default()).collect(),
+            result_gates: vec![ORGateN::new(8); width],
+            carry_gates: Default::default(),
+            carry: ORGateN::new(3),
+            overflow: ANDGate::default(),
+            not_zero: ORGateN::new(width),
+            zero: NOTGate::default(),
+            comparator: ComparatorN::new(width),
+        }
+    }
+}
+
+impl Component for ALU {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (2 * self.width + 3, self.width + 6)
+    }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "arithmetic logic unit",
+            description: "computes the op of two n-bit numbers, with the carry, zero and overflow flags and the comparison.",
+            timing: Timing::Combinational,
+        })
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+    fn ports(&self) -> Vec<Port> {
+        let n = self.width;
+        vec![
+            Port::input("a", 0..n),
+            Port::input("b", n..2 * n),
+            Port::input("op", 2 * n..2 * n + 3),
+            Port::output("result", 0..n),
+            Port::output("carry", n..n + 1),
+            Port::output("zero", n + 1..n + 2),
+            Port::output("overflow", n + 2..n + 3),
+            Port::output("equal", n + 3..n + 4),
+            Port::output("greater", n + 4..n + 5),
+            Port::output("less", n + 5..n + 6),
+        ]
+    }
+    fn update_state(&mut self) {
+        let n = self.width;
+        let a: Vec<Potential> = self.input[..n].iter().map(|wire| wire.output()).collect();
+        let b: Vec<Potential> = self.input[n..2 * n]
+            .iter()
+            .map(|wire| wire.output())
+            .collect();
+        let op: Vec<Potential> = self.input[2 * n..]
+            .iter()
+            .map(|wire| wire.output())
+            .collect();
+        self.decoder.input(&op);
+        let line = |decoder: &DecoderN, op: usize| decoder.get_pin_output(op);
+
+        let mut add_sub_input = vec![line(&self.decoder, op::SUB)];
+        add_sub_input.extend(&a);
+        add_sub_input.extend(&b);
+        self.add_sub.input(&add_sub_input);
+
+        for i in 0..n {
+            self.and_gates[i].input(&a[i], &b[i]);
+            self.or_gates[i].input(&a[i], &b[i]);
+            self.xor_gates[i].input(&a[i], &b[i]);
+            self.not_gates[i].input(&a[i]);
+            let sources = [
+                self.add_sub.get_pin_output(i),
+                self.add_sub.get_pin_output(i),
+                self.and_gates[i].output(),
+                self.or_gates[i].output(),
+                self.xor_gates[i].output(),
+                i > 0 && a[i - 1],
+                i + 1 < n && a[i + 1],
+                self.not_gates[i].output(),
+            ];
+            for (j, select_gate) in self.select_gates[i].iter_mut().enumerate() {
+                select_gate.input(&self.decoder.get_pin_output(j), &sources[j]);
+                self.result_gates[i].set_pin_input(j, &select_gate.output());
+            }
+            self.result_gates[i].update_state();
+            self.output[i].input(&self.result_gates[i].get_pin_output(0));
+        }
+
+        // flags
+        self.arithmetic
+            .input(&line(&self.decoder, op::ADD), &line(&self.decoder, op::SUB));
+        self.carry_gates[0].input(&self.arithmetic.output(), &self.add_sub.get_pin_output(n));
+        self.carry_gates[1].input(&line(&self.decoder, op::SHL), &a[n - 1]);
+        self.carry_gates[2].input(&line(&self.decoder, op::SHR), &a[0]);
+        let carries: Vec<Potential> = self.carry_gates.iter().map(|gate| gate.output()).collect();
+        self.carry.input(&carries);
+        self.output[n].input(&self.carry.get_pin_output(0));
+        let result: Vec<Potential> = self.output[..n].iter().map(|wire| wire.output()).collect();
+        self.not_zero.input(&result);
+        self.zero.input(&self.not_zero.get_pin_output(0));
+        self.output[n + 1].input(&self.zero.output());
+        self.overflow.input(
+            &self.arithmetic.output(),
+            &self.add_sub.get_pin_output(n + 1),
+        );
+        self.output[n + 2].input(&self.overflow.output());
+
+        let mut comparator_input = a;
+        comparator_input.extend(b);
+        self.comparator.input(&comparator_input);
+        for k in 0..3 {
+            self.output[n + 3 + k].input(&self.comparator.get_pin_output(k));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::{Pad, Potentials};
+    use rstest::rstest;
+
+    /// run the op and get the result and the flags [carry, zero, overflow].
+    fn run(alu: &mut ALU, op: usize, a: i64, b: i64) -> (i64, [bool; 3]) {
+        let n = alu.width;
+        alu.set_port("a", &Potentials::with_width(a, n, Pad::Zero));
+        alu.set_port("b", &Potentials::with_width(b, n, Pad::Zero));
+        alu.set_port("op", &Potentials::with_width(op as i64, 3, Pad::Zero));
+        alu.update_state();
+        let result = alu
+            .get_port("result")
+            .get_data(true)
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (i, bit)| acc | (*bit as i64) << i);
+        let flags = alu.get_bus_output(n, 3).get_data(true);
+        (result, [flags[0], flags[1], flags[2]])
+    }
+
+    #[test]
+    fn test_alu_default() {
+        let alu = ALU::new(4);
+        assert_eq!(alu.output(), vec![false; 10]);
+    }
+
+    #[rstest]
+    // op, a, b => result, [carry, zero, overflow]
+    #[case(op::ADD, 0x0f, 0x01, 0x10, [false, false, false])]
+    #[case(op::ADD, 0xff, 0x01, 0x00, [true, true, false])]
+    #[case(op::ADD, 0x7f, 0x01, 0x80, [false, false, true])]
+    #[case(op::SUB, 0x05, 0x03, 0x02, [true, false, false])]
+    #[case(op::SUB, 0x03, 0x05, 0xfe, [false, false, false])]
+    #[case(op::SUB, 0x80, 0x01, 0x7f, [true, false, true])]
+    #[case(op::SUB, 0x42, 0x42, 0x00, [true, true, false])]
+    #[case(op::AND, 0xcc, 0xaa, 0x88, [false, false, false])]
+    #[case(op::AND, 0xf0, 0x0f, 0x00, [false, true, false])]
+    #[case(op::OR, 0xcc, 0xaa, 0xee, [false, false, false])]
+    #[case(op::XOR, 0xcc, 0xaa, 0x66, [false, false, false])]
+    #[case(op::SHL, 0x81, 0x00, 0x02, [true, false, false])]
+    #[case(op::SHR, 0x81, 0x00, 0x40, [true, false, false])]
+    #[case(op::SHR, 0x01, 0xff, 0x00, [true, true, false])]
+    #[case(op::NOT, 0x0f, 0x00, 0xf0, [false, false, false])]
+    fn test_alu_op(
+        #[case] op: usize,
+        #[case] a: i64,
+        #[case] b: i64,
+        #[case] result: i64,
+        #[case] flags: [bool; 3],
+    ) {
+        let mut alu = ALU::new(8);
+        assert_eq!(run(&mut alu, op, a, b), (result, flags));
+    }
+
+    #[test]
+    fn test_alu_exhaustive() {
+        let mut alu = ALU::new(4);
+        for op in 0..8 {
+            for a in 0..16i64 {
+                for b in 0..16i64 {
+                    let expected = match op {
+                        op::ADD => a + b,
+                        op::SUB => a - b,
+                        op::AND => a & b,
+                        op::OR => a | b,
+                        op::XOR => a ^ b,
+                        op::SHL => a << 1,
+                        op::SHR => a >> 1,
+                        _ => !a,
+                    } & 0xf;
+                    let carry = match op {
+                        op::ADD => a + b > 0xf,
+                        op::SUB => a >= b,
+                        op::SHL => a & 0x8 != 0,
+                        op::SHR => a & 0x1 != 0,
+                        _ => false,
+                    };
+                    // the signed values of a and b, in 4 bits
+                    let (sa, sb) = ((a ^ 0x8) - 0x8, (b ^ 0x8) - 0x8);
+                    let overflow = match op {
+                        op::ADD => !(-8..8).contains(&(sa + sb)),
+                        op::SUB => !(-8..8).contains(&(sa - sb)),
+                        _ => false,
+                    };
+                    let (result, flags) = run(&mut alu, op, a, b);
+                    assert_eq!(result, expected);
+                    assert_eq!(flags, [carry, expected == 0, overflow]);
+                    let comparison = alu.get_bus_output(7, 3).get_data(true);
+                    assert_eq!(comparison, vec![a == b, a > b, a < b]);
+                }
+            }
+        }
+    }
+}
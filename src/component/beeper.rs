@@ -0,0 +1,311 @@
+use crate::{
+    circuit::{ANDGate, NOTGate, Potential, Wire, XORGate, init},
+    component::{
+        Component, adder::RippleCarryAdderN, big_gates::ORGateN, counter::Incrementer,
+        edge::EdgeDetector,
+    },
+};
+
+/// a tone played by [`BeeperN`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToneEvent {
+    /// the clock cycles of a half period of the square wave.
+    pub divider: usize,
+    /// the clock cycles the tone lasts.
+    pub duration: usize,
+}
+
+impl ToneEvent {
+    /// Get the frequency of the tone in hz, when the beeper runs at `clock_hz`.
+    pub fn frequency(&self, clock_hz: f64) -> f64 {
+        clock_hz / (2 * self.divider) as f64
+    }
+
+    /// Get the length of the tone in seconds, when the beeper runs at `clock_hz`.
+    pub fn seconds(&self, clock_hz: f64) -> f64 {
+        self.duration as f64 / clock_hz
+    }
+}
+
+/// a n-bit square wave beeper in circuit.
+/// the input is 2n+2 bits, and the output is 2 bits.
+///
+/// the wave toggles every `divider` clock cycles, and the beeper stops after
+/// `duration` clock cycles. every tone started is recorded as a [`ToneEvent`],
+/// so the host can log or play the tones.
+///
+/// ```ascii
+///              divider  duration
+///                 │        │
+///            ┌────┴────────┴────┐
+/// clock──────┤                  ├──────wave
+///            │      Beeper      │
+/// start──────┤                  ├──────busy
+///            └──────────────────┘
+/// ```
+///
+/// # input
+/// the first bit is the clock, the second bit is the start, the next n bits are the
+/// divider and the last n bits are the duration (the first bit is the lowest bit).
+/// the divider and the duration are loaded on a rising edge of the clock while start is high.
+/// a divider of 0 behaves as 2^n.
+///
+/// # output
+/// the first bit is the square wave, which returns low when the tone stops,
+/// and the second bit is high while the tone plays.
+#[derive(Debug, Clone)]
+pub struct BeeperN {
+    n_way: usize,
+    input: Vec<Wire>,
+    wave: Wire,
+    busy: Wire,
+    divider: Vec<Wire>,
+    phase: Vec<Wire>,
+    remaining: Vec<Wire>,
+    clock_edge: EdgeDetector,
+    // phase + 1 == divider
+    phase_incrementer: Incrementer,
+    not_reached: NOTGate,
+    toggle: XORGate,
+    // remaining - 1 == 0
+    remaining_decrementer: RippleCarryAdderN,
+    not_zero: ORGateN,
+    wave_gate: ANDGate,
+    events: Vec<ToneEvent>,
+}
+
+impl BeeperN {
+    pub fn new(n_way: usize) -> Self {
+        assert!(n_way > 0, "n_way must be greater than 0");
+        Self {
+            n_way,
+            input: vec![Wire::default(); n_way * 2 + 2],
+            wave: Wire::default(),
+            busy: Wire::default(),
+            divider: init::power_up(n_way),
+            phase: init::power_up(n_way),
+            remaining: init::power_up(n_way),
+            clock_edge: EdgeDetector::default(),
+            phase_incrementer: Incrementer::new(n_way),
+            not_reached: NOTGate::default(),
+            toggle: XORGate::default(),
+            remaining_decrementer: RippleCarryAdderN::new(n_way),
+            not_zero: ORGateN::new(n_way),
+            wave_gate: ANDGate::default(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Get the tones started so far.
+    pub fn events(&self) -> &[ToneEvent] {
+        &self.events
+    }
+
+    /// Take the tones started so far, the log is cleared.
+    pub fn take_events(&mut self) -> Vec<ToneEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    fn value(wires: &[Wire]) -> usize {
+        wires
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (i, wire)| acc | ((wire.output() as usize) << i))
+    }
+}
+
+impl Component for BeeperN {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.n_way * 2 + 2, 2)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        if position == 0 {
+            self.wave.output()
+        } else {
+            self.busy.output()
+        }
+    }
+    fn update_state(&mut self) {
+        let n = self.n_way;
+        self.clock_edge.input(&[self.input[0].output()]);
+
+        // phase + 1, cleared when it reaches the divider
+        self.phase_incrementer.increment(&self.phase, &true);
+        let reached = self.phase_incrementer.matches(&self.divider);
+        self.not_reached.input(&reached);
+        let phase = self.phase_incrementer.next(&self.not_reached.output());
+        self.toggle.input(&self.wave.output(), &reached);
+
+        // remaining - 1, by adding all ones
+        let mut adder_input = vec![false];
+        adder_input.extend(self.remaining.iter().map(|wire| wire.output()));
+        adder_input.extend(vec![true; n]);
+        self.remaining_decrementer.input(&adder_input);
+        for i in 0..n {
+            self.not_zero
+                .set_pin_input(i, &self.remaining_decrementer.get_pin_output(i));
+        }
+        self.not_zero.update_state();
+        self.wave_gate
+            .input(&self.toggle.output(), &self.not_zero.get_pin_output(0));
+
+        if !self.clock_edge.is_rising() {
+            return;
+        }
+        if self.input[1].output() {
+            for i in 0..n {
+                self.divider[i].input(&self.input[i + 2].output());
+                self.remaining[i].input(&self.input[i + 2 + n].output());
+                self.phase[i].input(&false);
+            }
+            // a divider of 0 wraps the phase after 2^n cycles
+            let divider = match Self::value(&self.divider) {
+                0 => 1 << n,
+                divider => divider,
+            };
+            let event = ToneEvent {
+                divider,
+                duration: Self::value(&self.remaining),
+            };
+            let playing = event.duration > 0;
+            if playing {
+                self.events.push(event);
+            }
+            self.wave.input(&false);
+            self.busy.input(&playing);
+        } else if self.busy.output() {
+            for (i, bit) in phase.iter().enumerate() {
+                self.phase[i].input(bit);
+                self.remaining[i].input(&self.remaining_decrementer.get_pin_output(i));
+            }
+            self.wave.input(&self.wave_gate.output());
+            self.busy.input(&self.not_zero.get_pin_output(0));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn bits(value: usize, width: usize) -> Vec<Potential> {
+        (0..width).map(|i| (value >> i) & 1 == 1).collect()
+    }
+
+    /// run a full clock cycle and return the wave and busy outputs.
+    fn tick(beeper: &mut BeeperN, start: bool, divider: usize, duration: usize) -> Vec<Potential> {
+        let mut input = vec![false, start];
+        input.extend(bits(divider, beeper.n_way));
+        input.extend(bits(duration, beeper.n_way));
+        beeper.input(&input);
+        input[0] = true;
+        beeper.input(&input);
+        beeper.output()
+    }
+
+    #[test]
+    fn test_beeper_default() {
+        let beeper = BeeperN::new(4);
+        assert_eq!(beeper.get_pin_count(), (10, 2));
+        assert_eq!(beeper.output(), vec![false, false]);
+        assert!(beeper.events().is_empty());
+    }
+
+    #[rstest]
+    #[case(1, 6, vec![true, false, true, false, true, false])]
+    #[case(2, 7, vec![false, true, true, false, false, true, false])]
+    #[case(3, 8, vec![false, false, true, true, true, false, false, false])]
+    fn test_beeper_wave(
+        #[case] divider: usize,
+        #[case] duration: usize,
+        #[case] expected: Vec<Potential>,
+    ) {
+        let mut beeper = BeeperN::new(4);
+        assert_eq!(
+            tick(&mut beeper, true, divider, duration),
+            vec![false, true]
+        );
+        let mut wave = Vec::new();
+        for _ in 0..duration {
+            let output = tick(&mut beeper, false, 0, 0);
+            wave.push(output[0]);
+        }
+        assert_eq!(wave, expected);
+        // the tone stops after the duration
+        assert_eq!(beeper.output(), vec![false, false]);
+        assert_eq!(tick(&mut beeper, false, 0, 0), vec![false, false]);
+    }
+
+    #[test]
+    fn test_beeper_busy() {
+        let mut beeper = BeeperN::new(3);
+        tick(&mut beeper, true, 1, 3);
+        assert!(tick(&mut beeper, false, 0, 0)[1]);
+        assert!(tick(&mut beeper, false, 0, 0)[1]);
+        assert!(!tick(&mut beeper, false, 0, 0)[1]);
+        // a zero duration plays nothing
+        assert_eq!(tick(&mut beeper, true, 1, 0), vec![false, false]);
+    }
+
+    #[test]
+    fn test_beeper_events() {
+        let mut beeper = BeeperN::new(8);
+        tick(&mut beeper, true, 100, 200);
+        tick(&mut beeper, false, 0, 0);
+        tick(&mut beeper, true, 50, 0);
+        tick(&mut beeper, true, 25, 10);
+        assert_eq!(
+            beeper.take_events(),
+            vec![
+                ToneEvent {
+                    divider: 100,
+                    duration: 200
+                },
+                ToneEvent {
+                    divider: 25,
+                    duration: 10
+                },
+            ]
+        );
+        assert!(beeper.events().is_empty());
+    }
+
+    #[test]
+    fn test_beeper_event_zero_divider() {
+        let mut beeper = BeeperN::new(4);
+        tick(&mut beeper, true, 0, 3);
+        let events = beeper.take_events();
+        assert_eq!(
+            events,
+            vec![ToneEvent {
+                divider: 16,
+                duration: 3
+            }]
+        );
+        assert_eq!(events[0].frequency(32.0), 1.0);
+    }
+
+    #[test]
+    fn test_tone_event_frequency() {
+        let event = ToneEvent {
+            divider: 100,
+            duration: 500,
+        };
+        assert_eq!(event.frequency(88_000.0), 440.0);
+        assert_eq!(event.seconds(1_000.0), 0.5);
+    }
+}
@@ -89,7 +89,8 @@ pub struct ANDGateN {
 }
 
 impl ANDGateN {
-    fn new(n_way: usize) -> Self {
+    pub fn new(n_way: usize) -> Self {
+        assert!(n_way > 0, "n_way must be greater than 0");
         Self {
             n_way,
             input: vec![Wire::default(); n_way],
@@ -123,15 +124,14 @@ impl Component for ANDGateN {
     }
 
     fn update_state(&mut self) {
-        self.and_gate[0].input(&self.input[0].output(), &self.input[1].output());
-        {}
-        for i in 1..self.n_way - 1 {
+        let mut cursor = self.input[0].output();
+        for i in 1..self.n_way {
             // use tmp variable avoid borrow problem
-            let tmp_1 = &self.and_gate[i - 1].output();
-            let tmp_2 = &self.input[i + 1].output();
-            self.and_gate[i].input(tmp_1, tmp_2);
+            let tmp = &self.input[i].output();
+            self.and_gate[i - 1].input(&cursor, tmp);
+            cursor = self.and_gate[i - 1].output();
         }
-        self.output.input(&self.and_gate[self.n_way - 2].output());
+        self.output.input(&cursor);
     }
 }
 
@@ -146,7 +146,8 @@ pub struct ORGateN {
 }
 
 impl ORGateN {
-    fn new(n_way: usize) -> Self {
+    pub fn new(n_way: usize) -> Self {
+        assert!(n_way > 0, "n_way must be greater than 0");
         Self {
             n_way,
             input: vec![Wire::default(); n_way],
@@ -180,14 +181,133 @@ impl Component for ORGateN {
     }
 
     fn update_state(&mut self) {
-        self.or_gate[0].input(&self.input[0].output(), &self.input[1].output());
-        for i in 1..self.n_way - 1 {
+        let mut cursor = self.input[0].output();
+        for i in 1..self.n_way {
             // use tmp variable avoid borrow problem
-            let tmp_1 = &self.or_gate[i - 1].output();
-            let tmp_2 = &self.input[i + 1].output();
-            self.or_gate[i].input(tmp_1, tmp_2);
+            let tmp = &self.input[i].output();
+            self.or_gate[i - 1].input(&cursor, tmp);
+            cursor = self.or_gate[i - 1].output();
+        }
+        self.output.input(&cursor);
+    }
+}
+
+/// 3-input majority gate.
+/// the output is high when at least 2 of the 3 inputs are high.
+///
+/// out = (a and b) or (b and c) or (a and c)
+#[derive(Debug, Default, Clone)]
+pub struct MajorityGate3 {
+    input: [Wire; 3],
+    and_gate: [ANDGate; 3],
+    big_or: ORGate3,
+    output: Wire,
+}
+
+impl Component for MajorityGate3 {
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output.output()
+    }
+
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+
+    fn update_state(&mut self) {
+        self.and_gate[0].input(&self.input[0].output(), &self.input[1].output());
+        self.and_gate[1].input(&self.input[1].output(), &self.input[2].output());
+        self.and_gate[2].input(&self.input[0].output(), &self.input[2].output());
+        self.big_or.input(&[
+            self.and_gate[0].output(),
+            self.and_gate[1].output(),
+            self.and_gate[2].output(),
+        ]);
+        self.output.input(&self.big_or.get_pin_output(0));
+    }
+
+    fn get_pin_count(&self) -> (usize, usize) {
+        (3, 1)
+    }
+}
+
+/// N way-input threshold gate.
+/// the output is high when at least k of the n inputs are high.
+///
+/// the gate is built as a grid of and/or gates, where the cell (i, j) is high
+/// when at least j of the first i inputs are high:
+///
+/// t(i, j) = t(i-1, j) or (input(i) and t(i-1, j-1))
+///
+/// with t(i, 0) = 1 and t(0, j) = 0 for j > 0.
+#[derive(Debug, Clone)]
+pub struct ThresholdGate {
+    n_way: usize,
+    k: usize,
+    input: Vec<Wire>,
+    and_gate: Vec<Vec<ANDGate>>,
+    or_gate: Vec<Vec<ORGate>>,
+    output: Wire,
+}
+
+impl ThresholdGate {
+    pub fn new(n_way: usize, k: usize) -> Self {
+        Self {
+            n_way,
+            k,
+            input: vec![Wire::default(); n_way],
+            and_gate: vec![vec![ANDGate::default(); k]; n_way],
+            or_gate: vec![vec![ORGate::default(); k]; n_way],
+            output: Wire::default(),
         }
-        self.output.input(&self.or_gate[self.n_way - 2].output());
+    }
+}
+
+impl Component for ThresholdGate {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.n_way, 1)
+    }
+
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output.output()
+    }
+
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+
+    fn update_state(&mut self) {
+        // row[j] = t(i, j), start with t(0, j)
+        let mut row: Vec<Potential> = (0..=self.k).map(|j| j == 0).collect();
+        for i in 0..self.n_way {
+            let mut next = row.clone();
+            for j in 1..=self.k {
+                self.and_gate[i][j - 1].input(&self.input[i].output(), &row[j - 1]);
+                self.or_gate[i][j - 1].input(&row[j], &self.and_gate[i][j - 1].output());
+                next[j] = self.or_gate[i][j - 1].output();
+            }
+            row = next;
+        }
+        self.output.input(&row[self.k]);
     }
 }
 
@@ -276,6 +396,15 @@ mod tests {
         assert_eq!(and_gate_3.output(), vec![d]);
     }
 
+    #[rstest]
+    #[case(true)]
+    #[case(false)]
+    fn test_and_gate_n_1(#[case] a: bool) {
+        let mut and_gate = ANDGateN::new(1);
+        and_gate.input(&[a]);
+        assert_eq!(and_gate.output(), vec![a]);
+    }
+
     #[test]
     fn test_or_gate_n_3_default() {
         let or_gate: ORGateN = ORGateN::new(3);
@@ -302,4 +431,78 @@ mod tests {
         or_gate_3.update_state();
         assert_eq!(or_gate_3.output(), vec![d]);
     }
+
+    #[rstest]
+    #[case(true)]
+    #[case(false)]
+    fn test_or_gate_n_1(#[case] a: bool) {
+        let mut or_gate = ORGateN::new(1);
+        or_gate.input(&[a]);
+        assert_eq!(or_gate.output(), vec![a]);
+    }
+
+    #[test]
+    fn test_majority_gate_3_default() {
+        let majority_gate = MajorityGate3::default();
+        assert_eq!(majority_gate.output(), vec![false]);
+    }
+
+    #[rstest]
+    #[case(true, true, true, true)]
+    #[case(true, true, false, true)]
+    #[case(true, false, true, true)]
+    #[case(true, false, false, false)]
+    #[case(false, true, true, true)]
+    #[case(false, true, false, false)]
+    #[case(false, false, true, false)]
+    #[case(false, false, false, false)]
+    fn test_majority_gate_3_with_truth_table(
+        #[case] a: bool,
+        #[case] b: bool,
+        #[case] c: bool,
+        #[case] d: bool,
+    ) {
+        let mut majority_gate = MajorityGate3::default();
+        majority_gate.input(&[a, b, c]);
+        assert_eq!(majority_gate.output(), vec![d]);
+    }
+
+    #[test]
+    fn test_threshold_gate_default() {
+        let threshold_gate = ThresholdGate::new(4, 2);
+        assert_eq!(threshold_gate.output(), vec![false]);
+    }
+
+    #[rstest]
+    #[case(4, 0)]
+    #[case(4, 1)]
+    #[case(4, 2)]
+    #[case(4, 3)]
+    #[case(4, 4)]
+    #[case(4, 5)]
+    #[case(1, 1)]
+    #[case(5, 3)]
+    fn test_threshold_gate_with_all_inputs(#[case] n: usize, #[case] k: usize) {
+        let mut threshold_gate = ThresholdGate::new(n, k);
+        for value in 0..(1usize << n) {
+            let input: Vec<Potential> = (0..n).map(|i| (value >> i) & 1 == 1).collect();
+            threshold_gate.input(&input);
+            let expected = value.count_ones() as usize >= k;
+            assert_eq!(threshold_gate.output(), vec![expected], "input {:?}", input);
+        }
+    }
+
+    #[rstest]
+    #[case(vec![true, true, false, false], vec![true])]
+    #[case(vec![true, false, false, false], vec![false])]
+    #[case(vec![false, true, false, true], vec![true])]
+    #[case(vec![false, false, false, false], vec![false])]
+    fn test_threshold_gate_boundary(
+        #[case] input: Vec<Potential>,
+        #[case] expected: Vec<Potential>,
+    ) {
+        let mut threshold_gate = ThresholdGate::new(4, 2);
+        threshold_gate.input(&input);
+        assert_eq!(threshold_gate.output(), expected);
+    }
 }
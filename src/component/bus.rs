@@ -0,0 +1,312 @@
+use crate::{
+    circuit::{ANDGate, NOTGate, ORGate, Potential, Wire},
+    component::{
+        Component,
+        big_gates::{ANDGate3, ORGateN},
+        port::Port,
+    },
+};
+
+/// the weak resistor of a [`SharedBus`], which sets the line when no driver drives it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pull {
+    /// the line is high when it is not driven.
+    Up,
+    /// the line is low when it is not driven.
+    Down,
+    /// the line floats when it is not driven, it reads low.
+    None,
+}
+
+/// the output mode of a driver of a [`SharedBus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Drive {
+    /// the driver drives the line high and low.
+    PushPull,
+    /// open collector, the driver only pulls the line low and releases it for a high.
+    OpenDrain,
+    /// open emitter, the driver only pulls the line high and releases it for a low.
+    OpenSource,
+}
+
+/// a driver of a [`SharedBus`], with the gates which decide what it drives.
+#[derive(Debug, Clone)]
+struct Driver {
+    drive: Drive,
+    attached: bool,
+    attached_enable: ANDGate,
+    not_value: NOTGate,
+    drive_low: ANDGate,
+    drive_high: ANDGate,
+}
+
+impl Driver {
+    fn new(drive: Drive) -> Self {
+        Self {
+            drive,
+            attached: true,
+            attached_enable: ANDGate::default(),
+            not_value: NOTGate::default(),
+            drive_low: ANDGate::default(),
+            drive_high: ANDGate::default(),
+        }
+    }
+
+    /// Get whether the driver drives the line (low, high).
+    fn resolve(&mut self, enable: &Potential, value: &Potential) -> (Potential, Potential) {
+        // a detached driver releases the line whatever its pins are
+        self.attached_enable.input(enable, &self.attached);
+        let enable = self.attached_enable.output();
+        self.not_value.input(value);
+        self.drive_low.input(&enable, &self.not_value.output());
+        self.drive_high.input(&enable, value);
+        (
+            self.drive != Drive::OpenSource && self.drive_low.output(),
+            self.drive != Drive::OpenDrain && self.drive_high.output(),
+        )
+    }
+}
+
+/// a line shared by several drivers, with a weak pull resistor.
+/// the input is 2 bits per driver, and the output is 3 bits.
+///
+/// open drain drivers with a pull up make a wired-AND line, e.g. a shared interrupt
+/// line or the lines of i2c, where any driver pulls the line low. open source
+/// drivers with a pull down make a wired-OR line.
+///
+/// a driver which drives low and another which drives high at the same time is a
+/// contention, which burns a real chip. the line reads low then, and the contention
+/// output is high.
+///
+/// every driver is a slot, which a device is attached to or detached from between the
+/// steps of a simulation, like a cartridge. a detached slot releases the line, and the
+/// pins stay, so the bus is not rebuilt.
+///
+/// # input
+/// the enable and the value of every driver, in the order of the drivers. an open
+/// drain driver drives the line while enable is high and value is low, an open source
+/// driver while enable and value are high.
+///
+/// # output
+/// the level of the line, the contention and the floating, which is high when no
+/// driver drives the line and there is no pull resistor.
+///
+/// # truth table
+/// 2 open drain drivers with a pull up, enabled.
+///
+/// | V0 | V1 | LINE |
+/// |---|---|---|
+/// | 0 | 0 | 0 |
+/// | 1 | 0 | 0 |
+/// | 0 | 1 | 0 |
+/// | 1 | 1 | 1 |
+#[derive(Debug, Clone)]
+pub struct SharedBus {
+    pull: Pull,
+    input: Vec<Wire>,
+    output: [Wire; 3],
+    drivers: Vec<Driver>,
+    any_low: ORGateN,
+    any_high: ORGateN,
+    contention: ANDGate,
+    driven: ORGate,
+    not_driven: NOTGate,
+    not_low: NOTGate,
+    high: ORGate,
+    level: ANDGate,
+    floating: ANDGate3,
+}
+
+impl SharedBus {
+    pub fn new(pull: Pull, drives: &[Drive]) -> Self {
+        assert!(!drives.is_empty(), "drives must not be empty");
+        Self {
+            pull,
+            input: vec![Wire::default(); drives.len() * 2],
+            output: [Wire::default(); 3],
+            drivers: drives.iter().map(|drive| Driver::new(*drive)).collect(),
+            any_low: ORGateN::new(drives.len()),
+            any_high: ORGateN::new(drives.len()),
+            contention: ANDGate::default(),
+            driven: ORGate::default(),
+            not_driven: NOTGate::default(),
+            not_low: NOTGate::default(),
+            high: ORGate::default(),
+            level: ANDGate::default(),
+            floating: ANDGate3::default(),
+        }
+    }
+
+    /// Create a wired-AND line of open drain drivers with a pull up.
+    pub fn wired_and(drivers: usize) -> Self {
+        Self::new(Pull::Up, &vec![Drive::OpenDrain; drivers])
+    }
+
+    /// Create a wired-OR line of open source drivers with a pull down.
+    pub fn wired_or(drivers: usize) -> Self {
+        Self::new(Pull::Down, &vec![Drive::OpenSource; drivers])
+    }
+
+    /// Attach a device of the drive to the slot, the line is settled again.
+    pub fn attach(&mut self, slot: usize, drive: Drive) {
+        assert!(
+            slot < self.drivers.len(),
+            "slot must be less than {}",
+            self.drivers.len()
+        );
+        self.drivers[slot].drive = drive;
+        self.drivers[slot].attached = true;
+        self.update_state();
+    }
+
+    /// Detach the device of the slot, which releases the line, the line is settled
+    /// again.
+    pub fn detach(&mut self, slot: usize) {
+        assert!(
+            slot < self.drivers.len(),
+            "slot must be less than {}",
+            self.drivers.len()
+        );
+        self.drivers[slot].attached = false;
+        self.update_state();
+    }
+
+    /// Whether a device is attached to the slot.
+    pub fn is_attached(&self, slot: usize) -> bool {
+        self.drivers[slot].attached
+    }
+}
+
+impl Component for SharedBus {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.input.len(), 3)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+    fn ports(&self) -> Vec<Port> {
+        vec![
+            Port::input("drivers", 0..self.input.len()),
+            Port::output("line", 0..1),
+            Port::output("contention", 1..2),
+            Port::output("floating", 2..3),
+        ]
+    }
+    fn update_state(&mut self) {
+        for (i, driver) in self.drivers.iter_mut().enumerate() {
+            let (low, high) =
+                driver.resolve(&self.input[i * 2].output(), &self.input[i * 2 + 1].output());
+            self.any_low.set_pin_input(i, &low);
+            self.any_high.set_pin_input(i, &high);
+        }
+        self.any_low.update_state();
+        self.any_high.update_state();
+        let low = self.any_low.get_pin_output(0);
+        let high = self.any_high.get_pin_output(0);
+
+        self.contention.input(&low, &high);
+        self.driven.input(&low, &high);
+        self.not_driven.input(&self.driven.output());
+
+        // a low driver wins, otherwise a high driver or the pull up sets the line
+        self.not_low.input(&low);
+        self.high.input(&high, &(self.pull == Pull::Up));
+        self.level
+            .input(&self.not_low.output(), &self.high.output());
+        self.floating.input(&[
+            self.not_driven.output(),
+            self.pull != Pull::Up,
+            self.pull != Pull::Down,
+        ]);
+
+        self.output[0].input(&self.level.output());
+        self.output[1].input(&self.contention.output());
+        self.output[2].input(&self.floating.get_pin_output(0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::truth_table::TruthTable;
+    use rstest::rstest;
+
+    #[test]
+    fn test_shared_bus_docs() {
+        let rows: Vec<Vec<Potential>> = (0..4)
+            .map(|i| vec![true, i & 1 == 1, true, i & 2 == 2])
+            .collect();
+        let mut bus = SharedBus::wired_and(2);
+        let table = TruthTable::of_rows(&mut bus, &rows);
+        let line: Vec<bool> = table.rows().iter().map(|(_, output)| output[0]).collect();
+        assert_eq!(line, vec![false, false, false, true]);
+    }
+
+    #[rstest]
+    // [enable, value] per driver => [line, contention, floating]
+    #[case(Pull::Up, &[false, false, false, false], [true, false, false])]
+    #[case(Pull::Up, &[true, false, false, false], [false, false, false])]
+    #[case(Pull::Up, &[true, true, true, true], [true, false, false])]
+    #[case(Pull::Down, &[false, false, false, false], [false, false, false])]
+    #[case(Pull::None, &[false, true, false, true], [false, false, true])]
+    fn test_shared_bus_open_drain(
+        #[case] pull: Pull,
+        #[case] input: &[Potential],
+        #[case] expected: [Potential; 3],
+    ) {
+        let mut bus = SharedBus::new(pull, &[Drive::OpenDrain; 2]);
+        bus.input(input);
+        assert_eq!(bus.output(), expected.to_vec());
+    }
+
+    #[rstest]
+    #[case(&[false, false, false, false], [false, false, false])]
+    #[case(&[true, true, false, false], [true, false, false])]
+    #[case(&[true, true, true, true], [true, false, false])]
+    fn test_shared_bus_wired_or(#[case] input: &[Potential], #[case] expected: [Potential; 3]) {
+        let mut bus = SharedBus::wired_or(2);
+        bus.input(input);
+        assert_eq!(bus.output(), expected.to_vec());
+    }
+
+    #[rstest]
+    // a push pull driver against an open drain driver
+    #[case(&[true, true, true, false], [false, true, false])]
+    #[case(&[true, true, true, true], [true, false, false])]
+    #[case(&[true, false, false, false], [false, false, false])]
+    #[case(&[false, false, false, false], [false, false, true])]
+    fn test_shared_bus_contention(#[case] input: &[Potential], #[case] expected: [Potential; 3]) {
+        let mut bus = SharedBus::new(Pull::None, &[Drive::PushPull, Drive::OpenDrain]);
+        bus.input(input);
+        assert_eq!(bus.output(), expected.to_vec());
+    }
+
+    #[test]
+    fn test_shared_bus_hot_plug() {
+        let mut bus = SharedBus::new(Pull::Up, &[Drive::PushPull, Drive::OpenDrain]);
+        bus.input(&[true, true, true, false]);
+        assert_eq!(bus.output(), vec![false, true, false]);
+        // the open drain device is unplugged with its pins still low
+        bus.detach(1);
+        assert!(!bus.is_attached(1));
+        assert_eq!(bus.output(), vec![true, false, false]);
+        bus.detach(0);
+        assert_eq!(bus.output(), vec![true, false, false]);
+        bus.attach(0, Drive::OpenDrain);
+        bus.input(&[true, false, true, false]);
+        assert_eq!(bus.output(), vec![false, false, false]);
+    }
+}
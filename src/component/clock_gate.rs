@@ -0,0 +1,209 @@
+use crate::{
+    circuit::{ANDGate, NOTGate, Potential, Wire, init},
+    component::{Component, edge::EdgeDetector},
+};
+
+/// an integrated clock gating cell in circuit.
+/// the input is 2 bits, and the output is 1 bit.
+///
+/// a latch holds the enable while the clock is high, so the gated clock has no
+/// glitch when the enable changes in the middle of a cycle. the registers behind a
+/// disabled gate see no clock edge, and do not toggle at all, which saves the power
+/// of the clock tree.
+///
+/// ```ascii
+///             ┌───────┐
+/// enable──────┤ latch ├────┐  ┌─────┐
+///          ┌──┤ (low) │    └──┤     │
+///          │  └───────┘       │ AND ├──────gated clock
+/// clock────┴──────────────────┤     │
+///                             └─────┘
+/// ```
+///
+/// # input
+/// the clock and the enable.
+///
+/// # output
+/// the gated clock.
+#[derive(Debug, Default, Clone)]
+pub struct ClockGate {
+    input: [Wire; 2],
+    output: Wire,
+    latch: Wire,
+    not_clock: NOTGate,
+    gate: ANDGate,
+}
+
+impl Component for ClockGate {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (2, 1)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output.output()
+    }
+    fn update_state(&mut self) {
+        let clock = self.input[0].output();
+        // the latch is transparent while the clock is low
+        self.not_clock.input(&clock);
+        if self.not_clock.output() {
+            self.latch.input(&self.input[1].output());
+        }
+        self.gate.input(&clock, &self.latch.output());
+        self.output.input(&self.gate.output());
+    }
+}
+
+/// a n-bit register behind a [`ClockGate`].
+/// the input is n+2 bits, and the output is n bits.
+///
+/// # input
+/// the clock, the enable and the n bits of data. the register loads the data on the
+/// rising edge of the clock while enable is high.
+///
+/// # output
+/// the n bits of the register.
+#[derive(Debug, Clone)]
+pub struct GatedRegisterN {
+    n_way: usize,
+    input: Vec<Wire>,
+    register: Vec<Wire>,
+    clock_gate: ClockGate,
+    clock_edge: EdgeDetector,
+}
+
+impl GatedRegisterN {
+    pub fn new(n_way: usize) -> Self {
+        assert!(n_way > 0, "n_way must be greater than 0");
+        Self {
+            n_way,
+            input: vec![Wire::default(); n_way + 2],
+            register: init::power_up(n_way),
+            clock_gate: ClockGate::default(),
+            clock_edge: EdgeDetector::default(),
+        }
+    }
+
+    /// Get the gated clock, which the bits of the register see.
+    pub fn gated_clock(&self) -> Potential {
+        self.clock_gate.get_pin_output(0)
+    }
+}
+
+impl Component for GatedRegisterN {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.n_way + 2, self.n_way)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.register[position].output()
+    }
+    fn update_state(&mut self) {
+        self.clock_gate
+            .input(&[self.input[0].output(), self.input[1].output()]);
+        self.clock_edge.input(&[self.gated_clock()]);
+        if self.clock_edge.is_rising() {
+            for i in 0..self.n_way {
+                let bit = self.input[2 + i].output();
+                self.register[i].input(&bit);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::toggle::ToggleCounter;
+    use rstest::rstest;
+
+    #[rstest]
+    // [clock, enable] => gated clock, from the reset state
+    #[case(&[[false, true], [true, true]], true)]
+    #[case(&[[false, false], [true, false]], false)]
+    // the enable is latched while the clock is high
+    #[case(&[[false, true], [true, true], [true, false]], true)]
+    #[case(&[[false, false], [true, false], [true, true]], false)]
+    fn test_clock_gate(#[case] inputs: &[[Potential; 2]], #[case] expected: Potential) {
+        let mut gate = ClockGate::default();
+        for input in inputs {
+            gate.input(input);
+        }
+        assert_eq!(gate.output(), vec![expected]);
+    }
+
+    fn tick(register: &mut GatedRegisterN, enable: bool, data: usize) -> Vec<Potential> {
+        let bits: Vec<Potential> = (0..register.n_way).map(|i| (data >> i) & 1 == 1).collect();
+        for clock in [false, true] {
+            let mut input = vec![clock, enable];
+            input.extend(bits.iter());
+            register.input(&input);
+        }
+        register.output()
+    }
+
+    #[test]
+    fn test_gated_register() {
+        let mut register = GatedRegisterN::new(4);
+        assert_eq!(
+            tick(&mut register, true, 0b1010),
+            vec![false, true, false, true]
+        );
+        assert_eq!(
+            tick(&mut register, false, 0b0101),
+            vec![false, true, false, true]
+        );
+        assert_eq!(
+            tick(&mut register, true, 0b0011),
+            vec![true, true, false, false]
+        );
+    }
+
+    #[test]
+    fn test_gated_register_toggles() {
+        // the register is written once every 8 cycles
+        let mut register = GatedRegisterN::new(8);
+        let mut free = ToggleCounter::default();
+        let mut gated = ToggleCounter::default();
+        for cycle in 0..64 {
+            let enable = cycle % 8 == 0;
+            for clock in [false, true] {
+                let mut input = vec![clock, enable];
+                input.extend((0..8).map(|i| (cycle >> i) & 1 == 1));
+                register.input(&input);
+                free.observe(&[clock]);
+                gated.observe(&[register.gated_clock()]);
+            }
+        }
+        assert_eq!(free.toggles(), 127);
+        assert_eq!(gated.toggles(), 16);
+        assert_eq!(
+            register.output(),
+            vec![false, false, false, true, true, true, false, false]
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{
+    circuit::{ANDGate, NOTGate, ORGate, Potential, Wire, XORGate},
+    component::{
+        Component,
+        datasheet::{Metadata, Timing},
+        port::Port,
+    },
+};
+
+/// a n-bit magnitude comparator in circuit.
+/// the input is 2*n bits (2*n+3 bits when cascaded), and the output is 3 bits.
+///
+/// the bits are compared from the lowest bit up: a higher bit which differs decides
+/// the result, and equal bits pass the result of the lower bits. the result of the
+/// lowest bit is the cascade inputs, so the outputs of a comparator of the lower
+/// bits are connected to the cascade inputs of the next to compare a wider word.
+///
+/// ```ascii
+///              ┌─────────────┐
+/// a────────────┤             ├──────equal
+/// b────────────┤ Comparator  ├──────greater
+/// cascade──────┤             ├──────less
+///              └─────────────┘
+/// ```
+///
+/// # input
+/// the n bits of a, the n bits of b (the first bit is the lowest bit), then the
+/// equal, the greater and the less of the lower bits when cascaded.
+///
+/// # output
+/// the equal, the greater (a > b) and the less (a < b).
+///
+/// # truth table (n = 1)
+/// | A | B | E | G | L |
+/// |---|---|---|---|---|
+/// | 0 | 0 | 1 | 0 | 0 |
+/// | 1 | 0 | 0 | 1 | 0 |
+/// | 0 | 1 | 0 | 0 | 1 |
+/// | 1 | 1 | 1 | 0 | 0 |
+#[derive(Debug, Clone)]
+pub struct ComparatorN {
+    width: usize,
+    cascaded: bool,
+    input: Vec<Wire>,
+    output: [Wire; 3],
+    // the a != b and a == b of every bit
+    xor_gates: Vec<XORGate>,
+    equal_gates: Vec<NOTGate>,
+    not_a: Vec<NOTGate>,
+    not_b: Vec<NOTGate>,
+    // the a > b and a < b of every bit
+    greater_gates: Vec<ANDGate>,
+    less_gates: Vec<ANDGate>,
+    // the equal, the greater and the less passed through an equal bit
+    pass_gates: Vec<[ANDGate; 3]>,
+    decide_gates: Vec<[ORGate; 2]>,
+}
+
+impl ComparatorN {
+    pub fn new(width: usize) -> Self {
+        Self::with_cascade(width, false)
+    }
+
+    /// Create a comparator with the cascade inputs of the lower bits.
+    pub fn cascaded(width: usize) -> Self {
+        Self::with_cascade(width, true)
+    }
+
+    fn with_cascade(width: usize, cascaded: bool) -> Self {
+        assert!(width > 0, "width must be greater than 0");
+        let inputs = if cascaded { 2 * width + 3 } else { 2 * width };
+        Self {
+            width,
+            cascaded,
+            input: vec![Wire::default(); inputs],
+            output: [Wire::new(true), Wire::default(), Wire::default()],
+            xor_gates: vec![XORGate::default(); width],
+            equal_gates: vec![NOTGate::default(); width],
+            not_a: vec![NOTGate::default(); width],
+            not_b: vec![NOTGate::default(); width],
+            greater_gates: vec![ANDGate::default(); width],
+            less_gates: vec![ANDGate::default(); width],
+            pass_gates: (0..width).map(|_| Default::default()).collect(),
+            decide_gates: (0..width).map(|_| Default::default()).collect(),
+        }
+    }
+}
+
+impl Component for ComparatorN {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.input.len(), 3)
+    }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "magnitude comparator",
+            description: "compares two n-bit numbers to equal, greater or less.",
+            timing: Timing::Combinational,
+        })
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+    fn ports(&self) -> Vec<Port> {
+        let n = self.width;
+        let mut ports = vec![Port::input("a", 0..n), Port::input("b", n..2 * n)];
+        if self.cascaded {
+            ports.push(Port::input("cascade", 2 * n..2 * n + 3));
+        }
+        ports.extend([
+            Port::output("equal", 0..1),
+            Port::output("greater", 1..2),
+            Port::output("less", 2..3),
+        ]);
+        ports
+    }
+    fn update_state(&mut self) {
+        let n = self.width;
+        // [equal, greater, less] of the bits below
+        let mut result = if self.cascaded {
+            [
+                self.input[2 * n].output(),
+                self.input[2 * n + 1].output(),
+                self.input[2 * n + 2].output(),
+            ]
+        } else {
+            [true, false, false]
+        };
+        for i in 0..n {
+            let a = self.input[i].output();
+            let b = self.input[n + i].output();
+            self.xor_gates[i].input(&a, &b);
+            self.equal_gates[i].input(&self.xor_gates[i].output());
+            self.not_a[i].input(&a);
+            self.not_b[i].input(&b);
+            self.greater_gates[i].input(&a, &self.not_b[i].output());
+            self.less_gates[i].input(&self.not_a[i].output(), &b);
+            let equal = self.equal_gates[i].output();
+            for (j, pass_gate) in self.pass_gates[i].iter_mut().enumerate() {
+                pass_gate.input(&equal, &result[j]);
+            }
+            self.decide_gates[i][0].input(
+                &self.greater_gates[i].output(),
+                &self.pass_gates[i][1].output(),
+            );
+            self.decide_gates[i][1].input(
+                &self.less_gates[i].output(),
+                &self.pass_gates[i][2].output(),
+            );
+            result = [
+                self.pass_gates[i][0].output(),
+                self.decide_gates[i][0].output(),
+                self.decide_gates[i][1].output(),
+            ];
+        }
+        for (wire, value) in self.output.iter_mut().zip(result) {
+            wire.input(&value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::{Endianness, Pad, Padding, Potentials};
+    use rstest::rstest;
+
+    fn compare(comparator: &mut ComparatorN, a: i64, b: i64) -> Vec<Potential> {
+        let n = comparator.width;
+        comparator.set_port("a", &Potentials::with_width(a, n, Pad::Zero));
+        comparator.set_port("b", &Potentials::with_width(b, n, Pad::Zero));
+        comparator.update_state();
+        comparator.output()
+    }
+
+    #[test]
+    fn test_comparator_default() {
+        let comparator = ComparatorN::new(4);
+        assert_eq!(comparator.output(), vec![true, false, false]);
+    }
+
+    #[rstest]
+    #[case("00", "100")]
+    #[case("10", "010")]
+    #[case("01", "001")]
+    #[case("11", "100")]
+    fn test_comparator_1_with_truth_table(#[case] input: &str, #[case] output: &str) {
+        let mut comparator = ComparatorN::new(1);
+        comparator.input(&Potentials::from_little_endian(input, Padding::Keep).get_data(true));
+        assert_eq!(
+            comparator.output(),
+            Potentials::from_little_endian(output, Padding::Keep).get_data(true)
+        );
+    }
+
+    #[test]
+    fn test_comparator_exhaustive() {
+        let mut comparator = ComparatorN::new(4);
+        for a in 0..16 {
+            for b in 0..16 {
+                assert_eq!(compare(&mut comparator, a, b), vec![a == b, a > b, a < b]);
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(0x12, 0x12)]
+    #[case(0x12, 0x13)]
+    #[case(0x23, 0x13)]
+    #[case(0x1f, 0x20)]
+    #[case(0xff, 0x00)]
+    fn test_comparator_cascade(#[case] a: i64, #[case] b: i64) {
+        // the lower nibble feeds the cascade of the higher nibble
+        let mut low = ComparatorN::new(4);
+        let mut high = ComparatorN::cascaded(4);
+        let result = compare(&mut low, a & 0xf, b & 0xf);
+        high.set_port("cascade", &Potentials::new(result, Endianness::Little));
+        assert_eq!(
+            compare(&mut high, a >> 4, b >> 4),
+            vec![a == b, a > b, a < b]
+        );
+    }
+}
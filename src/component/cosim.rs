@@ -0,0 +1,207 @@
+use std::io::{self, Read, Write};
+
+use crate::{
+    circuit::{Potential, Wire},
+    component::Component,
+};
+
+/// Write the potentials as a frame: a 4 bytes little endian length, then the
+/// potentials packed into bytes, the first potential is the lowest bit of the first byte.
+pub fn write_frame<W: Write>(writer: &mut W, potentials: &[Potential]) -> io::Result<()> {
+    let mut payload = vec![0u8; potentials.len().div_ceil(8)];
+    for (i, potential) in potentials.iter().enumerate() {
+        if *potential {
+            payload[i / 8] |= 1 << (i % 8);
+        }
+    }
+    writer.write_all(&(payload.len() as u32).to_le_bytes())?;
+    writer.write_all(&payload)?;
+    writer.flush()
+}
+
+/// Read a frame of `count` potentials written by [`write_frame`].
+pub fn read_frame<R: Read>(reader: &mut R, count: usize) -> io::Result<Vec<Potential>> {
+    let mut length = [0u8; 4];
+    reader.read_exact(&mut length)?;
+    let length = u32::from_le_bytes(length) as usize;
+    if length != count.div_ceil(8) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "frame of {} bytes, expect {} bytes",
+                length,
+                count.div_ceil(8)
+            ),
+        ));
+    }
+    let mut payload = vec![0u8; length];
+    reader.read_exact(&mut payload)?;
+    Ok((0..count)
+        .map(|i| (payload[i / 8] >> (i % 8)) & 1 == 1)
+        .collect())
+}
+
+/// a co-simulation bridge to an external process.
+/// the input is n bits, and the output is m bits.
+///
+/// every `update_state` is one tick of the bridge: the inputs are sent to the
+/// external process as a frame, and the outputs are the frame it answers.
+/// so the external process models a component with n inputs and m outputs.
+///
+/// ```ascii
+///          ┌─────────────────┐   frame   ┌──────────┐
+/// In───────┤                 ├──────────►│ external │
+///          │  Co-sim Bridge  │           │ process  │
+/// Out──────┤                 │◄──────────┤          │
+///          └─────────────────┘   frame   └──────────┘
+/// ```
+///
+/// the frames are written by [`write_frame`] and read by [`read_frame`], over any
+/// reader and writer, such as the stdio of a child process or a socket.
+/// when the exchange fails the outputs are kept, and the error is reported by
+/// [`CoSimBridge::error`].
+#[derive(Debug)]
+pub struct CoSimBridge<R: Read, W: Write> {
+    reader: R,
+    writer: W,
+    input: Vec<Wire>,
+    output: Vec<Wire>,
+    error: Option<io::Error>,
+}
+
+impl<R: Read, W: Write> CoSimBridge<R, W> {
+    pub fn new(reader: R, writer: W, input_count: usize, output_count: usize) -> Self {
+        assert!(input_count > 0, "input_count must be greater than 0");
+        assert!(output_count > 0, "output_count must be greater than 0");
+        Self {
+            reader,
+            writer,
+            input: vec![Wire::default(); input_count],
+            output: vec![Wire::default(); output_count],
+            error: None,
+        }
+    }
+
+    /// Get the error of the last exchange, if it fails.
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Release the reader and the writer.
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.writer)
+    }
+
+    fn exchange(&mut self) -> io::Result<Vec<Potential>> {
+        let input: Vec<Potential> = self.input.iter().map(|wire| wire.output()).collect();
+        write_frame(&mut self.writer, &input)?;
+        read_frame(&mut self.reader, self.output.len())
+    }
+}
+
+impl<R: Read, W: Write> Component for CoSimBridge<R, W> {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.input.len(), self.output.len())
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+    fn update_state(&mut self) {
+        match self.exchange() {
+            Ok(output) => {
+                for (wire, value) in self.output.iter_mut().zip(output.iter()) {
+                    wire.input(value);
+                }
+                self.error = None;
+            }
+            Err(error) => self.error = Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::io::Cursor;
+
+    #[rstest]
+    #[case(vec![true], vec![1, 0, 0, 0, 0b1])]
+    #[case(vec![false, true, true], vec![1, 0, 0, 0, 0b110])]
+    #[case(vec![true; 9], vec![2, 0, 0, 0, 0xff, 0b1])]
+    fn test_frame(#[case] potentials: Vec<Potential>, #[case] bytes: Vec<u8>) {
+        let mut buffer = Vec::new();
+        write_frame(&mut buffer, &potentials).unwrap();
+        assert_eq!(buffer, bytes);
+        let frame = read_frame(&mut Cursor::new(buffer), potentials.len()).unwrap();
+        assert_eq!(frame, potentials);
+    }
+
+    #[test]
+    fn test_frame_length_mismatch() {
+        let bytes = vec![2, 0, 0, 0, 0xff, 0xff];
+        assert!(read_frame(&mut Cursor::new(bytes), 3).is_err());
+    }
+
+    #[test]
+    fn test_bridge_exchange() {
+        // the external side answers two ticks
+        let mut answers = Vec::new();
+        write_frame(&mut answers, &[true, false]).unwrap();
+        write_frame(&mut answers, &[false, true]).unwrap();
+        let mut bridge = CoSimBridge::new(Cursor::new(answers), Vec::new(), 3, 2);
+        bridge.input(&[true, true, false]);
+        assert_eq!(bridge.output(), vec![true, false]);
+        bridge.input(&[false, false, true]);
+        assert_eq!(bridge.output(), vec![false, true]);
+        assert!(bridge.error().is_none());
+
+        // the external side is closed, the outputs are kept
+        bridge.input(&[true, true, true]);
+        assert_eq!(bridge.output(), vec![false, true]);
+        assert!(bridge.error().is_some());
+
+        let (_, sent) = bridge.into_inner();
+        let mut sent = Cursor::new(sent);
+        assert_eq!(read_frame(&mut sent, 3).unwrap(), vec![true, true, false]);
+        assert_eq!(read_frame(&mut sent, 3).unwrap(), vec![false, false, true]);
+        assert_eq!(read_frame(&mut sent, 3).unwrap(), vec![true, true, true]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_bridge_child_process() {
+        use std::process::{Command, Stdio};
+        // `cat` echoes every frame, so it models a buffer
+        let mut child = Command::new("cat")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let mut bridge = CoSimBridge::new(stdout, stdin, 4, 4);
+        for input in [
+            vec![true, false, true, false],
+            vec![false, true, true, true],
+        ] {
+            bridge.input(&input);
+            assert_eq!(bridge.output(), input);
+        }
+        drop(bridge);
+        child.wait().unwrap();
+    }
+}
@@ -0,0 +1,1204 @@
+use crate::{
+    circuit::{ANDGate, NOTGate, ORGate, Potential, Wire, XORGate, init},
+    component::{
+        Component,
+        adder::RippleCarryAdderN,
+        big_gates::{ANDGateN, ORGateN, ThresholdGate},
+        datasheet::{Metadata, Timing},
+        edge::EdgeDetector,
+        latch::DFlipFlop,
+        port::Port,
+        register::ShiftRegisterN,
+    },
+};
+
+/// a frequency counter in circuit.
+/// the input is 3 bits, and the output is n bits.
+///
+/// ```ascii
+///            ┌─────────────────────┐
+/// signal─────┤                     ├───── count (n bits)
+///            │                     │
+/// clock──────┤  Frequency Counter  │
+///            │                     │
+/// reset──────┤                     │
+///            └─────────────────────┘
+/// ```
+///
+/// the rising edges of the signal are counted during a gate time of
+/// `gate_cycles` cycles of the reference clock. at the end of each gate time
+/// the count is latched to the output and the counting restarts. while reset is
+/// high the rising edge of the clock clears the count, the gate time and the output.
+///
+/// # input
+/// the first bit is the measured signal, the second bit is the reference clock,
+/// and the third bit is reset.
+///
+/// # output
+/// the number of rising edges of the signal in the last gate time,
+/// the first bit is the lowest bit.
+#[derive(Debug, Clone)]
+pub struct FrequencyCounter {
+    gate_cycles: usize,
+    input: [Wire; 3],
+    output: Vec<Wire>,
+    signal_edge: EdgeDetector,
+    clock_edge: EdgeDetector,
+    // rising edges of the signal in the current gate time
+    count: Vec<Wire>,
+    count_incrementer: Incrementer,
+    // cycles of the reference clock in the current gate time
+    gate: Vec<Wire>,
+    gate_incrementer: Incrementer,
+    not_reached: NOTGate,
+    // reset at the rising edge of the clock
+    clear: ANDGate,
+    not_clear: NOTGate,
+    // not reached and not clear
+    keep: ANDGate,
+    // reached or clear
+    latch: ORGate,
+    // the count, or 0 on clear
+    output_gates: Vec<ANDGate>,
+}
+
+impl FrequencyCounter {
+    /// Create a new frequency counter.
+    ///
+    /// # Arguments
+    /// * `width` - The width of the output bus.
+    /// * `gate_cycles` - The number of reference clock cycles of a gate time.
+    pub fn new(width: usize, gate_cycles: usize) -> Self {
+        assert!(width > 0, "width must be greater than 0");
+        assert!(gate_cycles > 0, "gate_cycles must be greater than 0");
+        let gate_width = (usize::BITS - gate_cycles.leading_zeros()) as usize;
+        Self {
+            gate_cycles,
+            input: [Wire::default(); 3],
+            output: vec![Wire::default(); width],
+            signal_edge: EdgeDetector::default(),
+            clock_edge: EdgeDetector::default(),
+            count: init::power_up(width),
+            count_incrementer: Incrementer::new(width),
+            gate: init::power_up(gate_width),
+            gate_incrementer: Incrementer::new(gate_width),
+            not_reached: NOTGate::default(),
+            clear: ANDGate::default(),
+            not_clear: NOTGate::default(),
+            keep: ANDGate::default(),
+            latch: ORGate::default(),
+            output_gates: vec![ANDGate::default(); width],
+        }
+    }
+}
+
+/// the next value of a counter register in circuit, `count + carry`, and whether
+/// the sum reaches a target.
+///
+/// the sum comes from a [`RippleCarryAdderN`] whose carry out is dropped, so the
+/// count wraps around. the target is a constant, whose bits pick either the bit of
+/// the sum or its not, or a register, whose bits are compared by xnor gates.
+#[derive(Debug, Clone)]
+pub(crate) struct Incrementer {
+    adder: RippleCarryAdderN,
+    // sum == target
+    xor_gates: Vec<XORGate>,
+    not_gates: Vec<NOTGate>,
+    reached: ANDGateN,
+    // the sum, or 0 when it is not kept
+    keep_gates: Vec<ANDGate>,
+}
+
+impl Incrementer {
+    pub(crate) fn new(width: usize) -> Self {
+        Self {
+            adder: RippleCarryAdderN::new(width),
+            xor_gates: vec![XORGate::default(); width],
+            not_gates: vec![NOTGate::default(); width],
+            reached: ANDGateN::new(width),
+            keep_gates: vec![ANDGate::default(); width],
+        }
+    }
+
+    /// Evaluate `count + carry`.
+    pub(crate) fn increment(&mut self, count: &[Wire], carry: &Potential) {
+        let mut adder_input = vec![*carry];
+        adder_input.extend(count.iter().map(|wire| wire.output()));
+        adder_input.extend(vec![false; count.len()]);
+        self.adder.input(&adder_input);
+    }
+
+    /// Get a bit of the sum.
+    pub(crate) fn sum(&self, position: usize) -> Potential {
+        self.adder.get_pin_output(position)
+    }
+
+    /// Evaluate whether the sum equals the constant `target`.
+    pub(crate) fn reaches(&mut self, target: usize) -> Potential {
+        for i in 0..self.not_gates.len() {
+            let bit = self.sum(i);
+            self.not_gates[i].input(&bit);
+            let literal = if (target >> i) & 1 == 1 {
+                bit
+            } else {
+                self.not_gates[i].output()
+            };
+            self.reached.set_pin_input(i, &literal);
+        }
+        self.reached.update_state();
+        self.reached.get_pin_output(0)
+    }
+
+    /// Evaluate whether the sum equals the value of the register `target`.
+    pub(crate) fn matches(&mut self, target: &[Wire]) -> Potential {
+        for (i, wire) in target.iter().enumerate() {
+            let bit = self.sum(i);
+            self.xor_gates[i].input(&bit, &wire.output());
+            self.not_gates[i].input(&self.xor_gates[i].output());
+            self.reached.set_pin_input(i, &self.not_gates[i].output());
+        }
+        self.reached.update_state();
+        self.reached.get_pin_output(0)
+    }
+
+    /// Evaluate the next count, which is the sum while `keep` is high and 0 otherwise.
+    pub(crate) fn next(&mut self, keep: &Potential) -> Vec<Potential> {
+        for i in 0..self.keep_gates.len() {
+            let bit = self.sum(i);
+            self.keep_gates[i].input(keep, &bit);
+        }
+        self.keep_gates.iter().map(|gate| gate.output()).collect()
+    }
+}
+
+impl Component for FrequencyCounter {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (3, self.output.len())
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+    fn update_state(&mut self) {
+        self.signal_edge.input(&[self.input[0].output()]);
+        self.clock_edge.input(&[self.input[1].output()]);
+        self.clear
+            .input(&self.input[2].output(), &self.clock_edge.is_rising());
+        self.not_clear.input(&self.clear.output());
+
+        self.count_incrementer
+            .increment(&self.count, &self.signal_edge.is_rising());
+        self.gate_incrementer
+            .increment(&self.gate, &self.clock_edge.is_rising());
+
+        // gate + 1 == gate_cycles
+        let reached = self.gate_incrementer.reaches(self.gate_cycles);
+        self.not_reached.input(&reached);
+        self.keep
+            .input(&self.not_reached.output(), &self.not_clear.output());
+        self.latch.input(&reached, &self.clear.output());
+
+        // latch the count at the end of the gate time
+        for i in 0..self.output_gates.len() {
+            let bit = self.count_incrementer.sum(i);
+            self.output_gates[i].input(&bit, &self.not_clear.output());
+        }
+        if self.latch.output() {
+            for (wire, gate) in self.output.iter_mut().zip(&self.output_gates) {
+                wire.input(&gate.output());
+            }
+        }
+        // both registers restart at the end of the gate time
+        let count = self.count_incrementer.next(&self.keep.output());
+        for (wire, bit) in self.count.iter_mut().zip(count) {
+            wire.input(&bit);
+        }
+        let gate = self.gate_incrementer.next(&self.keep.output());
+        for (wire, bit) in self.gate.iter_mut().zip(gate) {
+            wire.input(&bit);
+        }
+    }
+}
+
+/// a n-bit ring counter in circuit.
+/// the input is 2 bits, and the output is n bits.
+///
+/// the counter is a [`ShiftRegisterN`] shifting left, whose serial in is fed back
+/// from its last bit, so it circulates a single high bit, which moves one position
+/// on every rising edge of the clock. while reset is high the rising edge loads the
+/// first state instead.
+///
+/// # state sequence (n = 4)
+/// | step | O0 | O1 | O2 | O3 |
+/// |---|---|---|---|---|
+/// | reset | 1 | 0 | 0 | 0 |
+/// | 1 | 0 | 1 | 0 | 0 |
+/// | 2 | 0 | 0 | 1 | 0 |
+/// | 3 | 0 | 0 | 0 | 1 |
+/// | 4 | 1 | 0 | 0 | 0 |
+///
+/// the plain counter circulates whatever it holds, so an illegal state (for example
+/// after power on without reset) is never left. the self-correcting counter feeds
+/// `nor(O0..On-2)` back instead of `On-1`, so it returns to the sequence within n steps
+/// and starts by itself after power on, see [`Stepper`].
+///
+/// ```ascii
+///              ┌─────────────┐
+/// clock────────┤             ├───── O0
+///              │ RingCounter │  ...
+/// reset────────┤             ├───── On-1
+///              └─────────────┘
+/// ```
+///
+/// # input
+/// the first bit is the clock, and the second bit is the synchronous reset.
+///
+/// # output
+/// the n steps, only one is high in the sequence.
+#[derive(Debug, Clone)]
+pub struct RingCounterN {
+    n_way: usize,
+    self_correcting: bool,
+    input: [Wire; 2],
+    register: ShiftRegisterN,
+    feedback_or: ORGateN,
+    feedback_not: NOTGate,
+}
+
+impl RingCounterN {
+    pub fn new(n_way: usize) -> Self {
+        Self::with_correction(n_way, false)
+    }
+
+    pub fn self_correcting(n_way: usize) -> Self {
+        Self::with_correction(n_way, true)
+    }
+
+    fn with_correction(n_way: usize, self_correcting: bool) -> Self {
+        assert!(n_way > 1, "n_way must be greater than 1");
+        Self {
+            n_way,
+            self_correcting,
+            input: [Wire::default(); 2],
+            register: ShiftRegisterN::new(n_way),
+            feedback_or: ORGateN::new(n_way - 1),
+            feedback_not: NOTGate::default(),
+        }
+    }
+}
+
+/// Clock a shift register, which shifts left from `serial_in`, or loads `data`
+/// while `load` is high, as the lowest bit of the mode.
+fn shift_or_load(
+    register: &mut ShiftRegisterN,
+    clock: Potential,
+    load: Potential,
+    serial_in: Potential,
+    data: &[Potential],
+) {
+    let mut input = vec![clock, load, true, serial_in];
+    input.extend(data);
+    register.input(&input);
+}
+
+impl Component for RingCounterN {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (2, self.n_way)
+    }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "ring counter",
+            description: "moves a single high step one position on the rising edge of the clock.",
+            timing: Timing::RisingEdge { clock: 0 },
+        })
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.register.get_pin_output(position)
+    }
+    fn ports(&self) -> Vec<Port> {
+        vec![
+            Port::input("clock", 0..1),
+            Port::input("reset", 1..2),
+            Port::output("step", 0..self.n_way),
+        ]
+    }
+    fn update_state(&mut self) {
+        let n = self.n_way;
+        let q: Vec<Potential> = (0..n).map(|i| self.register.get_pin_output(i)).collect();
+        let feedback = if self.self_correcting {
+            self.feedback_or.input(&q[..n - 1]);
+            self.feedback_not.input(&self.feedback_or.get_pin_output(0));
+            self.feedback_not.output()
+        } else {
+            q[n - 1]
+        };
+        let first: Vec<Potential> = (0..n).map(|i| i == 0).collect();
+        shift_or_load(
+            &mut self.register,
+            self.input[0].output(),
+            self.input[1].output(),
+            feedback,
+            &first,
+        );
+    }
+}
+
+/// a stepper of k steps in circuit, like the stepper of the control unit of the
+/// scott cpu, which sequences the steps of an instruction.
+/// the input is 2 bits, and the output is k bits.
+///
+/// the stepper is a self-correcting [`RingCounterN`], which moves a single high step
+/// one position on every rising edge of the clock, and returns from the last step to
+/// the first. so the stepper starts by itself after power on, and goes to the first
+/// step on the rising edge of the clock while `reset` is high.
+///
+/// ```ascii
+///              ┌─────────────┐
+/// clock────────┤             ├───── s0
+///              │   Stepper   │  ...
+/// reset────────┤             ├───── s(k-1)
+///              └─────────────┘
+/// ```
+///
+/// # input
+/// the clock and the reset.
+///
+/// # output
+/// the k steps, only one is high.
+#[derive(Debug, Clone)]
+pub struct Stepper(RingCounterN);
+
+impl Stepper {
+    pub fn new(steps: usize) -> Self {
+        assert!(steps > 1, "steps must be greater than 1");
+        Self(RingCounterN::self_correcting(steps))
+    }
+}
+
+impl Component for Stepper {
+    fn get_pin_count(&self) -> (usize, usize) {
+        self.0.get_pin_count()
+    }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "stepper",
+            description: "moves a single high step one position on the rising edge of the clock.",
+            timing: Timing::RisingEdge { clock: 0 },
+        })
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        self.0.set_pin_input(position, value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        self.0.get_pin_output(position)
+    }
+    fn ports(&self) -> Vec<Port> {
+        self.0.ports()
+    }
+    fn update_state(&mut self) {
+        self.0.update_state();
+    }
+}
+
+/// a n-bit johnson (twisted ring) counter in circuit.
+/// the input is 2 bits, and the output is n bits.
+///
+/// the counter is a [`ShiftRegisterN`] shifting left, whose serial in is fed with
+/// the inverted last bit, so it walks through 2n states on successive rising edges
+/// of the clock. while reset is high the rising edge clears the register instead.
+///
+/// # state sequence (n = 3)
+/// | step | O0 | O1 | O2 |
+/// |---|---|---|---|
+/// | reset | 0 | 0 | 0 |
+/// | 1 | 1 | 0 | 0 |
+/// | 2 | 1 | 1 | 0 |
+/// | 3 | 1 | 1 | 1 |
+/// | 4 | 0 | 1 | 1 |
+/// | 5 | 0 | 0 | 1 |
+/// | 6 | 0 | 0 | 0 |
+///
+/// every legal state has at most one boundary between neighbouring bits. the
+/// self-correcting counter detects two or more boundaries with a threshold gate and
+/// clears the register on the next clock, so it returns to the sequence within one step.
+///
+/// # input
+/// the first bit is the clock, and the second bit is the synchronous reset.
+#[derive(Debug, Clone)]
+pub struct JohnsonCounterN {
+    n_way: usize,
+    self_correcting: bool,
+    input: [Wire; 2],
+    register: ShiftRegisterN,
+    feedback_not: NOTGate,
+    boundary_gates: Vec<XORGate>,
+    illegal: ThresholdGate,
+    clear: ORGate,
+}
+
+impl JohnsonCounterN {
+    pub fn new(n_way: usize) -> Self {
+        Self::with_correction(n_way, false)
+    }
+
+    pub fn self_correcting(n_way: usize) -> Self {
+        Self::with_correction(n_way, true)
+    }
+
+    fn with_correction(n_way: usize, self_correcting: bool) -> Self {
+        assert!(n_way > 1, "n_way must be greater than 1");
+        Self {
+            n_way,
+            self_correcting,
+            input: [Wire::default(); 2],
+            register: ShiftRegisterN::new(n_way),
+            feedback_not: NOTGate::default(),
+            boundary_gates: vec![XORGate::default(); n_way - 1],
+            illegal: ThresholdGate::new(n_way - 1, 2),
+            clear: ORGate::default(),
+        }
+    }
+}
+
+impl Component for JohnsonCounterN {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (2, self.n_way)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.register.get_pin_output(position)
+    }
+    fn update_state(&mut self) {
+        let n = self.n_way;
+        let q: Vec<Potential> = (0..n).map(|i| self.register.get_pin_output(i)).collect();
+        self.feedback_not.input(&q[n - 1]);
+        let reset = self.input[1].output();
+        let clear = if self.self_correcting {
+            for i in 1..n {
+                self.boundary_gates[i - 1].input(&q[i - 1], &q[i]);
+            }
+            let boundaries: Vec<Potential> = self
+                .boundary_gates
+                .iter()
+                .map(|gate| gate.output())
+                .collect();
+            self.illegal.input(&boundaries);
+            self.clear.input(&reset, &self.illegal.get_pin_output(0));
+            self.clear.output()
+        } else {
+            reset
+        };
+        shift_or_load(
+            &mut self.register,
+            self.input[0].output(),
+            clear,
+            self.feedback_not.output(),
+            &vec![false; n],
+        );
+    }
+}
+
+/// a cascadable bcd up/down counter in circuit.
+/// the input is 4 bits, and the output is 5 bits.
+///
+/// ```ascii
+///              ┌─────────────────┐
+/// clock────────┤                 ├───── q0..q3
+/// clear────────┤   BCD Counter   │
+/// enable───────┤                 ├───── terminal
+/// down─────────┤                 │
+///              └─────────────────┘
+/// ```
+///
+/// on a rising edge of the clock the counter loads 0 when `clear` is high,
+/// otherwise it counts up (or down when `down` is high) while `enable` is high,
+/// wrapping from 9 to 0 (or from 0 to 9).
+///
+/// `terminal` is high when the counter is enabled and is about to wrap, so it can
+/// be connected to the `enable` of the next decade, all decades sharing the clock.
+///
+/// # input
+/// | 0 | 1 | 2 | 3 |
+/// |---|---|---|---|
+/// | clock | clear | enable | down |
+///
+/// # output
+/// the first 4 bits are the bcd digit (the first bit is the lowest bit),
+/// and the last bit is terminal.
+#[derive(Debug, Clone)]
+pub struct BcdCounter {
+    input: [Wire; 4],
+    state: Vec<Wire>,
+    terminal: Wire,
+    clock_edge: EdgeDetector,
+    // state + 1, or state + 15 (state - 1) when counting down
+    adder: RippleCarryAdderN,
+    up: NOTGate,
+    not_gates: [NOTGate; 4],
+    is_nine: ANDGateN,
+    is_zero: ANDGateN,
+    wrap_up: ANDGate,
+    wrap_down: ANDGate,
+    wrap: ORGate,
+    terminal_gate: ANDGate,
+    not_wrap: NOTGate,
+    sum_gates: [ANDGate; 4],
+    nine_gates: [ORGate; 2],
+    clear: NOTGate,
+    clear_gates: [ANDGate; 4],
+}
+
+impl Default for BcdCounter {
+    fn default() -> Self {
+        Self {
+            input: [Wire::default(); 4],
+            state: init::power_up(4),
+            terminal: Wire::default(),
+            clock_edge: EdgeDetector::default(),
+            adder: RippleCarryAdderN::new(4),
+            up: NOTGate::default(),
+            not_gates: Default::default(),
+            is_nine: ANDGateN::new(4),
+            is_zero: ANDGateN::new(4),
+            wrap_up: ANDGate::default(),
+            wrap_down: ANDGate::default(),
+            wrap: ORGate::default(),
+            terminal_gate: ANDGate::default(),
+            not_wrap: NOTGate::default(),
+            sum_gates: Default::default(),
+            nine_gates: Default::default(),
+            clear: NOTGate::default(),
+            clear_gates: Default::default(),
+        }
+    }
+}
+
+impl BcdCounter {
+    /// Evaluate whether the current state wraps with the current inputs.
+    fn evaluate_wrap(&mut self) {
+        let down = self.input[3].output();
+        for i in 0..4 {
+            self.not_gates[i].input(&self.state[i].output());
+        }
+        self.is_nine.input(&[
+            self.state[0].output(),
+            self.not_gates[1].output(),
+            self.not_gates[2].output(),
+            self.state[3].output(),
+        ]);
+        let zero: Vec<Potential> = self.not_gates.iter().map(|gate| gate.output()).collect();
+        self.is_zero.input(&zero);
+        self.up.input(&down);
+        self.wrap_up
+            .input(&self.is_nine.get_pin_output(0), &self.up.output());
+        self.wrap_down.input(&self.is_zero.get_pin_output(0), &down);
+        self.wrap
+            .input(&self.wrap_up.output(), &self.wrap_down.output());
+        self.terminal_gate
+            .input(&self.wrap.output(), &self.input[2].output());
+    }
+}
+
+impl Component for BcdCounter {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (4, 5)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        if position < 4 {
+            self.state[position].output()
+        } else {
+            self.terminal.output()
+        }
+    }
+    fn update_state(&mut self) {
+        self.clock_edge.input(&[self.input[0].output()]);
+        self.evaluate_wrap();
+        if self.clock_edge.is_rising() {
+            let down = self.input[3].output();
+            let mut adder_input = vec![self.up.output()];
+            adder_input.extend(self.state.iter().map(|wire| wire.output()));
+            adder_input.extend(vec![down; 4]);
+            self.adder.input(&adder_input);
+            self.not_wrap.input(&self.wrap.output());
+            let mut next = [false; 4];
+            for (i, value) in next.iter_mut().enumerate() {
+                self.sum_gates[i].input(&self.adder.get_pin_output(i), &self.not_wrap.output());
+                *value = self.sum_gates[i].output();
+            }
+            // 9 = 1001, only bit 0 and bit 3 are set when wrapping down
+            for (j, i) in [0, 3].into_iter().enumerate() {
+                self.nine_gates[j].input(&next[i], &self.wrap_down.output());
+                next[i] = self.nine_gates[j].output();
+            }
+            let clear = self.input[1].output();
+            self.clear.input(&clear);
+            // the register loads when it is enabled or cleared
+            if self.input[2].output() || clear {
+                for (i, value) in next.iter().enumerate() {
+                    self.clear_gates[i].input(value, &self.clear.output());
+                    self.state[i].input(&self.clear_gates[i].output());
+                }
+            }
+            self.evaluate_wrap();
+        }
+        self.terminal.input(&self.terminal_gate.output());
+    }
+}
+
+/// a n-bit synchronous up/down binary counter in circuit.
+/// the input is 3 bits, and the output is n bits.
+///
+/// every bit is a [`DFlipFlop`] sharing the clock, and the next count is the count
+/// plus 1, or plus `2^n - 1` (the count minus 1) when `down` is high, from a
+/// [`RippleCarryAdderN`]. the next count is gated to 0 while `reset` is high, so
+/// the counter is reset on the rising edge of the clock.
+///
+/// ```ascii
+///              ┌─────────────┐
+/// clock────────┤             │
+/// reset────────┤   Counter   ├───── q0..q(n-1)
+/// down─────────┤             │
+///              └─────────────┘
+/// ```
+///
+/// # input
+/// | 0 | 1 | 2 |
+/// |---|---|---|
+/// | clock | reset | down |
+///
+/// # output
+/// the n bits of the count (the first bit is the lowest bit), which wraps around.
+#[derive(Debug, Clone)]
+pub struct CounterN {
+    width: usize,
+    input: [Wire; 3],
+    adder: RippleCarryAdderN,
+    up: NOTGate,
+    not_reset: NOTGate,
+    reset_gates: Vec<ANDGate>,
+    flip_flops: Vec<DFlipFlop>,
+}
+
+impl CounterN {
+    pub fn new(width: usize) -> Self {
+        assert!(width > 0, "width must be greater than 0");
+        Self {
+            width,
+            input: [Wire::default(); 3],
+            adder: RippleCarryAdderN::new(width),
+            up: NOTGate::default(),
+            not_reset: NOTGate::default(),
+            reset_gates: vec![ANDGate::default(); width],
+            flip_flops: vec![DFlipFlop::default(); width],
+        }
+    }
+}
+
+impl Component for CounterN {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (3, self.width)
+    }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "counter",
+            description: "counts up, or down while down is high, on the rising edge of the clock.",
+            timing: Timing::RisingEdge { clock: 0 },
+        })
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.flip_flops[position].get_pin_output(0)
+    }
+    fn ports(&self) -> Vec<Port> {
+        vec![
+            Port::input("clock", 0..1),
+            Port::input("reset", 1..2),
+            Port::input("down", 2..3),
+            Port::output("count", 0..self.width),
+        ]
+    }
+    fn update_state(&mut self) {
+        let down = self.input[2].output();
+        self.up.input(&down);
+        self.not_reset.input(&self.input[1].output());
+        // the count + 1, or the count + 11..1 when counting down
+        let mut adder_input = vec![self.up.output()];
+        adder_input.extend(
+            self.flip_flops
+                .iter()
+                .map(|flip_flop| flip_flop.get_pin_output(0)),
+        );
+        adder_input.extend(vec![down; self.width]);
+        self.adder.input(&adder_input);
+        let clock = self.input[0].output();
+        for (i, flip_flop) in self.flip_flops.iter_mut().enumerate() {
+            self.reset_gates[i].input(&self.adder.get_pin_output(i), &self.not_reset.output());
+            flip_flop.input(&[clock, self.reset_gates[i].output()]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::{Padding, Potentials};
+    use rstest::rstest;
+
+    #[test]
+    fn test_incrementer() {
+        let count: Vec<Wire> = [true, true, false]
+            .iter()
+            .map(|bit| Wire::new(*bit))
+            .collect();
+        let mut incrementer = Incrementer::new(3);
+        incrementer.increment(&count, &true);
+        assert_eq!(
+            (0..3).map(|i| incrementer.sum(i)).collect::<Vec<_>>(),
+            vec![false, false, true]
+        );
+        assert!(incrementer.reaches(4));
+        assert!(!incrementer.reaches(3));
+        let target: Vec<Wire> = [false, false, true]
+            .iter()
+            .map(|bit| Wire::new(*bit))
+            .collect();
+        assert!(incrementer.matches(&target));
+        assert_eq!(incrementer.next(&false), vec![false; 3]);
+        // the carry out is dropped
+        let count = vec![Wire::new(true); 3];
+        incrementer.increment(&count, &true);
+        assert_eq!(incrementer.next(&true), vec![false; 3]);
+    }
+
+    /// run a full clock cycle without reset.
+    fn tick<C: Component>(counter: &mut C) -> Vec<Potential> {
+        counter.input(&[false, false]);
+        counter.input(&[true, false]);
+        counter.output()
+    }
+
+    /// load an arbitrary, possibly illegal, state into the shift register of a counter.
+    fn load(register: &mut ShiftRegisterN, n_way: usize, state: usize) {
+        let mut input = vec![false, true, true, false];
+        input.extend((0..n_way).map(|i| (state >> i) & 1 == 1));
+        register.input(&input);
+        input[0] = true;
+        register.input(&input);
+    }
+
+    fn johnson_from(n_way: usize, state: usize, self_correcting: bool) -> JohnsonCounterN {
+        let mut counter = if self_correcting {
+            JohnsonCounterN::self_correcting(n_way)
+        } else {
+            JohnsonCounterN::new(n_way)
+        };
+        load(&mut counter.register, n_way, state);
+        counter
+    }
+
+    /// run a full clock cycle with reset.
+    fn reset<C: Component>(counter: &mut C) -> Vec<Potential> {
+        counter.input(&[false, true]);
+        counter.input(&[true, true]);
+        counter.output()
+    }
+
+    /// create a frequency counter, and clear it by a clock cycle with reset.
+    fn frequency_counter(width: usize, gate_cycles: usize) -> FrequencyCounter {
+        let mut counter = FrequencyCounter::new(width, gate_cycles);
+        counter.input(&[false, false, true]);
+        counter.input(&[false, true, true]);
+        counter.input(&[false, false, false]);
+        counter
+    }
+
+    #[test]
+    fn test_frequency_counter_default() {
+        let counter = FrequencyCounter::new(4, 8);
+        assert_eq!(counter.output(), vec![false; 4]);
+    }
+
+    #[test]
+    fn test_frequency_counter_reset() {
+        let mut counter = frequency_counter(4, 2);
+        // 3 signal edges in a gate time
+        for _ in 0..3 {
+            counter.input(&[true, false, false]);
+            counter.input(&[false, false, false]);
+        }
+        counter.input(&[false, true, false]);
+        counter.input(&[false, false, false]);
+        counter.input(&[false, true, false]);
+        assert_eq!(counter.output(), vec![true, true, false, false]);
+        // reset clears the latched count
+        counter.input(&[false, false, true]);
+        counter.input(&[false, true, true]);
+        assert_eq!(counter.output(), vec![false; 4]);
+    }
+
+    #[rstest]
+    // the signal toggles every `signal_period / 2` samples,
+    // the reference clock toggles every sample.
+    #[case(2, 8, "0001")]
+    #[case(4, 8, "0010")]
+    #[case(8, 8, "0100")]
+    #[case(2, 3, "1100")]
+    fn test_frequency_counter_measure(
+        #[case] signal_period: usize,
+        #[case] gate_cycles: usize,
+        #[case] expected: String,
+    ) {
+        let mut counter = FrequencyCounter::new(4, gate_cycles);
+        let expected = Potentials::from_little_endian(&expected, Padding::Keep);
+        // run several gate times, the output holds the count of the last one
+        for sample in 0..4 * gate_cycles * 2 {
+            let signal = (sample / (signal_period / 2)) % 2 == 1;
+            let clock = sample % 2 == 1;
+            counter.input(&[signal, clock, false]);
+        }
+        assert_eq!(counter.output(), expected.get_data(true));
+    }
+
+    #[test]
+    fn test_frequency_counter_latches_at_gate_end() {
+        let mut counter = frequency_counter(4, 2);
+        // 1 signal edge in the first clock cycle
+        counter.input(&[true, true, false]);
+        counter.input(&[false, false, false]);
+        assert_eq!(counter.output(), vec![false; 4]);
+        // 1 more signal edge and the gate time ends
+        counter.input(&[true, true, false]);
+        assert_eq!(counter.output(), vec![false, true, false, false]);
+        // no signal edge in the next gate time
+        for _ in 0..2 {
+            counter.input(&[true, false, false]);
+            counter.input(&[true, true, false]);
+        }
+        assert_eq!(counter.output(), vec![false; 4]);
+    }
+
+    fn ring_from(n_way: usize, state: usize) -> RingCounterN {
+        let mut counter = RingCounterN::self_correcting(n_way);
+        load(&mut counter.register, n_way, state);
+        counter
+    }
+
+    #[test]
+    fn test_ring_counter_default() {
+        let mut counter = RingCounterN::new(4);
+        assert_eq!(counter.get_pin_count(), (2, 4));
+        assert_eq!(reset(&mut counter), vec![true, false, false, false]);
+    }
+
+    #[rstest]
+    #[case(RingCounterN::new(4))]
+    #[case(RingCounterN::self_correcting(4))]
+    fn test_ring_counter_sequence(#[case] mut counter: RingCounterN) {
+        assert_eq!(reset(&mut counter), vec![true, false, false, false]);
+        assert_eq!(tick(&mut counter), vec![false, true, false, false]);
+        assert_eq!(tick(&mut counter), vec![false, false, true, false]);
+        assert_eq!(tick(&mut counter), vec![false, false, false, true]);
+        assert_eq!(tick(&mut counter), vec![true, false, false, false]);
+    }
+
+    #[test]
+    fn test_ring_counter_holds_without_clock_edge() {
+        let mut counter = RingCounterN::new(3);
+        assert_eq!(reset(&mut counter), vec![true, false, false]);
+        counter.input(&[true, false]);
+        assert_eq!(counter.output(), vec![true, false, false]);
+        counter.input(&[false, false]);
+        assert_eq!(counter.output(), vec![true, false, false]);
+    }
+
+    #[test]
+    fn test_ring_counter_without_reset_is_stuck() {
+        // the plain ring counter never leaves the all zero state
+        let mut counter = RingCounterN::new(3);
+        load(&mut counter.register, 3, 0);
+        assert_eq!(tick(&mut counter), vec![false; 3]);
+        assert_eq!(tick(&mut counter), vec![false; 3]);
+    }
+
+    #[rstest]
+    #[case(3)]
+    #[case(4)]
+    #[case(5)]
+    fn test_ring_counter_self_correcting(#[case] n_way: usize) {
+        for state in 0..(1 << n_way) {
+            let mut counter = ring_from(n_way, state);
+            for _ in 0..n_way {
+                tick(&mut counter);
+            }
+            let ones = counter.output().iter().filter(|bit| **bit).count();
+            assert_eq!(ones, 1, "state {:b} is not corrected", state);
+        }
+    }
+
+    #[test]
+    fn test_johnson_counter_default() {
+        let mut counter = JohnsonCounterN::new(3);
+        assert_eq!(counter.get_pin_count(), (2, 3));
+        assert_eq!(reset(&mut counter), vec![false; 3]);
+    }
+
+    #[rstest]
+    #[case(JohnsonCounterN::new(3))]
+    #[case(JohnsonCounterN::self_correcting(3))]
+    fn test_johnson_counter_sequence(#[case] mut counter: JohnsonCounterN) {
+        let sequence = ["000", "100", "110", "111", "011", "001", "000"];
+        assert_eq!(
+            reset(&mut counter),
+            Potentials::from_little_endian(sequence[0], Padding::Keep).get_data(true)
+        );
+        for state in &sequence[1..] {
+            assert_eq!(
+                tick(&mut counter),
+                Potentials::from_little_endian(state, Padding::Keep).get_data(true)
+            );
+        }
+    }
+
+    #[test]
+    fn test_johnson_counter_plain_keeps_illegal_state() {
+        // 010 -> 101 -> 010 ...
+        let mut counter = johnson_from(3, 0b010, false);
+        assert_eq!(tick(&mut counter), vec![true, false, true]);
+        assert_eq!(tick(&mut counter), vec![false, true, false]);
+    }
+
+    #[rstest]
+    #[case(3)]
+    #[case(4)]
+    #[case(6)]
+    fn test_johnson_counter_self_correcting(#[case] n_way: usize) {
+        for state in 0..(1 << n_way) {
+            let mut counter = johnson_from(n_way, state, true);
+            let output = tick(&mut counter);
+            let boundaries = output.windows(2).filter(|pair| pair[0] != pair[1]).count();
+            assert!(boundaries <= 1, "state {:b} is not corrected", state);
+        }
+    }
+
+    /// run a full clock cycle of a bcd counter and return the digit and terminal.
+    fn tick_bcd(counter: &mut BcdCounter, clear: bool, enable: bool, down: bool) -> (usize, bool) {
+        counter.input(&[false, clear, enable, down]);
+        counter.input(&[true, clear, enable, down]);
+        let output = counter.output();
+        let digit = (0..4).filter(|i| output[*i]).map(|i| 1 << i).sum();
+        (digit, output[4])
+    }
+
+    /// clear a new bcd counter, whatever it holds after power on.
+    fn cleared_bcd() -> BcdCounter {
+        let mut counter = BcdCounter::default();
+        tick_bcd(&mut counter, true, false, false);
+        counter
+    }
+
+    #[test]
+    fn test_bcd_counter_default() {
+        let counter = cleared_bcd();
+        assert_eq!(counter.get_pin_count(), (4, 5));
+        assert_eq!(counter.output(), vec![false; 5]);
+    }
+
+    #[test]
+    fn test_bcd_counter_up() {
+        let mut counter = cleared_bcd();
+        for expected in (1..10).chain(0..3) {
+            let (digit, _) = tick_bcd(&mut counter, false, true, false);
+            assert_eq!(digit, expected);
+        }
+    }
+
+    #[test]
+    fn test_bcd_counter_down() {
+        let mut counter = cleared_bcd();
+        for expected in (0..10).rev().chain((7..10).rev()) {
+            let (digit, _) = tick_bcd(&mut counter, false, true, true);
+            assert_eq!(digit, expected);
+        }
+    }
+
+    #[test]
+    fn test_bcd_counter_enable_and_clear() {
+        let mut counter = cleared_bcd();
+        tick_bcd(&mut counter, false, true, false);
+        tick_bcd(&mut counter, false, true, false);
+        assert_eq!(tick_bcd(&mut counter, false, false, false), (2, false));
+        assert_eq!(tick_bcd(&mut counter, true, true, false), (0, false));
+    }
+
+    #[rstest]
+    #[case(9, false, true)]
+    #[case(8, false, false)]
+    #[case(0, true, true)]
+    #[case(1, true, false)]
+    #[case(0, false, false)]
+    fn test_bcd_counter_terminal(#[case] digit: usize, #[case] down: bool, #[case] terminal: bool) {
+        let mut counter = cleared_bcd();
+        for _ in 0..digit {
+            tick_bcd(&mut counter, false, true, false);
+        }
+        // terminal follows the inputs without a clock edge
+        counter.input(&[false, false, true, down]);
+        assert_eq!(counter.output()[4], terminal);
+        counter.input(&[false, false, false, down]);
+        assert!(!counter.output()[4]);
+    }
+
+    #[test]
+    fn test_bcd_counter_cascade() {
+        let mut units = cleared_bcd();
+        let mut tens = cleared_bcd();
+        let to_digit = |output: Vec<Potential>| -> usize {
+            (0..4).filter(|i| output[*i]).map(|i| 1 << i).sum()
+        };
+        for _ in 0..42 {
+            // settle the carry chain, then clock both decades together
+            units.input(&[false, false, true, false]);
+            tens.input(&[false, false, units.output()[4], false]);
+            units.input(&[true, false, true, false]);
+            tens.input(&[true, false, tens.input[2].output(), false]);
+        }
+        assert_eq!(to_digit(tens.output()) * 10 + to_digit(units.output()), 42);
+    }
+
+    fn count(counter: &mut CounterN, reset: bool, down: bool) -> u64 {
+        counter.input(&[false, reset, down]);
+        counter.input(&[true, reset, down]);
+        counter
+            .output()
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (i, bit)| acc | (*bit as u64) << i)
+    }
+
+    #[test]
+    fn test_counter_n_default() {
+        let mut counter = CounterN::new(4);
+        assert_eq!(counter.get_pin_count(), (3, 4));
+        assert_eq!(count(&mut counter, true, false), 0);
+    }
+
+    #[test]
+    fn test_counter_n_up_down() {
+        let mut counter = CounterN::new(4);
+        assert_eq!(count(&mut counter, true, false), 0);
+        for expected in 1..20 {
+            assert_eq!(count(&mut counter, false, false), expected % 16);
+        }
+        // 19 % 16 = 3, count down and wrap from 0 to 15
+        for expected in [2, 1, 0, 15, 14] {
+            assert_eq!(count(&mut counter, false, true), expected);
+        }
+        assert_eq!(count(&mut counter, true, true), 0);
+    }
+
+    #[test]
+    fn test_counter_n_counts_on_rising_edge() {
+        let mut counter = CounterN::new(3);
+        count(&mut counter, true, false);
+        counter.input(&[false, false, false]);
+        counter.input(&[false, false, false]);
+        assert_eq!(counter.output(), vec![false; 3]);
+        counter.input(&[true, false, false]);
+        counter.input(&[true, false, false]);
+        assert_eq!(counter.output(), vec![true, false, false]);
+    }
+
+    fn step(stepper: &mut Stepper, reset: bool) -> usize {
+        stepper.input(&[false, reset]);
+        stepper.input(&[true, reset]);
+        let output = stepper.output();
+        assert_eq!(output.iter().filter(|bit| **bit).count(), 1);
+        output.iter().position(|bit| *bit).unwrap()
+    }
+
+    #[test]
+    fn test_stepper_sequence() {
+        let mut stepper = Stepper::new(6);
+        assert_eq!(step(&mut stepper, true), 0);
+        for expected in 1..14 {
+            assert_eq!(step(&mut stepper, false), expected % 6);
+        }
+        // 13 % 6 = 1, back to the first step
+        assert_eq!(step(&mut stepper, true), 0);
+    }
+
+    #[test]
+    fn test_stepper_starts_without_reset() {
+        let mut stepper = Stepper::new(4);
+        // whatever the flip-flops hold after power on, the stepper reaches the
+        // sequence within k steps
+        for _ in 0..4 {
+            stepper.input(&[false, false]);
+            stepper.input(&[true, false]);
+        }
+        let first = step(&mut stepper, false);
+        assert_eq!(step(&mut stepper, false), (first + 1) % 4);
+    }
+}
@@ -0,0 +1,111 @@
+use crate::component::{Component, port, truth_table::TruthTable};
+
+/// when the outputs of a component follow its inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timing {
+    /// the outputs follow the inputs in the same `update_state`.
+    Combinational,
+    /// the outputs follow the levels of the inputs, and hold a state otherwise, like a
+    /// latch.
+    Level,
+    /// the outputs change on the rising edge of the clock pin.
+    RisingEdge { clock: usize },
+}
+
+/// the metadata of a component, which is queried at runtime and rendered to a
+/// datasheet by [`datasheet`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metadata {
+    pub name: &'static str,
+    /// a sentence of what the component does.
+    pub description: &'static str,
+    pub timing: Timing,
+}
+
+/// the most input pins of a combinational component whose truth table is in its
+/// datasheet.
+pub const MAX_TRUTH_TABLE_INPUTS: usize = 6;
+
+/// Render the datasheet of a component as a markdown page, with its metadata, its
+/// pins, its ports and, for a small combinational component, its truth table.
+///
+/// the truth table is run from the component, which is left with the last row on
+/// its inputs.
+///
+/// # Examples
+///
+/// ```
+/// use simulation_computer_rust::component::{adder::HalfAdder, datasheet::datasheet};
+///
+/// let page = datasheet(&mut HalfAdder::default()).unwrap();
+/// assert!(page.starts_with("# half adder\n"));
+/// assert!(page.contains("| 1 | 1 | 0 | 1 |"));
+/// ```
+///
+/// # Returns
+/// the page, or none when the component has no metadata.
+pub fn datasheet<C: Component + ?Sized>(component: &mut C) -> Option<String> {
+    let metadata = component.metadata()?;
+    let (inputs, outputs) = component.get_pin_count();
+    let mut page = format!("# {}\n\n{}\n\n", metadata.name, metadata.description);
+    page.push_str(&format!(
+        "## pins\n\n{} inputs, {} outputs.\n\n",
+        inputs, outputs
+    ));
+    if !component.ports().is_empty() {
+        page.push_str(&format!("## ports\n\n{}\n", port::markdown(component)));
+    }
+    let timing = match metadata.timing {
+        Timing::Combinational => "combinational.".to_string(),
+        Timing::Level => "level sensitive.".to_string(),
+        Timing::RisingEdge { clock } => format!("rising edge of the clock pin {}.", clock),
+    };
+    page.push_str(&format!("## timing\n\n{}\n", timing));
+    if metadata.timing == Timing::Combinational && inputs <= MAX_TRUTH_TABLE_INPUTS {
+        let table = TruthTable::exhaustive(component);
+        page.push_str(&format!("\n## truth table\n\n{}", table.to_markdown()));
+    }
+    Some(page)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::{
+        adder::{FullAdder, RippleCarryAdderN},
+        counter::JohnsonCounterN,
+        latch::SRLatch,
+        mux::DemuxN,
+    };
+
+    #[test]
+    fn test_datasheet_full_adder() {
+        let page = datasheet(&mut FullAdder::default()).unwrap();
+        assert!(page.starts_with("# full adder\n\n"));
+        assert!(page.contains("3 inputs, 2 outputs."));
+        assert!(page.contains("| carry_in | input | 2..3 | 1 |"));
+        assert!(page.contains("## truth table"));
+        assert_eq!(page.matches("\n| 1 | 1 | 1 | 1 | 1 |").count(), 1);
+    }
+
+    #[test]
+    fn test_datasheet_without_truth_table() {
+        // too many inputs
+        let page = datasheet(&mut RippleCarryAdderN::new(8)).unwrap();
+        assert!(!page.contains("## truth table"));
+        // not combinational
+        let page = datasheet(&mut SRLatch::default()).unwrap();
+        assert!(page.contains("level sensitive."));
+        assert!(!page.contains("## truth table"));
+        assert!(!page.contains("## ports"));
+    }
+
+    #[test]
+    fn test_datasheet_metadata() {
+        assert_eq!(
+            DemuxN::new(2).metadata().map(|metadata| metadata.timing),
+            Some(Timing::Combinational)
+        );
+        assert_eq!(datasheet(&mut JohnsonCounterN::new(4)), None);
+    }
+}
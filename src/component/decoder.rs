@@ -1,10 +1,10 @@
 use crate::{
     circuit::{ANDGate, NOTGate, Potential, Wire},
-    component::Component,
+    component::{Component, big_gates::ORGateN, fanout::Fanout},
 };
 
 #[derive(Debug, Default, Clone)]
-struct Decoder1_2 {
+pub struct Decoder1_2 {
     input: [Wire; 1],
     output: [Wire; 2],
     not_gate: NOTGate,
@@ -41,7 +41,7 @@ impl Component for Decoder1_2 {
 }
 
 #[derive(Debug, Default, Clone)]
-struct Decoder2_4 {
+pub struct Decoder2_4 {
     input: [Wire; 2],
     output: [Wire; 4],
     not_gate: [NOTGate; 2],
@@ -85,16 +85,221 @@ impl Component for Decoder2_4 {
     }
 }
 
+/// a n-2^n decoder in circuit.
+/// the input is n bits, and the output is 2^n bits.
+///
+/// # input
+/// the n bits of the binary value, the first bit is the lowest bit.
+///
+/// # output
+/// only the output at the position of the binary value is high.
+///
+/// # truth table (n = 2)
+/// | I0 | I1 | O0 | O1 | O2 | O3 |
+/// |---|---|---|---|---|---|
+/// | 0 | 0 | 1 | 0 | 0 | 0 |
+/// | 1 | 0 | 0 | 1 | 0 | 0 |
+/// | 0 | 1 | 0 | 0 | 1 | 0 |
+/// | 1 | 1 | 0 | 0 | 0 | 1 |
+#[derive(Debug, Clone)]
+pub struct DecoderN {
+    width: usize,
+    input: Vec<Wire>,
+    output: Vec<Wire>,
+    not_gates: Vec<NOTGate>,
+    // and_gates[k - 1][j]: the and of the literals of bits 0..=k of j, the outputs
+    // sharing their lower bits share the gates of them
+    and_gates: Vec<Vec<ANDGate>>,
+    // fanouts[k][j]: the and of bits 0..=k of j, to the two gates of bit k + 1
+    fanouts: Vec<Vec<Fanout>>,
+}
+
+impl DecoderN {
+    pub fn new(width: usize) -> Self {
+        assert!(width > 0, "width must be greater than 0");
+        Self {
+            width,
+            input: vec![Wire::default(); width],
+            output: vec![Wire::default(); 1 << width],
+            not_gates: vec![NOTGate::default(); width],
+            and_gates: (1..width)
+                .map(|k| vec![ANDGate::default(); 2 << k])
+                .collect(),
+            fanouts: (0..width - 1)
+                .map(|k| vec![Fanout::new(2); 2 << k])
+                .collect(),
+        }
+    }
+
+    /// Get the literal of bit `i`, the input itself when `high`, otherwise the inverted input.
+    fn literal(&self, i: usize, high: bool) -> Potential {
+        if high {
+            self.input[i].output()
+        } else {
+            self.not_gates[i].output()
+        }
+    }
+}
+
+impl Component for DecoderN {
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+
+    fn update_state(&mut self) {
+        for i in 0..self.width {
+            self.not_gates[i].input(&self.input[i].output());
+        }
+        let mut terms = vec![self.literal(0, false), self.literal(0, true)];
+        for k in 1..self.width {
+            for (j, term) in terms.iter().enumerate() {
+                self.fanouts[k - 1][j].input(&[*term]);
+            }
+            let low = terms.len();
+            terms = Vec::with_capacity(2 * low);
+            for j in 0..2 * low {
+                // bit k of j takes the second output of the fanout of its lower bits
+                let high = j >= low;
+                let term = self.fanouts[k - 1][j % low].get_pin_output(high as usize);
+                let literal = self.literal(k, high);
+                self.and_gates[k - 1][j].input(&term, &literal);
+                terms.push(self.and_gates[k - 1][j].output());
+            }
+        }
+        for (j, term) in terms.iter().enumerate() {
+            self.output[j].input(term);
+        }
+    }
+
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.width, 1 << self.width)
+    }
+}
+
+/// Binary to one-hot converter, which is a n-2^n decoder.
+pub type BinaryToOneHot = DecoderN;
+
+/// a bcd to seven-segment decoder in circuit.
+/// the input is 4 bits, and the output is 7 bits.
+///
+/// ```ascii
+///  ─a─
+/// f   b
+///  ─g─
+/// e   c
+///  ─d─
+/// ```
+///
+/// the bcd digit is decoded to one-hot, and each segment is the or of the digits
+/// that light it. the inputs 10 to 15 are not bcd digits and light no segment.
+///
+/// # input
+/// the bcd digit, the first bit is the lowest bit.
+///
+/// # output
+/// the segments a, b, c, d, e, f, g.
+#[derive(Debug, Clone)]
+pub struct SevenSegmentDecoder {
+    decoder: DecoderN,
+    segments: Vec<ORGateN>,
+    output: [Wire; 7],
+}
+
+impl SevenSegmentDecoder {
+    /// digits lighting each of the segments a, b, c, d, e, f, g.
+    const SEGMENT_DIGITS: [&'static [usize]; 7] = [
+        &[0, 2, 3, 5, 6, 7, 8, 9],
+        &[0, 1, 2, 3, 4, 7, 8, 9],
+        &[0, 1, 3, 4, 5, 6, 7, 8, 9],
+        &[0, 2, 3, 5, 6, 8, 9],
+        &[0, 2, 6, 8],
+        &[0, 4, 5, 6, 8, 9],
+        &[2, 3, 4, 5, 6, 8, 9],
+    ];
+}
+
+impl Default for SevenSegmentDecoder {
+    fn default() -> Self {
+        Self {
+            decoder: DecoderN::new(4),
+            segments: Self::SEGMENT_DIGITS
+                .iter()
+                .map(|digits| ORGateN::new(digits.len()))
+                .collect(),
+            output: [Wire::default(); 7],
+        }
+    }
+}
+
+impl Component for SevenSegmentDecoder {
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.decoder.set_pin_input(position, value);
+    }
+
+    fn update_state(&mut self) {
+        self.decoder.update_state();
+        for (i, digits) in Self::SEGMENT_DIGITS.iter().enumerate() {
+            let lighting: Vec<Potential> = digits
+                .iter()
+                .map(|digit| self.decoder.get_pin_output(*digit))
+                .collect();
+            self.segments[i].input(&lighting);
+            self.output[i].input(&self.segments[i].get_pin_output(0));
+        }
+    }
+
+    fn get_pin_count(&self) -> (usize, usize) {
+        (4, 7)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
-        circuit::Potentials,
-        component::{adder, decoder},
+        circuit::{Padding, Potentials},
+        component::{adder, decoder, truth_table::TruthTable},
     };
 
     use super::*;
     use rstest::rstest;
 
+    #[test]
+    fn test_decoder_docs() {
+        // the truth table in the doc comment is the behavior of the decoder
+        let source = include_str!("decoder.rs");
+        let table = TruthTable::exhaustive(&mut DecoderN::new(2))
+            .with_names(&["I0", "I1"], &["O0", "O1", "O2", "O3"]);
+        assert!(source.contains(&table.to_doc_comment()));
+    }
+
     #[test]
     fn test_decoder1_2_default() {
         let decoder = Decoder1_2::default();
@@ -132,4 +337,86 @@ mod tests {
         decoder.input(&input);
         assert_eq!(decoder.output(), output);
     }
+
+    #[test]
+    fn test_decoder_n_default() {
+        let decoder = DecoderN::new(3);
+        assert_eq!(decoder.output(), vec![false; 8]);
+    }
+
+    #[rstest]
+    #[case(vec![false,false], vec![true, false,false,false])]
+    #[case(vec![true,false], vec![false, true,false,false])]
+    #[case(vec![false,true], vec![false, false,true,false])]
+    #[case(vec![true,true], vec![false, false,false,true])]
+    fn test_decoder_n_2_with_truth_table(
+        #[case] input: Vec<Potential>,
+        #[case] output: Vec<Potential>,
+    ) {
+        let mut decoder = DecoderN::new(2);
+        decoder.input(&input);
+        assert_eq!(decoder.output(), output);
+    }
+
+    #[rstest]
+    #[case(vec![false], vec![true, false])]
+    #[case(vec![true], vec![false, true])]
+    fn test_decoder_n_1_with_truth_table(
+        #[case] input: Vec<Potential>,
+        #[case] output: Vec<Potential>,
+    ) {
+        let mut decoder = DecoderN::new(1);
+        decoder.input(&input);
+        assert_eq!(decoder.output(), output);
+    }
+
+    #[test]
+    fn test_decoder_n_4() {
+        let mut decoder = DecoderN::new(4);
+        for value in 0..16 {
+            let input: Vec<Potential> = (0..4).map(|i| (value >> i) & 1 == 1).collect();
+            decoder.input(&input);
+            let expected: Vec<Potential> = (0..16).map(|j| j == value).collect();
+            assert_eq!(decoder.output(), expected);
+        }
+    }
+
+    #[test]
+    fn test_binary_to_one_hot() {
+        let mut converter = BinaryToOneHot::new(3);
+        for value in 0..8 {
+            let input: Vec<Potential> = (0..3).map(|i| (value >> i) & 1 == 1).collect();
+            converter.input(&input);
+            let expected: Vec<Potential> = (0..8).map(|j| j == value).collect();
+            assert_eq!(converter.output(), expected);
+        }
+    }
+
+    #[test]
+    fn test_seven_segment_decoder_default() {
+        let decoder = SevenSegmentDecoder::default();
+        assert_eq!(decoder.output(), vec![false; 7]);
+    }
+
+    #[rstest]
+    // a b c d e f g
+    #[case(0, "1111110")]
+    #[case(1, "0110000")]
+    #[case(2, "1101101")]
+    #[case(3, "1111001")]
+    #[case(4, "0110011")]
+    #[case(5, "1011011")]
+    #[case(6, "1011111")]
+    #[case(7, "1110000")]
+    #[case(8, "1111111")]
+    #[case(9, "1111011")]
+    #[case(10, "0000000")]
+    #[case(15, "0000000")]
+    fn test_seven_segment_decoder_with_truth_table(#[case] digit: usize, #[case] segments: String) {
+        let mut decoder = SevenSegmentDecoder::default();
+        let input: Vec<Potential> = (0..4).map(|i| (digit >> i) & 1 == 1).collect();
+        decoder.input(&input);
+        let expected = crate::circuit::Potentials::from_little_endian(&segments, Padding::Keep);
+        assert_eq!(decoder.output(), expected.get_data(true));
+    }
 }
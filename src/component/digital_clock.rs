@@ -0,0 +1,237 @@
+use crate::{
+    circuit::{ANDGate, ORGate, Potential, Wire},
+    component::{
+        Component, big_gates::ANDGate3, counter::BcdCounter, decoder::SevenSegmentDecoder,
+    },
+};
+
+/// the position of each digit in the counters and the decoders of [`DigitalClock`].
+const SECOND_UNITS: usize = 0;
+const SECOND_TENS: usize = 1;
+const MINUTE_UNITS: usize = 2;
+const MINUTE_TENS: usize = 3;
+const HOUR_UNITS: usize = 4;
+const HOUR_TENS: usize = 5;
+
+/// a 24-hour digital clock in circuit.
+/// the input is 2 bits, and the output is 42 bits.
+///
+/// six cascaded [`BcdCounter`] share the clock, each decade is enabled by the
+/// terminal of the previous one. the tens of seconds and minutes are cleared
+/// after 5, and all hour digits are cleared after 23. every digit drives a
+/// [`SevenSegmentDecoder`].
+///
+/// ```ascii
+///             ┌────┐  ┌────┐   ┌────┐  ┌────┐   ┌────┐  ┌────┐
+///             │ HT │  │ HU │ : │ MT │  │ MU │ : │ ST │  │ SU │
+///             └─┬──┘  └─┬──┘   └─┬──┘  └─┬──┘   └─┬──┘  └─┬──┘
+///               └──◄────┴──◄─────┴──◄────┴──◄─────┴──◄────┴──── clock
+/// ```
+///
+/// # input
+/// the first bit is the 1 Hz clock, and the second bit is the synchronous reset.
+///
+/// # output
+/// 7 segments (a to g) for each digit, in the order of seconds units, seconds tens,
+/// minutes units, minutes tens, hours units and hours tens.
+#[derive(Debug, Default, Clone)]
+pub struct DigitalClock {
+    input: [Wire; 2],
+    clock: Wire,
+    counters: [BcdCounter; 6],
+    decoders: [SevenSegmentDecoder; 6],
+    // tens == 5, only bit 0 and bit 2 are set among 0 to 5
+    second_tens_five: ANDGate,
+    minute_tens_five: ANDGate,
+    second_carry: ANDGate,
+    minute_carry: ANDGate,
+    second_clear: ORGate,
+    minute_clear: ORGate,
+    // hours == 23, hour units never passes 3 while hour tens is 2
+    twenty_three: ANDGate3,
+    day_end: ANDGate,
+    hour_clear: ORGate,
+}
+
+impl DigitalClock {
+    /// Settle the carry chain of the counters on the current state.
+    fn settle(&mut self, clock: &Potential) {
+        let reset = self.input[1].output();
+
+        self.counters[SECOND_UNITS].input(&[*clock, reset, true, false]);
+        let units_carry = self.counters[SECOND_UNITS].get_pin_output(4);
+        self.second_tens_five.input(
+            &self.counters[SECOND_TENS].get_pin_output(0),
+            &self.counters[SECOND_TENS].get_pin_output(2),
+        );
+        self.second_carry
+            .input(&self.second_tens_five.output(), &units_carry);
+        self.second_clear.input(&reset, &self.second_carry.output());
+        self.counters[SECOND_TENS].input(&[*clock, self.second_clear.output(), units_carry, false]);
+
+        self.counters[MINUTE_UNITS].input(&[*clock, reset, self.second_carry.output(), false]);
+        let units_carry = self.counters[MINUTE_UNITS].get_pin_output(4);
+        self.minute_tens_five.input(
+            &self.counters[MINUTE_TENS].get_pin_output(0),
+            &self.counters[MINUTE_TENS].get_pin_output(2),
+        );
+        self.minute_carry
+            .input(&self.minute_tens_five.output(), &units_carry);
+        self.minute_clear.input(&reset, &self.minute_carry.output());
+        self.counters[MINUTE_TENS].input(&[*clock, self.minute_clear.output(), units_carry, false]);
+
+        self.twenty_three.input(&[
+            self.counters[HOUR_TENS].get_pin_output(1),
+            self.counters[HOUR_UNITS].get_pin_output(0),
+            self.counters[HOUR_UNITS].get_pin_output(1),
+        ]);
+        self.day_end.input(
+            &self.twenty_three.get_pin_output(0),
+            &self.minute_carry.output(),
+        );
+        self.hour_clear.input(&reset, &self.day_end.output());
+        self.counters[HOUR_UNITS].input(&[
+            *clock,
+            self.hour_clear.output(),
+            self.minute_carry.output(),
+            false,
+        ]);
+        let units_carry = self.counters[HOUR_UNITS].get_pin_output(4);
+        self.counters[HOUR_TENS].input(&[*clock, self.hour_clear.output(), units_carry, false]);
+    }
+}
+
+impl Component for DigitalClock {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (2, 42)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.decoders[position / 7].get_pin_output(position % 7)
+    }
+    fn update_state(&mut self) {
+        // the carry chain settles before the clock edge reaches all counters together
+        self.settle(&self.clock.output());
+        let clock = self.input[0].output();
+        for counter in self.counters.iter_mut() {
+            counter.set_pin_input(0, &clock);
+            counter.update_state();
+        }
+        self.clock.input(&clock);
+        for (counter, decoder) in self.counters.iter().zip(self.decoders.iter_mut()) {
+            let digit: Vec<Potential> = (0..4).map(|i| counter.get_pin_output(i)).collect();
+            decoder.input(&digit);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the segments a to g of the digits 0 to 9.
+    const DIGITS: [[bool; 7]; 10] = [
+        [true, true, true, true, true, true, false],
+        [false, true, true, false, false, false, false],
+        [true, true, false, true, true, false, true],
+        [true, true, true, true, false, false, true],
+        [false, true, true, false, false, true, true],
+        [true, false, true, true, false, true, true],
+        [true, false, true, true, true, true, true],
+        [true, true, true, false, false, false, false],
+        [true, true, true, true, true, true, true],
+        [true, true, true, true, false, true, true],
+    ];
+
+    /// read the display as hh:mm:ss.
+    fn display(clock: &DigitalClock) -> String {
+        let output = clock.output();
+        let digits: Vec<usize> = output
+            .chunks(7)
+            .map(|segments| {
+                DIGITS
+                    .iter()
+                    .position(|digit| digit == segments)
+                    .expect("segments do not show a digit")
+            })
+            .collect();
+        format!(
+            "{}{}:{}{}:{}{}",
+            digits[HOUR_TENS],
+            digits[HOUR_UNITS],
+            digits[MINUTE_TENS],
+            digits[MINUTE_UNITS],
+            digits[SECOND_TENS],
+            digits[SECOND_UNITS]
+        )
+    }
+
+    fn tick(clock: &mut DigitalClock, seconds: usize) {
+        for _ in 0..seconds {
+            clock.input(&[false, false]);
+            clock.input(&[true, false]);
+        }
+    }
+
+    #[test]
+    fn test_digital_clock_reset() {
+        let mut clock = DigitalClock::default();
+        clock.input(&[false, true]);
+        clock.input(&[true, true]);
+        assert_eq!(display(&clock), "00:00:00");
+        tick(&mut clock, 75);
+        clock.input(&[false, true]);
+        clock.input(&[true, true]);
+        assert_eq!(display(&clock), "00:00:00");
+    }
+
+    #[test]
+    fn test_digital_clock_counts() {
+        let mut clock = DigitalClock::default();
+        clock.input(&[false, true]);
+        clock.input(&[true, true]);
+        tick(&mut clock, 59);
+        assert_eq!(display(&clock), "00:00:59");
+        tick(&mut clock, 1);
+        assert_eq!(display(&clock), "00:01:00");
+        tick(&mut clock, 3601);
+        assert_eq!(display(&clock), "01:01:01");
+    }
+
+    /// preset the digits in the order of [`SECOND_UNITS`] to [`HOUR_TENS`], by
+    /// counting every counter up on its own.
+    fn preset(clock: &mut DigitalClock, digits: [usize; 6]) {
+        clock.input(&[false, true]);
+        clock.input(&[true, true]);
+        for (counter, digit) in clock.counters.iter_mut().zip(digits) {
+            for _ in 0..digit {
+                counter.input(&[false, false, true, false]);
+                counter.input(&[true, false, true, false]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_digital_clock_wraps_at_midnight() {
+        let mut clock = DigitalClock::default();
+        preset(&mut clock, [5, 5, 9, 5, 3, 2]);
+        tick(&mut clock, 4);
+        assert_eq!(display(&clock), "23:59:59");
+        tick(&mut clock, 1);
+        assert_eq!(display(&clock), "00:00:00");
+        tick(&mut clock, 61);
+        assert_eq!(display(&clock), "00:01:01");
+    }
+}
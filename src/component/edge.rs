@@ -0,0 +1,285 @@
+use crate::{
+    circuit::{ANDGate, NOTGate, Potential, Wire, XORGate, init},
+    component::{Component, counter::Incrementer},
+};
+
+/// a edge detector in circuit.
+/// the input is 1 bit, and the output is 2 bits.
+///
+/// the detector remembers the input of the previous `update_state`,
+/// so every call of `update_state` is one sample of the input.
+///
+/// ```ascii
+///          ┌─────────────────┐
+///          │                 ┼─────Rising
+/// In───────┼  Edge Detector  │
+///          │                 ┼─────Falling
+///          └─────────────────┘
+/// ```
+///
+/// # output
+/// the first bit is high when the input changes from low to high,
+/// the second bit is high when the input changes from high to low.
+#[derive(Debug, Default, Clone)]
+pub struct EdgeDetector {
+    input: [Wire; 1],
+    output: [Wire; 2],
+    previous: Wire,
+    not_gate: [NOTGate; 2],
+    and_gate: [ANDGate; 2],
+}
+
+impl EdgeDetector {
+    /// Whether the last sample is a rising edge.
+    pub fn is_rising(&self) -> bool {
+        self.output[0].output()
+    }
+
+    /// Whether the last sample is a falling edge.
+    pub fn is_falling(&self) -> bool {
+        self.output[1].output()
+    }
+}
+
+impl Component for EdgeDetector {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (1, 2)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+    fn update_state(&mut self) {
+        let current = self.input[0].output();
+        self.not_gate[0].input(&self.previous.output());
+        self.not_gate[1].input(&current);
+        // rising = current and not previous
+        self.and_gate[0].input(&current, &self.not_gate[0].output());
+        // falling = previous and not current
+        self.and_gate[1].input(&self.previous.output(), &self.not_gate[1].output());
+        self.output[0].input(&self.and_gate[0].output());
+        self.output[1].input(&self.and_gate[1].output());
+        self.previous.input(&current);
+    }
+}
+
+/// a counter-based debouncer in circuit.
+/// the input is 3 bits, and the output is 1 bit.
+///
+/// ```ascii
+///          ┌─────────────────┐
+/// In───────┼                 │
+/// Clock────┼    Debouncer    ┼─────Out
+/// Reset────┼                 │
+///          └─────────────────┘
+/// ```
+///
+/// # input
+/// the first bit is the bouncing input, the second bit is the clock, and the third
+/// bit is the synchronous reset, which clears the counter and the output on the
+/// rising edge of the clock.
+///
+/// # output
+/// the output follows the input only after the input has differed from the output
+/// on `threshold` successive rising edges of the clock. any sample that agrees with
+/// the output resets the counter.
+#[derive(Debug, Clone)]
+pub struct Debouncer {
+    threshold: usize,
+    input: [Wire; 3],
+    output: Wire,
+    count: Vec<Wire>,
+    clock_edge: EdgeDetector,
+    differ: XORGate,
+    incrementer: Incrementer,
+    not_reached: NOTGate,
+    keep_counting: ANDGate,
+    flip: ANDGate,
+    next_output: XORGate,
+    not_reset: NOTGate,
+    // the next counter and output, or 0 while reset is high
+    keep_count: ANDGate,
+    keep_output: ANDGate,
+}
+
+impl Debouncer {
+    pub fn new(threshold: usize) -> Self {
+        assert!(threshold > 0, "threshold must be greater than 0");
+        let width = (usize::BITS - threshold.leading_zeros()) as usize;
+        Self {
+            threshold,
+            input: [Wire::default(); 3],
+            output: init::power_up_wire(),
+            count: init::power_up(width),
+            clock_edge: EdgeDetector::default(),
+            differ: XORGate::default(),
+            incrementer: Incrementer::new(width),
+            not_reached: NOTGate::default(),
+            keep_counting: ANDGate::default(),
+            flip: ANDGate::default(),
+            next_output: XORGate::default(),
+            not_reset: NOTGate::default(),
+            keep_count: ANDGate::default(),
+            keep_output: ANDGate::default(),
+        }
+    }
+}
+
+impl Component for Debouncer {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (3, 1)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output.output()
+    }
+    fn update_state(&mut self) {
+        self.clock_edge.input(&[self.input[1].output()]);
+
+        self.differ
+            .input(&self.input[0].output(), &self.output.output());
+        // count + 1 == threshold
+        self.incrementer.increment(&self.count, &true);
+        let reached = self.incrementer.reaches(self.threshold);
+        self.not_reached.input(&reached);
+        self.keep_counting
+            .input(&self.differ.output(), &self.not_reached.output());
+        self.not_reset.input(&self.input[2].output());
+        self.keep_count
+            .input(&self.keep_counting.output(), &self.not_reset.output());
+        let count = self.incrementer.next(&self.keep_count.output());
+        self.flip.input(&self.differ.output(), &reached);
+        self.next_output
+            .input(&self.output.output(), &self.flip.output());
+        self.keep_output
+            .input(&self.next_output.output(), &self.not_reset.output());
+
+        // the registers only load on the rising edge of the clock
+        if self.clock_edge.is_rising() {
+            for (wire, bit) in self.count.iter_mut().zip(count) {
+                wire.input(&bit);
+            }
+            self.output.input(&self.keep_output.output());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn test_edge_detector_default() {
+        let detector = EdgeDetector::default();
+        assert_eq!(detector.output(), vec![false, false]);
+    }
+
+    #[rstest]
+    #[case(false, false, vec![false, false])]
+    #[case(false, true, vec![true, false])]
+    #[case(true, false, vec![false, true])]
+    #[case(true, true, vec![false, false])]
+    fn test_edge_detector_with_truth_table(
+        #[case] previous: bool,
+        #[case] current: bool,
+        #[case] expected: Vec<Potential>,
+    ) {
+        let mut detector = EdgeDetector::default();
+        detector.input(&[previous]);
+        detector.input(&[current]);
+        assert_eq!(detector.output(), expected);
+    }
+
+    /// feed a sample and a full clock cycle into the debouncer.
+    fn tick(debouncer: &mut Debouncer, sample: bool) -> Potential {
+        debouncer.input(&[sample, false, false]);
+        debouncer.input(&[sample, true, false]);
+        debouncer.output()[0]
+    }
+
+    /// create a debouncer and clear whatever it powers up with.
+    fn reset(threshold: usize) -> Debouncer {
+        let mut debouncer = Debouncer::new(threshold);
+        debouncer.input(&[false, false, true]);
+        debouncer.input(&[false, true, true]);
+        debouncer
+    }
+
+    #[test]
+    fn test_debouncer_default() {
+        let debouncer = reset(3);
+        assert_eq!(debouncer.output(), vec![false]);
+    }
+
+    #[test]
+    fn test_debouncer_reset() {
+        let mut debouncer = reset(2);
+        tick(&mut debouncer, true);
+        assert!(tick(&mut debouncer, true));
+        // a stable input does not survive the reset
+        tick(&mut debouncer, false);
+        debouncer.input(&[false, false, true]);
+        debouncer.input(&[false, true, true]);
+        assert!(!debouncer.output()[0]);
+        assert!(!tick(&mut debouncer, true));
+        assert!(tick(&mut debouncer, true));
+    }
+
+    #[test]
+    fn test_debouncer_filters_bounce() {
+        let mut debouncer = reset(3);
+        let samples = [true, false, true, true, false, true, false, false];
+        for sample in samples {
+            assert!(!tick(&mut debouncer, sample));
+        }
+    }
+
+    #[test]
+    fn test_debouncer_follows_stable_input() {
+        let mut debouncer = reset(3);
+        assert!(!tick(&mut debouncer, true));
+        assert!(!tick(&mut debouncer, true));
+        assert!(tick(&mut debouncer, true));
+        assert!(tick(&mut debouncer, false));
+        assert!(tick(&mut debouncer, true));
+        assert!(tick(&mut debouncer, false));
+        assert!(tick(&mut debouncer, false));
+        assert!(!tick(&mut debouncer, false));
+    }
+
+    #[test]
+    fn test_debouncer_ignores_input_without_clock() {
+        let mut debouncer = reset(1);
+        for _ in 0..4 {
+            debouncer.input(&[true, false, false]);
+        }
+        assert_eq!(debouncer.output(), vec![false]);
+        debouncer.input(&[true, true, false]);
+        assert_eq!(debouncer.output(), vec![true]);
+    }
+}
@@ -25,7 +25,7 @@ use crate::{
 /// if the switcher is high, the output is the input.
 /// if the switcher is low, the output is low.
 #[derive(Debug, Default, Clone)]
-struct EnablerN {
+pub struct EnablerN {
     n_way: usize,
     input: Vec<Wire>,
     and_gates: Vec<ANDGate>,
@@ -14,7 +14,7 @@ use crate::{
 /// | 1 | 0 | 0 |
 /// | 0 | 1 | 1 |
 #[derive(Debug, Default, Clone)]
-struct Encoder2_1 {
+pub struct Encoder2_1 {
     input: [Wire; 2],
     output: [Wire; 1],
 }
@@ -81,7 +81,7 @@ impl Component for Encoder2_1 {
 ///  1: "out1"
 /// ```
 #[derive(Debug, Default, Clone)]
-struct Encoder4_2 {
+pub struct Encoder4_2 {
     input: [Wire; 4],
     output: [Wire; 2],
     or_gates: [ORGate; 2],
@@ -160,7 +160,7 @@ impl Component for Encoder4_2 {
 /// ```
 ///
 #[derive(Debug, Default, Clone)]
-struct PriorityEncoder4_2 {
+pub struct PriorityEncoder4_2 {
     input: [Wire; 4],
     output: [Wire; 3],
     or_gate_1: ORGate,
@@ -203,7 +203,7 @@ impl Component for PriorityEncoder4_2 {
             .input(&self.input[3].output(), &self.and_gate.output());
         let o0 = &self.or_gate_2.output();
         self.output[0].input(o0);
-        self.big_or.input(&vec![
+        self.big_or.input(&[
             o1.to_owned(),
             self.input[0].output(),
             self.input[1].output(),
@@ -213,11 +213,149 @@ impl Component for PriorityEncoder4_2 {
     }
 }
 
+/// One-hot to binary converter.
+///
+/// # description
+/// convert 2^n one-hot bits to n binary bits, the first output bit is the lowest bit.
+/// unlike the [Simple Encoder](https://en.wikipedia.org/wiki/Encoder_(digital)),
+/// a validity output reports whether exactly one input bit is high.
+///
+/// # truth table (n = 2)
+///
+/// | I0 | I1 | I2 | I3 | OUT0 | OUT1 | v |
+/// |---|---|---|---|---|---|---|
+/// | 0 | 0 | 0 | 0 | 0 | 0 | 0 |
+/// | 1 | 0 | 0 | 0 | 0 | 0 | 1 |
+/// | 0 | 1 | 0 | 0 | 1 | 0 | 1 |
+/// | 0 | 0 | 1 | 0 | 0 | 1 | 1 |
+/// | 0 | 0 | 0 | 1 | 1 | 1 | 1 |
+/// | more than one 1 | | | | any | any | 0 |
+///
+/// # output
+///
+/// ```mermaid
+///  ---
+///  title: "out Packet"
+///  ---
+///  packet-beta
+///  0: "out0"
+///  1: "out1"
+///  2: "v"
+/// ```
+#[derive(Debug, Clone)]
+pub struct OneHotToBinary {
+    width: usize,
+    input: Vec<Wire>,
+    output: Vec<Wire>,
+    // each output bit owns a chain of or gates over the inputs whose index has the bit set
+    or_gates: Vec<Vec<ORGate>>,
+    // seen_gates[i - 1]: any of input 0..=i is high
+    seen_gates: Vec<ORGate>,
+    // multiple_and_gates[i - 1]: input i is high and an earlier input is high
+    multiple_and_gates: Vec<ANDGate>,
+    // multiple_or_gates[i - 2]: more than one of input 0..=i is high
+    multiple_or_gates: Vec<ORGate>,
+    not_gate: NOTGate,
+    valid_gate: ANDGate,
+}
+
+impl OneHotToBinary {
+    pub fn new(width: usize) -> Self {
+        assert!(width > 0, "width must be greater than 0");
+        let n_way = 1 << width;
+        Self {
+            width,
+            input: vec![Wire::default(); n_way],
+            output: vec![Wire::default(); width + 1],
+            or_gates: vec![vec![ORGate::default(); n_way / 2 - 1]; width],
+            seen_gates: vec![ORGate::default(); n_way - 1],
+            multiple_and_gates: vec![ANDGate::default(); n_way - 1],
+            multiple_or_gates: vec![ORGate::default(); n_way - 2],
+            not_gate: NOTGate::default(),
+            valid_gate: ANDGate::default(),
+        }
+    }
+}
+
+impl Component for OneHotToBinary {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (1 << self.width, self.width + 1)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+    fn update_state(&mut self) {
+        let n_way = self.input.len();
+        for bit in 0..self.width {
+            let selected: Vec<Potential> = (0..n_way)
+                .filter(|i| (i >> bit) & 1 == 1)
+                .map(|i| self.input[i].output())
+                .collect();
+            let mut cursor = selected[0];
+            for (i, value) in selected.iter().enumerate().skip(1) {
+                self.or_gates[bit][i - 1].input(&cursor, value);
+                cursor = self.or_gates[bit][i - 1].output();
+            }
+            self.output[bit].input(&cursor);
+        }
+
+        let mut seen = self.input[0].output();
+        let mut multiple = false;
+        for i in 1..n_way {
+            let current = self.input[i].output();
+            self.multiple_and_gates[i - 1].input(&seen, &current);
+            if i == 1 {
+                multiple = self.multiple_and_gates[0].output();
+            } else {
+                self.multiple_or_gates[i - 2]
+                    .input(&multiple, &self.multiple_and_gates[i - 1].output());
+                multiple = self.multiple_or_gates[i - 2].output();
+            }
+            self.seen_gates[i - 1].input(&seen, &current);
+            seen = self.seen_gates[i - 1].output();
+        }
+        // v = seen and not multiple
+        self.not_gate.input(&multiple);
+        self.valid_gate.input(&seen, &self.not_gate.output());
+        self.output[self.width].input(&self.valid_gate.output());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::component::truth_table::TruthTable;
     use rstest::rstest;
 
+    fn one_hot(width: usize) -> Vec<Vec<Potential>> {
+        (0..width)
+            .map(|i| (0..width).map(|j| i == j).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_encoder_docs() {
+        // the truth tables in the doc comments are the behavior of the encoders
+        let source = include_str!("encoder.rs");
+        let table = TruthTable::of_rows(&mut Encoder2_1::default(), &one_hot(2));
+        assert!(source.contains(&table.to_doc_comment()));
+        let table = TruthTable::of_rows(&mut Encoder4_2::default(), &one_hot(4));
+        assert!(source.contains(&table.to_doc_comment()));
+    }
+
     #[test]
     fn test_encoder2_1_default() {
         let enabler = Encoder2_1::default();
@@ -271,4 +409,52 @@ mod tests {
         enabler.input(&input);
         assert_eq!(enabler.output(), expected);
     }
+    #[test]
+    fn test_one_hot_to_binary_default() {
+        let converter = OneHotToBinary::new(2);
+        assert_eq!(converter.output(), vec![false, false, false]);
+    }
+    #[rstest]
+    #[case(vec![false,false,false,false],vec![false,false,false])]
+    #[case(vec![true,false,false,false],vec![false,false,true])]
+    #[case(vec![false,true,false,false],vec![true,false,true])]
+    #[case(vec![false,false,true,false],vec![false,true,true])]
+    #[case(vec![false,false,false,true],vec![true,true,true])]
+    #[case(vec![true,true,false,false],vec![true,false,false])]
+    #[case(vec![false,true,false,true],vec![true,true,false])]
+    #[case(vec![true,true,true,true],vec![true,true,false])]
+    fn test_one_hot_to_binary_truth_table(
+        #[case] input: Vec<Potential>,
+        #[case] expected: Vec<Potential>,
+    ) {
+        let mut converter = OneHotToBinary::new(2);
+        converter.input(&input);
+        assert_eq!(converter.output(), expected);
+    }
+    #[rstest]
+    #[case(vec![false,false],vec![false,false])]
+    #[case(vec![true,false],vec![false,true])]
+    #[case(vec![false,true],vec![true,true])]
+    #[case(vec![true,true],vec![true,false])]
+    fn test_one_hot_to_binary_1_truth_table(
+        #[case] input: Vec<Potential>,
+        #[case] expected: Vec<Potential>,
+    ) {
+        let mut converter = OneHotToBinary::new(1);
+        converter.input(&input);
+        assert_eq!(converter.output(), expected);
+    }
+    #[test]
+    fn test_one_hot_to_binary_round_trip() {
+        let mut decoder = crate::component::decoder::BinaryToOneHot::new(3);
+        let mut converter = OneHotToBinary::new(3);
+        for value in 0..8 {
+            let binary: Vec<Potential> = (0..3).map(|i| (value >> i) & 1 == 1).collect();
+            decoder.input(&binary);
+            converter.input(&decoder.output());
+            let mut expected = binary.clone();
+            expected.push(true);
+            assert_eq!(converter.output(), expected);
+        }
+    }
 }
@@ -0,0 +1,75 @@
+use crate::{
+    circuit::{Potential, Wire},
+    component::Component,
+};
+
+/// a n-way fan-out in circuit.
+/// the input is 1 bit, and the output is n bits.
+///
+/// the output of a gate feeds the inputs of many gates through a single wire, so a
+/// term used by several parts of a component is computed once and fanned out,
+/// instead of a copy of the gates for every user.
+/// [`DecoderN`](crate::component::decoder::DecoderN) fans the and of the lower bits
+/// out to the outputs sharing them.
+///
+/// ```ascii
+///              ┌──────────┐
+///              │          ├──────o0
+/// In───────────┤  Fanout  ├──────o1
+///              │          ├──────o2
+///              └──────────┘
+/// ```
+///
+/// # output
+/// every output is the input.
+#[derive(Debug, Clone)]
+pub struct Fanout {
+    n_way: usize,
+    wire: Wire,
+}
+
+impl Fanout {
+    pub fn new(n_way: usize) -> Self {
+        assert!(n_way > 0, "n_way must be greater than 0");
+        Self {
+            n_way,
+            wire: Wire::default(),
+        }
+    }
+}
+
+impl Component for Fanout {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (1, self.n_way)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.wire.input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.wire.output()
+    }
+    fn update_state(&mut self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fanout() {
+        let mut fanout = Fanout::new(3);
+        assert_eq!(fanout.output(), vec![false; 3]);
+        fanout.input(&[true]);
+        assert_eq!(fanout.output(), vec![true; 3]);
+    }
+}
@@ -0,0 +1,141 @@
+use std::fmt;
+
+use crate::{circuit::Potential, component::Component};
+
+/// the level of detail a component is simulated at.
+///
+/// a gate level model builds every gate of the component, so its internal signals
+/// can be traced, and a behavioral model computes the same outputs from a host data
+/// structure, which saves the memory and the time of a large memory or register file.
+///
+/// both models are a [`Component`] with the same pins, so a design mixes them freely,
+/// and [`check_equivalent`] verifies a behavioral model against its gates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Fidelity {
+    #[default]
+    Gate,
+    Behavioral,
+}
+
+/// the first step of a workload where two models disagree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub step: usize,
+    pub input: Vec<Potential>,
+    /// the outputs of the reference model.
+    pub expected: Vec<Potential>,
+    pub actual: Vec<Potential>,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "step {}: input {:?} expected {:?}, got {:?}",
+            self.step, self.input, self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+/// Drive the same workload into a reference model and a model swapped for it, and
+/// verify the traces of their outputs are identical.
+///
+/// every step of the workload is the input pins of one `input` call, so a clocked
+/// component is driven by steps with the clock low and then high.
+///
+/// # Examples
+///
+/// ```
+/// use simulation_computer_rust::component::{
+///     fidelity::{Fidelity, check_equivalent},
+///     register::RegisterN,
+/// };
+///
+/// let workload = vec![vec![true, false, true, true], vec![false, true, false, true]];
+/// let mut gates = RegisterN::new(2);
+/// let mut fast = RegisterN::with_fidelity(2, Fidelity::Behavioral);
+/// assert!(check_equivalent(&mut gates, &mut fast, &workload).is_ok());
+/// ```
+///
+/// # Returns
+/// the first mismatch, if any.
+pub fn check_equivalent(
+    reference: &mut dyn Component,
+    model: &mut dyn Component,
+    workload: &[Vec<Potential>],
+) -> Result<(), Mismatch> {
+    assert_eq!(
+        reference.get_pin_count(),
+        model.get_pin_count(),
+        "pin counts must be equal"
+    );
+    for (step, input) in workload.iter().enumerate() {
+        reference.input(input);
+        model.input(input);
+        let expected = reference.output();
+        let actual = model.output();
+        if expected != actual {
+            return Err(Mismatch {
+                step,
+                input: input.clone(),
+                expected,
+                actual,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        circuit::init::Rng,
+        component::{register::RegisterN, rom::Rom},
+    };
+
+    fn workload(width: usize, steps: usize) -> Vec<Vec<Potential>> {
+        let mut rng = Rng::new(7);
+        (0..steps).map(|_| rng.bits(width)).collect()
+    }
+
+    #[test]
+    fn test_equivalent_register() {
+        let mut gates = RegisterN::new(8);
+        let mut fast = RegisterN::with_fidelity(8, Fidelity::Behavioral);
+        assert_eq!(gates.fidelity(), Fidelity::Gate);
+        assert_eq!(fast.fidelity(), Fidelity::Behavioral);
+        assert_eq!(
+            check_equivalent(&mut gates, &mut fast, &workload(10, 500)),
+            Ok(())
+        );
+        assert_eq!(gates.stored(), fast.stored());
+    }
+
+    #[test]
+    fn test_equivalent_rom() {
+        let image: Vec<u8> = (0..64).map(|i| (i * 13) as u8).collect();
+        let mut gates = Rom::new(&image);
+        let mut fast = Rom::with_fidelity(&image, Fidelity::Behavioral);
+        assert_eq!(
+            check_equivalent(&mut gates, &mut fast, &workload(6, 200)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_mismatch() {
+        let mut gates = Rom::new(&[0, 1, 2, 3]);
+        let mut other = Rom::with_fidelity(&[0, 1, 2, 4], Fidelity::Behavioral);
+        let workload: Vec<Vec<Potential>> = (0..4).map(|i| vec![i & 1 == 1, i & 2 == 2]).collect();
+        let mismatch = check_equivalent(&mut gates, &mut other, &workload).unwrap_err();
+        assert_eq!(mismatch.step, 3);
+        assert!(
+            mismatch
+                .to_string()
+                .starts_with("step 3: input [true, true]")
+        );
+    }
+}
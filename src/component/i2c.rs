@@ -0,0 +1,555 @@
+use std::collections::VecDeque;
+
+use crate::{
+    circuit::{Potential, Wire, layout::Layout},
+    component::{Component, edge::EdgeDetector},
+};
+
+/// what the master does with the level of the data line in a step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Sample {
+    /// nothing.
+    None,
+    /// the acknowledge of the peripheral, a high level is a nack.
+    Ack,
+    /// a bit of the byte read, the highest bit first.
+    Data,
+}
+
+/// a third of a bit on the bus, the master holds the lines for a clock cycle.
+#[derive(Debug, Clone, Copy)]
+struct Step {
+    scl: Potential,
+    /// the master releases the data line for a high, and pulls it low otherwise.
+    sda: Potential,
+    /// the data line is sampled before the step, while the clock line is still high.
+    sample: Sample,
+}
+
+const fn step(scl: Potential, sda: Potential) -> Step {
+    Step {
+        scl,
+        sda,
+        sample: Sample::None,
+    }
+}
+
+/// an i2c master, which runs a single byte transaction with a peripheral.
+/// the input is 27 bits, and the output is 12 bits.
+///
+/// a write sends the address with the write bit, the register and the data, a read
+/// sends the address and the register, then a repeated start, the address with the
+/// read bit, and reads a byte which it does not acknowledge. the master holds every
+/// level of the lines for a clock cycle, so a bit takes 3 clock cycles.
+///
+/// the data line is open drain, connect it to the peripherals with a pull up, e.g. a
+/// [`crate::component::bus::SharedBus`]. the master drives the clock line push-pull,
+/// clock stretching is not supported.
+///
+/// ```ascii
+///              ┌──────────────┐
+/// clock────────┤              ├──────scl
+/// start────────┤              ├──────sda pull low
+/// read─────────┤     I2C      ├──────busy
+/// address──────┤    Master    ├──────nack
+/// register─────┤              ├──────data out
+/// data in──────┤              │
+/// sda──────────┤              │
+///              └──────────────┘
+/// ```
+///
+/// # input
+/// the clock, the start, the read bit, the 7 bits address of the peripheral, the
+/// register, the data to write and the level of the data line. a transaction is
+/// started on the rising edge of the clock while start is high and the master is
+/// not busy.
+///
+/// # output
+/// the clock line, whether the master pulls the data line low, busy, nack, which is
+/// high when the last transaction is not acknowledged, and the byte read.
+#[derive(Debug, Clone)]
+pub struct I2cMaster {
+    layout: Layout,
+    input: Vec<Wire>,
+    scl: Wire,
+    sda: Wire,
+    nack: Wire,
+    data: Vec<Wire>,
+    steps: VecDeque<Step>,
+    clock_edge: EdgeDetector,
+}
+
+impl Default for I2cMaster {
+    fn default() -> Self {
+        let layout = Layout::default()
+            .field("clock", 1)
+            .field("start", 1)
+            .field("read", 1)
+            .field("address", 7)
+            .field("register", 8)
+            .field("data", 8)
+            .field("sda", 1);
+        Self {
+            input: vec![Wire::default(); layout.width()],
+            layout,
+            scl: Wire::new(true),
+            sda: Wire::new(true),
+            nack: Wire::default(),
+            data: vec![Wire::default(); 8],
+            steps: VecDeque::new(),
+            clock_edge: EdgeDetector::default(),
+        }
+    }
+}
+
+impl I2cMaster {
+    fn value(&self, field: &str) -> u8 {
+        let bits: Vec<Potential> = self.input.iter().map(|wire| wire.output()).collect();
+        self.layout.read(&bits, field) as u8
+    }
+
+    fn start(&mut self, repeated: bool) {
+        if repeated {
+            self.steps.extend([step(false, true), step(true, true)]);
+        } else {
+            self.steps.push_back(step(true, true));
+        }
+        self.steps.extend([step(true, false), step(false, false)]);
+    }
+
+    fn write_byte(&mut self, byte: u8) {
+        for i in (0..8).rev() {
+            let bit = (byte >> i) & 1 == 1;
+            self.steps
+                .extend([step(false, bit), step(true, bit), step(false, bit)]);
+        }
+        self.read_bit(Sample::Ack);
+    }
+
+    fn read_bit(&mut self, sample: Sample) {
+        self.steps.extend([
+            step(false, true),
+            step(true, true),
+            Step {
+                scl: false,
+                sda: true,
+                sample,
+            },
+        ]);
+    }
+
+    fn stop(&mut self) {
+        self.steps
+            .extend([step(false, false), step(true, false), step(true, true)]);
+    }
+
+    /// Queue the steps of a transaction.
+    fn transaction(&mut self) {
+        let address = self.value("address") << 1;
+        self.start(false);
+        self.write_byte(address);
+        self.write_byte(self.value("register"));
+        if self.input[self.layout.range("read").start].output() {
+            self.start(true);
+            self.write_byte(address | 1);
+            for _ in 0..8 {
+                self.read_bit(Sample::Data);
+            }
+            // nack the last byte
+            self.steps
+                .extend([step(false, true), step(true, true), step(false, true)]);
+        } else {
+            self.write_byte(self.value("data"));
+        }
+        self.stop();
+    }
+}
+
+impl Component for I2cMaster {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.layout.width(), 12)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        match position {
+            0 => self.scl.output(),
+            1 => !self.sda.output(),
+            2 => !self.steps.is_empty(),
+            3 => self.nack.output(),
+            _ => self.data[position - 4].output(),
+        }
+    }
+    fn update_state(&mut self) {
+        self.clock_edge.input(&[self.input[0].output()]);
+        if !self.clock_edge.is_rising() {
+            return;
+        }
+        if self.steps.is_empty() && self.input[1].output() {
+            self.nack.input(&false);
+            self.transaction();
+        }
+        let Some(step) = self.steps.pop_front() else {
+            return;
+        };
+        let line = self.input[self.layout.range("sda").start].output();
+        match step.sample {
+            Sample::None => {}
+            Sample::Ack => {
+                if line {
+                    // give up the transaction, and release the bus
+                    self.nack.input(&true);
+                    self.steps.clear();
+                    self.stop();
+                }
+            }
+            Sample::Data => {
+                for i in (1..8).rev() {
+                    let lower = self.data[i - 1].output();
+                    self.data[i].input(&lower);
+                }
+                self.data[0].input(&line);
+            }
+        }
+        self.scl.input(&step.scl);
+        self.sda.input(&step.sda);
+    }
+}
+
+/// the states of [`I2cEeprom`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EepromState {
+    /// wait for a start.
+    Idle,
+    /// receive the address and the read bit.
+    Address,
+    /// receive the word address.
+    Word,
+    /// receive the data to write.
+    Write,
+    /// acknowledge a byte, then go to the state.
+    Ack(EepromNext),
+    /// send a byte.
+    Send,
+    /// receive the acknowledge of the byte sent.
+    SendAck,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EepromNext {
+    Word,
+    Write,
+    Send,
+}
+
+/// an i2c eeprom peripheral, of up to 256 bytes.
+/// the input is 2 bits, and the output is 1 bit.
+///
+/// a write sets the word address, then stores the bytes from it. a read sends the
+/// bytes from the word address until the master does not acknowledge. the word
+/// address moves to the next byte after every byte, and wraps at the end.
+///
+/// # input
+/// the levels of the clock line and the data line.
+///
+/// # output
+/// whether the eeprom pulls the data line low, it is open drain.
+#[derive(Debug, Clone)]
+pub struct I2cEeprom {
+    address: u8,
+    memory: Vec<u8>,
+    input: [Wire; 2],
+    output: Wire,
+    scl_edge: EdgeDetector,
+    sda_edge: EdgeDetector,
+    state: EepromState,
+    shift: u8,
+    bits: usize,
+    pointer: usize,
+}
+
+impl I2cEeprom {
+    /// Create an eeprom of `size` bytes at the 7 bits `address`, the bytes are 0xff.
+    pub fn new(address: u8, size: usize) -> Self {
+        assert!(address < 0x80, "address must be 7 bits");
+        assert!(
+            size > 0 && size <= 256,
+            "size must be between 1 and 256 bytes"
+        );
+        Self {
+            address,
+            memory: vec![0xff; size],
+            input: [Wire::new(true); 2],
+            output: Wire::default(),
+            scl_edge: EdgeDetector::default(),
+            sda_edge: EdgeDetector::default(),
+            state: EepromState::Idle,
+            shift: 0,
+            bits: 0,
+            pointer: 0,
+        }
+    }
+
+    /// Get the bytes of the eeprom.
+    pub fn memory(&self) -> &[u8] {
+        &self.memory
+    }
+
+    /// Get the bytes of the eeprom to preload them.
+    pub fn memory_mut(&mut self) -> &mut [u8] {
+        &mut self.memory
+    }
+
+    /// Drive the bit of the byte being sent.
+    fn send_bit(&mut self) {
+        let bit = (self.memory[self.pointer] >> (7 - self.bits)) & 1 == 1;
+        self.output.input(&!bit);
+    }
+
+    fn falling(&mut self) {
+        match self.state {
+            EepromState::Address | EepromState::Word | EepromState::Write if self.bits == 8 => {
+                self.bits = 0;
+                let next = match self.state {
+                    EepromState::Address if self.shift >> 1 != self.address => {
+                        self.state = EepromState::Idle;
+                        return;
+                    }
+                    EepromState::Address if self.shift & 1 == 1 => EepromNext::Send,
+                    EepromState::Address => EepromNext::Word,
+                    EepromState::Word => {
+                        self.pointer = self.shift as usize % self.memory.len();
+                        EepromNext::Write
+                    }
+                    _ => {
+                        self.memory[self.pointer] = self.shift;
+                        self.pointer = (self.pointer + 1) % self.memory.len();
+                        EepromNext::Write
+                    }
+                };
+                self.output.input(&true);
+                self.state = EepromState::Ack(next);
+            }
+            EepromState::Ack(next) => {
+                self.output.input(&false);
+                self.state = match next {
+                    EepromNext::Word => EepromState::Word,
+                    EepromNext::Write => EepromState::Write,
+                    EepromNext::Send => {
+                        self.send_bit();
+                        EepromState::Send
+                    }
+                };
+            }
+            EepromState::Send if self.bits == 8 => {
+                self.output.input(&false);
+                self.state = EepromState::SendAck;
+            }
+            EepromState::Send => self.send_bit(),
+            EepromState::SendAck if self.bits == 0 => {
+                // the master acknowledges, send the next byte
+                self.send_bit();
+                self.state = EepromState::Send;
+            }
+            _ => {}
+        }
+    }
+
+    fn rising(&mut self, sda: Potential) {
+        match self.state {
+            EepromState::Address | EepromState::Word | EepromState::Write if self.bits < 8 => {
+                self.shift = (self.shift << 1) | sda as u8;
+                self.bits += 1;
+            }
+            EepromState::Send => self.bits += 1,
+            EepromState::SendAck => {
+                self.pointer = (self.pointer + 1) % self.memory.len();
+                if sda {
+                    self.state = EepromState::Idle;
+                } else {
+                    self.bits = 0;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Component for I2cEeprom {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (2, 1)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output.output()
+    }
+    fn update_state(&mut self) {
+        let scl = self.input[0].output();
+        let sda = self.input[1].output();
+        self.scl_edge.input(&[scl]);
+        self.sda_edge.input(&[sda]);
+        if scl && !self.scl_edge.is_rising() {
+            // the data line changes while the clock is high: a start or a stop
+            if self.sda_edge.is_falling() {
+                self.state = EepromState::Address;
+                self.bits = 0;
+                self.output.input(&false);
+            } else if self.sda_edge.is_rising() {
+                self.state = EepromState::Idle;
+                self.output.input(&false);
+            }
+        } else if self.scl_edge.is_rising() {
+            self.rising(sda);
+        } else if self.scl_edge.is_falling() {
+            self.falling();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::bus::SharedBus;
+    use rstest::rstest;
+
+    /// a master and an eeprom on a data line with a pull up.
+    struct Board {
+        master: I2cMaster,
+        eeprom: I2cEeprom,
+        sda: SharedBus,
+    }
+
+    impl Board {
+        fn new() -> Self {
+            Self {
+                master: I2cMaster::default(),
+                eeprom: I2cEeprom::new(0x50, 16),
+                sda: SharedBus::wired_and(2),
+            }
+        }
+
+        fn line(&mut self) -> Potential {
+            self.sda.input(&[
+                self.master.get_pin_output(1),
+                false,
+                self.eeprom.get_pin_output(0),
+                false,
+            ]);
+            self.sda.get_pin_output(0)
+        }
+
+        /// run a clock cycle of the master, and let the eeprom follow the lines.
+        fn tick(&mut self, start: bool, read: bool, address: u8, register: u8, data: u8) {
+            let line = self.line();
+            let input = self
+                .master
+                .layout
+                .pack(&[
+                    ("start", start as u64),
+                    ("read", read as u64),
+                    ("address", address as u64),
+                    ("register", register as u64),
+                    ("data", data as u64),
+                    ("sda", line as u64),
+                ])
+                .get_data(true);
+            let mut high = input.clone();
+            high[0] = true;
+            self.master.input(&input);
+            self.master.input(&high);
+            let line = self.line();
+            self.eeprom.input(&[self.master.get_pin_output(0), line]);
+        }
+
+        /// run a transaction to the end, and return the cycles.
+        fn run(&mut self, read: bool, address: u8, register: u8, data: u8) -> usize {
+            self.tick(true, read, address, register, data);
+            let mut cycles = 1;
+            while self.master.get_pin_output(2) {
+                self.tick(false, read, address, register, data);
+                cycles += 1;
+                assert!(cycles < 1000, "transaction does not end");
+            }
+            cycles
+        }
+
+        fn data(&self) -> u8 {
+            (0..8).fold(0, |acc, i| {
+                acc | ((self.master.get_pin_output(4 + i) as u8) << i)
+            })
+        }
+    }
+
+    #[test]
+    fn test_i2c_default() {
+        let master = I2cMaster::default();
+        assert_eq!(master.get_pin_count(), (27, 12));
+        // the lines are released while idle
+        assert_eq!(master.output()[0..3], [true, false, false]);
+        let eeprom = I2cEeprom::new(0x50, 256);
+        assert_eq!(eeprom.output(), vec![false]);
+        assert!(eeprom.memory().iter().all(|byte| *byte == 0xff));
+    }
+
+    #[rstest]
+    #[case(0x00, 0x5a)]
+    #[case(0x07, 0x00)]
+    #[case(0x0f, 0xff)]
+    fn test_i2c_write_then_read(#[case] register: u8, #[case] data: u8) {
+        let mut board = Board::new();
+        // start, 3 bytes of 9 bits, stop
+        assert_eq!(board.run(false, 0x50, register, data), 3 + 27 * 3 + 3);
+        assert!(!board.master.get_pin_output(3));
+        assert_eq!(board.eeprom.memory()[register as usize], data);
+
+        board.run(true, 0x50, register, 0);
+        assert!(!board.master.get_pin_output(3));
+        assert_eq!(board.data(), data);
+    }
+
+    #[test]
+    fn test_i2c_read_preloaded() {
+        let mut board = Board::new();
+        board.eeprom.memory_mut()[3] = 0xa5;
+        board.eeprom.memory_mut()[4] = 0x3c;
+        board.run(true, 0x50, 3, 0);
+        assert_eq!(board.data(), 0xa5);
+        board.run(true, 0x50, 4, 0);
+        assert_eq!(board.data(), 0x3c);
+    }
+
+    #[test]
+    fn test_i2c_wrong_address_nack() {
+        let mut board = Board::new();
+        board.run(false, 0x51, 0, 0x12);
+        assert!(board.master.get_pin_output(3));
+        assert!(board.eeprom.memory().iter().all(|byte| *byte == 0xff));
+        // the bus is released, and the next transaction works
+        board.run(false, 0x50, 1, 0x12);
+        assert!(!board.master.get_pin_output(3));
+        assert_eq!(board.eeprom.memory()[1], 0x12);
+    }
+}
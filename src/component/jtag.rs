@@ -0,0 +1,446 @@
+use crate::{
+    circuit::{Potential, Wire},
+    component::{Component, edge::EdgeDetector, scan::ScanChain},
+};
+
+/// the states of the test access port controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TapState {
+    TestLogicReset,
+    RunTestIdle,
+    SelectDrScan,
+    CaptureDr,
+    ShiftDr,
+    Exit1Dr,
+    PauseDr,
+    Exit2Dr,
+    UpdateDr,
+    SelectIrScan,
+    CaptureIr,
+    ShiftIr,
+    Exit1Ir,
+    PauseIr,
+    Exit2Ir,
+    UpdateIr,
+}
+
+impl TapState {
+    /// Get the state after a rising edge of the clock with the mode select.
+    pub fn next(self, tms: Potential) -> TapState {
+        use TapState::*;
+        match (self, tms) {
+            (TestLogicReset, false) => RunTestIdle,
+            (TestLogicReset, true) => TestLogicReset,
+            (RunTestIdle, false) => RunTestIdle,
+            (RunTestIdle, true) => SelectDrScan,
+            (SelectDrScan, false) => CaptureDr,
+            (SelectDrScan, true) => SelectIrScan,
+            (CaptureDr, false) | (ShiftDr, false) | (Exit2Dr, false) => ShiftDr,
+            (CaptureDr, true) | (ShiftDr, true) => Exit1Dr,
+            (Exit1Dr, false) | (PauseDr, false) => PauseDr,
+            (PauseDr, true) => Exit2Dr,
+            (Exit1Dr, true) | (Exit2Dr, true) => UpdateDr,
+            (UpdateDr, false) | (UpdateIr, false) => RunTestIdle,
+            (UpdateDr, true) | (UpdateIr, true) => SelectDrScan,
+            (SelectIrScan, false) => CaptureIr,
+            (SelectIrScan, true) => TestLogicReset,
+            (CaptureIr, false) | (ShiftIr, false) | (Exit2Ir, false) => ShiftIr,
+            (CaptureIr, true) | (ShiftIr, true) => Exit1Ir,
+            (Exit1Ir, false) | (PauseIr, false) => PauseIr,
+            (PauseIr, true) => Exit2Ir,
+            (Exit1Ir, true) | (Exit2Ir, true) => UpdateIr,
+        }
+    }
+}
+
+/// the bits of the instruction register.
+pub const IR_WIDTH: usize = 4;
+/// the instruction which selects the 32 bits id code.
+pub const IDCODE: u8 = 0b0001;
+/// the instruction which selects the scan chain of the design.
+pub const SCAN: u8 = 0b0010;
+/// the instruction which selects the memory access register.
+pub const MEMORY: u8 = 0b0011;
+/// the instruction which selects the 1 bit bypass register.
+pub const BYPASS: u8 = 0b1111;
+/// the bits of the memory access register, the write bit, the address and the data.
+pub const MEMORY_WIDTH: usize = 17;
+
+/// a test access port in circuit, like jtag.
+/// the input is 3 bits, and the output is 1 bit.
+///
+/// the controller moves through [`TapState`] on the rising edge of the clock, and
+/// shifts the selected register from test data in to test data out, the bit 0 first.
+/// test data out changes on the falling edge of the clock.
+///
+/// the data registers are selected by the instruction:
+/// - [`IDCODE`], the id code of the port, and the instruction after a reset.
+/// - [`SCAN`], the [`ScanChain`] of the design, which is read on capture and written
+///   on update.
+/// - [`MEMORY`], the write bit, the 8 bits address and the 8 bits data. an update
+///   sets the address, and writes the data with the write bit. a capture reads the
+///   byte at the address.
+/// - [`BYPASS`], and any other instruction, a single bit.
+///
+/// ```ascii
+///        ┌──────────┐
+/// tck────┤          │
+/// tms────┤   TAP    ├──────tdo
+/// tdi────┤          │
+///        └──────────┘
+/// ```
+///
+/// # input
+/// the test clock, the test mode select and the test data in.
+///
+/// # output
+/// the test data out.
+#[derive(Debug, Clone)]
+pub struct TapController {
+    idcode: u32,
+    chain: ScanChain,
+    memory: Vec<u8>,
+    input: [Wire; 3],
+    output: Wire,
+    clock_edge: EdgeDetector,
+    state: TapState,
+    instruction: u8,
+    shift: Vec<Potential>,
+    address: u8,
+}
+
+impl TapController {
+    /// Create a port with the id code, the scan chain of the design, and `size` bytes
+    /// of memory, which are 0.
+    pub fn new(idcode: u32, chain: ScanChain, size: usize) -> Self {
+        assert!(
+            size > 0 && size <= 256,
+            "size must be between 1 and 256 bytes"
+        );
+        Self {
+            idcode,
+            chain,
+            memory: vec![0; size],
+            input: [Wire::default(); 3],
+            output: Wire::default(),
+            clock_edge: EdgeDetector::default(),
+            state: TapState::TestLogicReset,
+            instruction: IDCODE,
+            shift: Vec::new(),
+            address: 0,
+        }
+    }
+
+    pub fn state(&self) -> TapState {
+        self.state
+    }
+
+    pub fn instruction(&self) -> u8 {
+        self.instruction
+    }
+
+    pub fn chain(&self) -> &ScanChain {
+        &self.chain
+    }
+
+    pub fn chain_mut(&mut self) -> &mut ScanChain {
+        &mut self.chain
+    }
+
+    pub fn memory(&self) -> &[u8] {
+        &self.memory
+    }
+
+    pub fn memory_mut(&mut self) -> &mut [u8] {
+        &mut self.memory
+    }
+
+    fn capture_dr(&mut self) -> Vec<Potential> {
+        match self.instruction {
+            IDCODE => bits(self.idcode as u64, 32),
+            SCAN => self.chain.dump(),
+            MEMORY => {
+                let byte = self.memory[self.address as usize % self.memory.len()];
+                let mut register = vec![false];
+                register.extend(bits(self.address as u64, 8));
+                register.extend(bits(byte as u64, 8));
+                register
+            }
+            _ => vec![false],
+        }
+    }
+
+    fn update_dr(&mut self) {
+        match self.instruction {
+            SCAN => self.chain.load(&self.shift),
+            MEMORY => {
+                self.address = value(&self.shift[1..9]) as u8;
+                if self.shift[0] {
+                    let index = self.address as usize % self.memory.len();
+                    self.memory[index] = value(&self.shift[9..17]) as u8;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn rising(&mut self) {
+        let tms = self.input[1].output();
+        let tdi = self.input[2].output();
+        match self.state {
+            TapState::TestLogicReset => self.instruction = IDCODE,
+            TapState::CaptureDr => self.shift = self.capture_dr(),
+            // the pattern 01 of the standard, to find the length of the chain
+            TapState::CaptureIr => self.shift = bits(0b0001, IR_WIDTH),
+            TapState::ShiftDr | TapState::ShiftIr => {
+                self.shift.remove(0);
+                self.shift.push(tdi);
+            }
+            TapState::UpdateDr => self.update_dr(),
+            TapState::UpdateIr => self.instruction = value(&self.shift) as u8,
+            _ => {}
+        }
+        self.state = self.state.next(tms);
+    }
+}
+
+fn bits(value: u64, width: usize) -> Vec<Potential> {
+    (0..width).map(|i| (value >> i) & 1 == 1).collect()
+}
+
+fn value(bits: &[Potential]) -> u64 {
+    bits.iter()
+        .enumerate()
+        .fold(0, |value, (i, bit)| value | (*bit as u64) << i)
+}
+
+impl Component for TapController {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (3, 1)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output.output()
+    }
+    fn update_state(&mut self) {
+        self.clock_edge.input(&[self.input[0].output()]);
+        if self.clock_edge.is_rising() {
+            self.rising();
+        } else if self.clock_edge.is_falling() {
+            let tdo = match self.state {
+                TapState::ShiftDr | TapState::ShiftIr => self.shift[0],
+                _ => false,
+            };
+            self.output.input(&tdo);
+        }
+    }
+}
+
+/// a host-side driver of a test access port, which drives the 4 wires of a
+/// [`TapController`], like an external debugger.
+///
+/// # Examples
+///
+/// ```
+/// use simulation_computer_rust::component::jtag::{JtagDriver, TapController};
+/// use simulation_computer_rust::component::scan::{ScanChain, ScanRegisterN};
+///
+/// let chain = ScanChain::new(vec![ScanRegisterN::new(4)]);
+/// let mut tap = TapController::new(0x1234_5678, chain, 16);
+/// let mut driver = JtagDriver::new(&mut tap);
+/// driver.reset();
+/// assert_eq!(driver.idcode(), 0x1234_5678);
+/// driver.write_memory(3, 0xa5);
+/// assert_eq!(driver.read_memory(3), 0xa5);
+/// ```
+pub struct JtagDriver<'a> {
+    tap: &'a mut dyn Component,
+}
+
+impl<'a> JtagDriver<'a> {
+    pub fn new(tap: &'a mut dyn Component) -> Self {
+        assert!(
+            tap.get_pin_count() == (3, 1),
+            "tap must have 3 inputs and 1 output"
+        );
+        Self { tap }
+    }
+
+    /// Run a clock cycle, and get the test data out sampled before the rising edge.
+    pub fn clock(&mut self, tms: Potential, tdi: Potential) -> Potential {
+        self.tap.input(&[false, tms, tdi]);
+        let tdo = self.tap.get_pin_output(0);
+        self.tap.input(&[true, tms, tdi]);
+        tdo
+    }
+
+    /// Reset the port, and go to run test idle.
+    pub fn reset(&mut self) {
+        for _ in 0..5 {
+            self.clock(true, false);
+        }
+        self.clock(false, false);
+    }
+
+    /// Shift the bits through the shift state from run test idle, and go back to it.
+    fn shift(&mut self, bits: &[Potential]) -> Vec<Potential> {
+        self.clock(false, false);
+        self.clock(false, false);
+        let mut out = Vec::with_capacity(bits.len());
+        for (i, bit) in bits.iter().enumerate() {
+            out.push(self.clock(i + 1 == bits.len(), *bit));
+        }
+        self.clock(true, false);
+        self.clock(false, false);
+        out
+    }
+
+    /// Shift the instruction register from run test idle, and get the bits out.
+    pub fn shift_ir(&mut self, bits: &[Potential]) -> Vec<Potential> {
+        self.clock(true, false);
+        self.clock(true, false);
+        self.shift(bits)
+    }
+
+    /// Shift the data register from run test idle, and get the bits out.
+    pub fn shift_dr(&mut self, bits: &[Potential]) -> Vec<Potential> {
+        self.clock(true, false);
+        self.shift(bits)
+    }
+
+    /// Load an instruction.
+    pub fn instruction(&mut self, instruction: u8) {
+        self.shift_ir(&bits(instruction as u64, IR_WIDTH));
+    }
+
+    /// Read the id code.
+    pub fn idcode(&mut self) -> u32 {
+        self.instruction(IDCODE);
+        value(&self.shift_dr(&[false; 32])) as u32
+    }
+
+    /// Read the scan chain of `len` bits, which is written back unchanged.
+    pub fn read_scan(&mut self, len: usize) -> Vec<Potential> {
+        self.instruction(SCAN);
+        let state = self.shift_dr(&vec![false; len]);
+        self.shift_dr(&state);
+        state
+    }
+
+    /// Write the scan chain.
+    pub fn write_scan(&mut self, state: &[Potential]) {
+        self.instruction(SCAN);
+        self.shift_dr(state);
+    }
+
+    fn memory(&mut self, write: bool, address: u8, data: u8) -> u8 {
+        self.instruction(MEMORY);
+        let mut register = vec![write];
+        register.extend(bits(address as u64, 8));
+        register.extend(bits(data as u64, 8));
+        self.shift_dr(&register);
+        // the capture of the next shift reads the byte at the address
+        let out = self.shift_dr(&register.iter().map(|_| false).collect::<Vec<_>>());
+        value(&out[9..MEMORY_WIDTH]) as u8
+    }
+
+    /// Read a byte of the memory.
+    pub fn read_memory(&mut self, address: u8) -> u8 {
+        self.memory(false, address, 0)
+    }
+
+    /// Write a byte of the memory.
+    pub fn write_memory(&mut self, address: u8, data: u8) {
+        self.memory(true, address, data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::scan::ScanRegisterN;
+    use rstest::rstest;
+
+    fn tap() -> TapController {
+        let chain = ScanChain::new(vec![ScanRegisterN::new(3), ScanRegisterN::new(2)]);
+        TapController::new(0x4ba0_0477, chain, 32)
+    }
+
+    #[rstest]
+    #[case(&[true; 5], TapState::TestLogicReset)]
+    #[case(&[false], TapState::RunTestIdle)]
+    #[case(&[false, true, false, false], TapState::ShiftDr)]
+    #[case(&[false, true, true, false, false], TapState::ShiftIr)]
+    #[case(&[false, true, false, true, false, true, true], TapState::UpdateDr)]
+    #[case(&[false, true, true, true], TapState::TestLogicReset)]
+    fn test_tap_state(#[case] tms: &[Potential], #[case] expected: TapState) {
+        let state = tms
+            .iter()
+            .fold(TapState::TestLogicReset, |state, tms| state.next(*tms));
+        assert_eq!(state, expected);
+    }
+
+    #[test]
+    fn test_tap_reset() {
+        let mut tap = tap();
+        let mut driver = JtagDriver::new(&mut tap);
+        driver.reset();
+        driver.instruction(BYPASS);
+        driver.reset();
+        assert_eq!(tap.state(), TapState::RunTestIdle);
+        assert_eq!(tap.instruction(), IDCODE);
+    }
+
+    #[test]
+    fn test_tap_idcode() {
+        let mut tap = tap();
+        let mut driver = JtagDriver::new(&mut tap);
+        driver.reset();
+        assert_eq!(driver.idcode(), 0x4ba0_0477);
+        // the capture of the instruction register shifts out 01
+        assert_eq!(
+            driver.shift_ir(&[true; IR_WIDTH]),
+            vec![true, false, false, false]
+        );
+        // the bypass register delays the data a bit
+        assert_eq!(
+            driver.shift_dr(&[true, false, true]),
+            vec![false, true, false]
+        );
+    }
+
+    #[test]
+    fn test_tap_memory() {
+        let mut tap = tap();
+        tap.memory_mut()[7] = 0x3c;
+        let mut driver = JtagDriver::new(&mut tap);
+        driver.reset();
+        assert_eq!(driver.read_memory(7), 0x3c);
+        driver.write_memory(8, 0x81);
+        assert_eq!(driver.read_memory(8), 0x81);
+        assert_eq!(tap.memory()[8], 0x81);
+    }
+
+    #[test]
+    fn test_tap_scan() {
+        let mut tap = tap();
+        let mut driver = JtagDriver::new(&mut tap);
+        driver.reset();
+        let state = [true, false, true, true, false];
+        driver.write_scan(&state);
+        assert_eq!(driver.read_scan(5), state.to_vec());
+        assert_eq!(tap.chain_mut().dump(), state.to_vec());
+    }
+}
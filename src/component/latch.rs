@@ -0,0 +1,656 @@
+use crate::{
+    circuit::{ANDGate, NANDGate, NOTGate, ORGate, Potential, Wire, XORGate, init},
+    component::{
+        Component,
+        datasheet::{Metadata, Timing},
+    },
+};
+
+/// a sr latch of cross-coupled nand gates in circuit.
+/// the input is 2 bits, and the output is 2 bits.
+///
+/// the inputs are inverted before the nand gates, so set and reset are active high.
+/// `update_state` evaluates the feedback loop of the two gates until it is stable.
+///
+/// ```ascii
+///          ┌─────┐  ┌──────┐
+/// S────────┤ NOT ├──┤      │
+///          └─────┘  │ NAND ├──┬───────Q
+///               ┌───┤      │  │
+///               │   └──────┘  │
+///               └──────────┐┌─┘
+///               ┌──────────┘└─┐
+///               │   ┌──────┐  │
+///          ┌─────┐  │      ├──┘
+/// R────────┤ NOT ├──┤ NAND ├──────────Q̄
+///          └─────┘  └──────┘
+/// ```
+///
+/// # input
+/// the set and the reset.
+///
+/// # output
+/// the q and the q̄. both are high while set and reset are high, which is not a
+/// valid state of the latch.
+///
+/// # truth table
+/// | S | R | Q | Q̄ |
+/// |---|---|---|---|
+/// | 0 | 0 | Q | Q̄ |
+/// | 1 | 0 | 1 | 0 |
+/// | 0 | 1 | 0 | 1 |
+/// | 1 | 1 | 1 | 1 |
+#[derive(Debug, Clone)]
+pub struct SRLatch {
+    input: [Wire; 2],
+    output: [Wire; 2],
+    not_gates: [NOTGate; 2],
+    nand_gates: [NANDGate; 2],
+}
+
+impl SRLatch {
+    /// the most evaluations of the feedback loop, the loop is stable after two.
+    const MAX_ITERATIONS: usize = 4;
+}
+
+impl Default for SRLatch {
+    fn default() -> Self {
+        let q = init::power_up_wire().output();
+        let mut latch = Self {
+            input: [Wire::default(); 2],
+            output: [Wire::new(q), Wire::new(!q)],
+            not_gates: Default::default(),
+            nand_gates: Default::default(),
+        };
+        // the gates power up holding q and q̄
+        latch.nand_gates[0].input(&!q, &true);
+        latch.nand_gates[1].input(&q, &true);
+        latch
+    }
+}
+
+impl Component for SRLatch {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (2, 2)
+    }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "sr latch",
+            description: "holds a bit, which is set high by the set and low by the reset.",
+            timing: Timing::Level,
+        })
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+    fn update_state(&mut self) {
+        self.not_gates[0].input(&self.input[0].output());
+        self.not_gates[1].input(&self.input[1].output());
+        for _ in 0..Self::MAX_ITERATIONS {
+            let q = self.nand_gates[0].output();
+            let q_bar = self.nand_gates[1].output();
+            self.nand_gates[0].input(&self.not_gates[0].output(), &q_bar);
+            self.nand_gates[1].input(&self.not_gates[1].output(), &self.nand_gates[0].output());
+            if self.nand_gates[0].output() == q && self.nand_gates[1].output() == q_bar {
+                break;
+            }
+        }
+        self.output[0].input(&self.nand_gates[0].output());
+        self.output[1].input(&self.nand_gates[1].output());
+    }
+}
+
+/// a gated d latch in circuit, a [`SRLatch`] which is set by the data while enable is
+/// high.
+/// the input is 2 bits, and the output is 2 bits.
+///
+/// ```ascii
+///           ┌─────┐  ┌──────────┐
+/// D─────┬───┤ AND ├──┤S         ├──────Q
+///       │ ┌─┤     │  │ SR Latch │
+/// E─────┼─┤ └─────┘  │          │
+///       │ │ ┌─────┐  │          │
+///      NOT└─┤ AND ├──┤R         ├──────Q̄
+///       └───┤     │  └──────────┘
+///           └─────┘
+/// ```
+///
+/// # input
+/// the enable and the data.
+///
+/// # output
+/// the q and the q̄.
+///
+/// # truth table
+/// | E | D | Q | Q̄ |
+/// |---|---|---|---|
+/// | 0 | 0 | Q | Q̄ |
+/// | 0 | 1 | Q | Q̄ |
+/// | 1 | 0 | 0 | 1 |
+/// | 1 | 1 | 1 | 0 |
+#[derive(Debug, Default, Clone)]
+pub struct DLatch {
+    input: [Wire; 2],
+    not_data: NOTGate,
+    set: ANDGate,
+    reset: ANDGate,
+    latch: SRLatch,
+}
+
+impl Component for DLatch {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (2, 2)
+    }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "d latch",
+            description: "holds a bit, which follows the data while enable is high.",
+            timing: Timing::Level,
+        })
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.latch.get_pin_output(position)
+    }
+    fn update_state(&mut self) {
+        let enable = self.input[0].output();
+        let data = self.input[1].output();
+        self.not_data.input(&data);
+        self.set.input(&enable, &data);
+        self.reset.input(&enable, &self.not_data.output());
+        self.latch.input(&[self.set.output(), self.reset.output()]);
+    }
+}
+
+/// a master-slave d flip-flop in circuit, which captures the data on the rising edge
+/// of the clock.
+/// the input is 2 bits, and the output is 2 bits.
+///
+/// the master [`DLatch`] follows the data while the clock is low, and the slave
+/// [`DLatch`] follows the master while the clock is high. the clock is a level of
+/// its pin, so the edge is seen across the calls of `update_state`.
+///
+/// ```ascii
+///            ┌────────┐     ┌───────┐
+/// D──────────┤D      Q├─────┤D     Q├──────Q
+///            │ master │     │ slave │
+///        ┌───┤E       │  ┌──┤E     Q̄├──────Q̄
+///       NOT  └────────┘  │  └───────┘
+/// clock──┴───────────────┘
+/// ```
+///
+/// # input
+/// the clock and the data.
+///
+/// # output
+/// the q and the q̄.
+#[derive(Debug, Default, Clone)]
+pub struct DFlipFlop {
+    input: [Wire; 2],
+    not_clock: NOTGate,
+    master: DLatch,
+    slave: DLatch,
+}
+
+impl Component for DFlipFlop {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (2, 2)
+    }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "d flip-flop",
+            description: "holds a bit, which captures the data on the rising edge of the clock.",
+            timing: Timing::RisingEdge { clock: 0 },
+        })
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.slave.get_pin_output(position)
+    }
+    fn update_state(&mut self) {
+        let clock = self.input[0].output();
+        // the master closes before the slave opens
+        self.not_clock.input(&clock);
+        self.master
+            .input(&[self.not_clock.output(), self.input[1].output()]);
+        self.slave.input(&[clock, self.master.get_pin_output(0)]);
+    }
+}
+
+/// a jk flip-flop in circuit, a [`DFlipFlop`] whose data is `j & !q | !k & q`.
+/// the input is 3 bits, and the output is 2 bits.
+///
+/// # input
+/// the clock, the j and the k.
+///
+/// # output
+/// the q and the q̄.
+///
+/// # truth table
+/// on the rising edge of the clock.
+///
+/// | J | K | Q |
+/// |---|---|---|
+/// | 0 | 0 | Q |
+/// | 1 | 0 | 1 |
+/// | 0 | 1 | 0 |
+/// | 1 | 1 | Q̄ |
+#[derive(Debug, Default, Clone)]
+pub struct JKFlipFlop {
+    input: [Wire; 3],
+    not_k: NOTGate,
+    set: ANDGate,
+    hold: ANDGate,
+    data: ORGate,
+    flip_flop: DFlipFlop,
+}
+
+impl Component for JKFlipFlop {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (3, 2)
+    }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "jk flip-flop",
+            description: "holds a bit, which is set by j, reset by k and toggled by both.",
+            timing: Timing::RisingEdge { clock: 0 },
+        })
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.flip_flop.get_pin_output(position)
+    }
+    fn update_state(&mut self) {
+        let q = self.flip_flop.get_pin_output(0);
+        let q_bar = self.flip_flop.get_pin_output(1);
+        self.not_k.input(&self.input[2].output());
+        self.set.input(&self.input[1].output(), &q_bar);
+        self.hold.input(&self.not_k.output(), &q);
+        self.data.input(&self.set.output(), &self.hold.output());
+        self.flip_flop
+            .input(&[self.input[0].output(), self.data.output()]);
+    }
+}
+
+/// a t flip-flop in circuit, a [`DFlipFlop`] whose data is `t ^ q`.
+/// the input is 2 bits, and the output is 2 bits.
+///
+/// # input
+/// the clock and the toggle.
+///
+/// # output
+/// the q and the q̄.
+///
+/// # truth table
+/// on the rising edge of the clock.
+///
+/// | T | Q |
+/// |---|---|
+/// | 0 | Q |
+/// | 1 | Q̄ |
+#[derive(Debug, Default, Clone)]
+pub struct TFlipFlop {
+    input: [Wire; 2],
+    data: XORGate,
+    flip_flop: DFlipFlop,
+}
+
+impl Component for TFlipFlop {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (2, 2)
+    }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "t flip-flop",
+            description: "holds a bit, which is toggled while t is high.",
+            timing: Timing::RisingEdge { clock: 0 },
+        })
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.flip_flop.get_pin_output(position)
+    }
+    fn update_state(&mut self) {
+        self.data
+            .input(&self.input[1].output(), &self.flip_flop.get_pin_output(0));
+        self.flip_flop
+            .input(&[self.input[0].output(), self.data.output()]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::truth_table::TruthTable;
+    use rstest::rstest;
+
+    /// the cells of the doc truth table starting at the line `header`, the header
+    /// first.
+    fn doc_table<'a>(source: &'a str, header: &str) -> Vec<Vec<&'a str>> {
+        let mut lines = source.lines().skip_while(|line| *line != header);
+        let header = lines.next().expect("the header must be in the source");
+        std::iter::once(header)
+            .chain(lines.skip(1).take_while(|line| line.starts_with("/// |")))
+            .map(|line| {
+                line.trim_start_matches("/// |")
+                    .trim_end_matches('|')
+                    .split('|')
+                    .map(str::trim)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// the potential of a doc cell, `Q` and `Q̄` are the state before the row.
+    fn doc_cell(cell: &str, q: Potential) -> Potential {
+        match cell {
+            "0" => false,
+            "1" => true,
+            "Q" => q,
+            "Q̄" => !q,
+            _ => panic!("unknown cell {}", cell),
+        }
+    }
+
+    /// check every row of the doc truth table from both states, `state` makes the
+    /// component in a state and `steps` are the inputs of a row, the outputs are the
+    /// columns from `Q`.
+    fn check_docs<C: Component>(
+        header: &str,
+        state: impl Fn(Potential) -> C,
+        steps: impl Fn(&[Potential]) -> Vec<Vec<Potential>>,
+    ) {
+        let table = doc_table(include_str!("latch.rs"), header);
+        let inputs = table[0].iter().position(|name| *name == "Q").unwrap();
+        for row in &table[1..] {
+            for q in [false, true] {
+                let input: Vec<Potential> =
+                    row[..inputs].iter().map(|cell| doc_cell(cell, q)).collect();
+                let expected: Vec<Potential> =
+                    row[inputs..].iter().map(|cell| doc_cell(cell, q)).collect();
+                let run = TruthTable::of_rows(&mut state(q), &steps(&input));
+                let output = &run.rows().last().unwrap().1;
+                assert_eq!(
+                    output[..expected.len()],
+                    expected,
+                    "{:?} from q = {}",
+                    row,
+                    q
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_latch_docs() {
+        // the truth tables in the doc comments are the behavior of the latches
+        check_docs(
+            "/// | S | R | Q | Q̄ |",
+            |q| {
+                let mut latch = SRLatch::default();
+                latch.input(&[q, !q]);
+                latch.input(&[false, false]);
+                latch
+            },
+            |input| vec![input.to_vec()],
+        );
+        check_docs(
+            "/// | E | D | Q | Q̄ |",
+            |q| {
+                let mut latch = DLatch::default();
+                latch.input(&[true, q]);
+                latch.input(&[false, q]);
+                latch
+            },
+            |input| vec![input.to_vec()],
+        );
+        check_docs(
+            "/// | J | K | Q |",
+            |q| {
+                let mut flip_flop = JKFlipFlop::default();
+                tick_jk(&mut flip_flop, q, !q);
+                flip_flop
+            },
+            |input| vec![[&[false], input].concat(), [&[true], input].concat()],
+        );
+        check_docs(
+            "/// | T | Q |",
+            |q| {
+                let mut flip_flop = TFlipFlop::default();
+                if tick_t(&mut flip_flop, false) != q {
+                    tick_t(&mut flip_flop, true);
+                }
+                flip_flop
+            },
+            |input| vec![[&[false], input].concat(), [&[true], input].concat()],
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict-init"))]
+    fn test_sr_latch_default() {
+        let latch = SRLatch::default();
+        assert_eq!(latch.output(), vec![false, true]);
+    }
+
+    #[rstest]
+    // [s, r] one after another => [q, q̄]
+    #[case(&[[true, false]], [true, false])]
+    #[case(&[[false, true]], [false, true])]
+    #[case(&[[true, false], [false, false]], [true, false])]
+    #[case(&[[false, true], [false, false]], [false, true])]
+    #[case(&[[true, false], [false, true]], [false, true])]
+    #[case(&[[true, true]], [true, true])]
+    // the reset is released last
+    #[case(&[[true, true], [true, false], [false, false]], [true, false])]
+    fn test_sr_latch_with_truth_table(
+        #[case] inputs: &[[Potential; 2]],
+        #[case] expected: [Potential; 2],
+    ) {
+        let mut latch = SRLatch::default();
+        for input in inputs {
+            latch.input(input);
+        }
+        assert_eq!(latch.output(), expected.to_vec());
+    }
+
+    #[test]
+    fn test_sr_latch_holds() {
+        let mut latch = SRLatch::default();
+        latch.input(&[true, false]);
+        for _ in 0..3 {
+            latch.input(&[false, false]);
+            assert_eq!(latch.output(), vec![true, false]);
+        }
+        latch.input(&[false, true]);
+        latch.input(&[false, false]);
+        assert_eq!(latch.output(), vec![false, true]);
+    }
+
+    #[rstest]
+    // [e, d] one after another => [q, q̄]
+    #[case(&[[true, false]], [false, true])]
+    #[case(&[[true, true]], [true, false])]
+    #[case(&[[true, true], [false, false]], [true, false])]
+    #[case(&[[true, false], [false, true]], [false, true])]
+    #[case(&[[true, true], [true, false]], [false, true])]
+    fn test_d_latch_with_truth_table(
+        #[case] inputs: &[[Potential; 2]],
+        #[case] expected: [Potential; 2],
+    ) {
+        let mut latch = DLatch::default();
+        for input in inputs {
+            latch.input(input);
+        }
+        assert_eq!(latch.output(), expected.to_vec());
+    }
+
+    #[rstest]
+    // [clock, d] one after another => q
+    #[case(&[[false, true], [true, true]], true)]
+    #[case(&[[false, false], [true, false]], false)]
+    // the data is ignored while the clock is high or low
+    #[case(&[[false, true], [true, true], [true, false]], true)]
+    #[case(&[[false, true], [true, true], [false, false]], true)]
+    // the data of the rising edge, not the data before it
+    #[case(&[[false, true], [false, false], [true, false]], false)]
+    fn test_d_flip_flop(#[case] inputs: &[[Potential; 2]], #[case] expected: Potential) {
+        let mut flip_flop = DFlipFlop::default();
+        for input in inputs {
+            flip_flop.input(input);
+        }
+        assert_eq!(flip_flop.output(), vec![expected, !expected]);
+    }
+
+    #[test]
+    fn test_d_flip_flop_shift() {
+        // two flip-flops on the same clock make a 2 bits shift register
+        let mut first = DFlipFlop::default();
+        let mut second = DFlipFlop::default();
+        let mut seen = Vec::new();
+        for data in [true, false, true, true, false] {
+            for clock in [false, true] {
+                let carried = first.get_pin_output(0);
+                first.input(&[clock, data]);
+                second.input(&[clock, carried]);
+            }
+            seen.push(second.get_pin_output(0));
+        }
+        assert_eq!(seen[1..], [true, false, true, true]);
+    }
+
+    fn tick_jk(flip_flop: &mut JKFlipFlop, j: bool, k: bool) -> Potential {
+        flip_flop.input(&[false, j, k]);
+        flip_flop.input(&[true, j, k]);
+        flip_flop.get_pin_output(0)
+    }
+
+    #[rstest]
+    // q before, j, k => q after
+    #[case(false, false, false, false)]
+    #[case(true, false, false, true)]
+    #[case(false, true, false, true)]
+    #[case(true, true, false, true)]
+    #[case(false, false, true, false)]
+    #[case(true, false, true, false)]
+    #[case(false, true, true, true)]
+    #[case(true, true, true, false)]
+    fn test_jk_flip_flop_with_truth_table(
+        #[case] q: Potential,
+        #[case] j: Potential,
+        #[case] k: Potential,
+        #[case] expected: Potential,
+    ) {
+        let mut flip_flop = JKFlipFlop::default();
+        tick_jk(&mut flip_flop, q, !q);
+        assert_eq!(tick_jk(&mut flip_flop, j, k), expected);
+        assert_eq!(flip_flop.get_pin_output(1), !expected);
+    }
+
+    fn tick_t(flip_flop: &mut TFlipFlop, t: bool) -> Potential {
+        flip_flop.input(&[false, t]);
+        flip_flop.input(&[true, t]);
+        flip_flop.get_pin_output(0)
+    }
+
+    #[rstest]
+    // q before, t => q after
+    #[case(false, false, false)]
+    #[case(true, false, true)]
+    #[case(false, true, true)]
+    #[case(true, true, false)]
+    fn test_t_flip_flop_with_truth_table(
+        #[case] q: Potential,
+        #[case] t: Potential,
+        #[case] expected: Potential,
+    ) {
+        let mut flip_flop = TFlipFlop::default();
+        if tick_t(&mut flip_flop, false) != q {
+            tick_t(&mut flip_flop, true);
+        }
+        assert_eq!(tick_t(&mut flip_flop, t), expected);
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict-init"))]
+    fn test_t_flip_flop_divider() {
+        // a chain of t flip-flops, each clocked by the q̄ of the one before, counts up
+        let mut stages: [TFlipFlop; 3] = Default::default();
+        let mut counts = Vec::new();
+        for _ in 0..8 {
+            for clock in [false, true] {
+                let mut clock = clock;
+                for stage in stages.iter_mut() {
+                    stage.input(&[clock, true]);
+                    clock = stage.get_pin_output(1);
+                }
+            }
+            let count: usize = (0..3)
+                .map(|i| (stages[i].get_pin_output(0) as usize) << i)
+                .sum();
+            counts.push(count);
+        }
+        assert_eq!(counts, vec![1, 2, 3, 4, 5, 6, 7, 0]);
+    }
+}
@@ -0,0 +1,287 @@
+use crate::{
+    circuit::{ANDGate, NOTGate, ORGate, Potential, Wire, init},
+    component::{
+        Component,
+        big_gates::{ANDGate3, ORGateN},
+        decoder::DecoderN,
+        edge::EdgeDetector,
+    },
+};
+
+/// the default width of [`LedMatrix`].
+pub const LED_MATRIX_WIDTH: usize = 64;
+/// the default height of [`LedMatrix`].
+pub const LED_MATRIX_HEIGHT: usize = 32;
+
+/// a monochrome led matrix display with a pixel addressed frame buffer in circuit.
+/// the input is 4+log2(width)+log2(height) bits, and the output is 1 bit.
+///
+/// the row and the column of the address are decoded separately, and a pixel is
+/// written on the rising edge of the clock when both of its lines and write are high.
+/// while clear is high the rising edge of the clock darkens every pixel instead.
+///
+/// ```ascii
+///              x    y
+///              │    │
+///          ┌───┴────┴───┐
+/// clock────┤            │
+/// write────┤            │
+///          │ LED Matrix ├────pixel
+/// data─────┤            │
+/// clear────┤            │
+///          └────────────┘
+/// ```
+///
+/// # input
+/// the first bit is the clock, the second bit is write, the third bit is the data,
+/// the fourth bit is clear, then the column x and the row y of the pixel (the first
+/// bit is the lowest bit).
+///
+/// # output
+/// the pixel at the address.
+#[derive(Debug, Clone)]
+pub struct LedMatrix {
+    width: usize,
+    height: usize,
+    input: Vec<Wire>,
+    output: Wire,
+    pixels: Vec<Wire>,
+    clock_edge: EdgeDetector,
+    column: DecoderN,
+    row: DecoderN,
+    write_gates: Vec<ANDGate3>,
+    // a pixel loads on write or clear, and the data is 0 on clear
+    not_clear: NOTGate,
+    data_gate: ANDGate,
+    load_gates: Vec<ORGate>,
+    read_gates: Vec<ANDGate3>,
+    read: ORGateN,
+}
+
+impl Default for LedMatrix {
+    fn default() -> Self {
+        Self::new(LED_MATRIX_WIDTH, LED_MATRIX_HEIGHT)
+    }
+}
+
+impl LedMatrix {
+    pub fn new(width: usize, height: usize) -> Self {
+        assert!(
+            width > 1 && width.is_power_of_two(),
+            "width must be a power of 2 greater than 1"
+        );
+        assert!(
+            height > 1 && height.is_power_of_two(),
+            "height must be a power of 2 greater than 1"
+        );
+        let x_bits = width.trailing_zeros() as usize;
+        let y_bits = height.trailing_zeros() as usize;
+        Self {
+            width,
+            height,
+            input: vec![Wire::default(); 4 + x_bits + y_bits],
+            output: Wire::default(),
+            pixels: init::power_up(width * height),
+            clock_edge: EdgeDetector::default(),
+            column: DecoderN::new(x_bits),
+            row: DecoderN::new(y_bits),
+            write_gates: vec![ANDGate3::default(); width * height],
+            not_clear: NOTGate::default(),
+            data_gate: ANDGate::default(),
+            load_gates: vec![ORGate::default(); width * height],
+            read_gates: vec![ANDGate3::default(); width * height],
+            read: ORGateN::new(width * height),
+        }
+    }
+
+    /// Get the width of the display.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get the height of the display.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the frame, indexed by row and then column.
+    pub fn frame(&self) -> Vec<Vec<bool>> {
+        self.pixels
+            .chunks(self.width)
+            .map(|row| row.iter().map(|pixel| pixel.output()).collect())
+            .collect()
+    }
+
+    /// Render the frame as text, a lit pixel is `#` and a dark pixel is `.`.
+    pub fn render_text(&self) -> String {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        for row in self.frame() {
+            text.extend(row.iter().map(|lit| if *lit { '#' } else { '.' }));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl Component for LedMatrix {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.input.len(), 1)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output.output()
+    }
+    fn update_state(&mut self) {
+        let x_bits = self.column.get_pin_count().0;
+        self.clock_edge.input(&[self.input[0].output()]);
+        let x: Vec<Potential> = self.input[4..4 + x_bits]
+            .iter()
+            .map(|wire| wire.output())
+            .collect();
+        let y: Vec<Potential> = self.input[4 + x_bits..]
+            .iter()
+            .map(|wire| wire.output())
+            .collect();
+        self.column.input(&x);
+        self.row.input(&y);
+
+        let write = self.input[1].output();
+        let clear = self.input[3].output();
+        self.not_clear.input(&clear);
+        self.data_gate
+            .input(&self.input[2].output(), &self.not_clear.output());
+        let data = self.data_gate.output();
+        for (i, gate) in self.write_gates.iter_mut().enumerate() {
+            gate.input(&[
+                self.row.get_pin_output(i / self.width),
+                self.column.get_pin_output(i % self.width),
+                write,
+            ]);
+            self.load_gates[i].input(&gate.get_pin_output(0), &clear);
+            if self.clock_edge.is_rising() && self.load_gates[i].output() {
+                self.pixels[i].input(&data);
+            }
+        }
+
+        for (i, gate) in self.read_gates.iter_mut().enumerate() {
+            gate.input(&[
+                self.pixels[i].output(),
+                self.row.get_pin_output(i / self.width),
+                self.column.get_pin_output(i % self.width),
+            ]);
+            self.read.set_pin_input(i, &gate.get_pin_output(0));
+        }
+        self.read.update_state();
+        self.output.input(&self.read.get_pin_output(0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits(value: usize, width: usize) -> Vec<Potential> {
+        (0..width).map(|i| (value >> i) & 1 == 1).collect()
+    }
+
+    /// run a full clock cycle with the given write, data and address.
+    fn tick(matrix: &mut LedMatrix, write: bool, data: bool, x: usize, y: usize) -> Potential {
+        tick_clear(matrix, write, data, false, x, y)
+    }
+
+    /// run a full clock cycle with the given write, data, clear and address.
+    fn tick_clear(
+        matrix: &mut LedMatrix,
+        write: bool,
+        data: bool,
+        clear: bool,
+        x: usize,
+        y: usize,
+    ) -> Potential {
+        let x_bits = matrix.width().trailing_zeros() as usize;
+        let y_bits = matrix.height().trailing_zeros() as usize;
+        let mut input = vec![false, write, data, clear];
+        input.extend(bits(x, x_bits));
+        input.extend(bits(y, y_bits));
+        matrix.input(&input);
+        input[0] = true;
+        matrix.input(&input);
+        matrix.output()[0]
+    }
+
+    /// create a led matrix, and darken whatever it powers up with.
+    fn cleared(width: usize, height: usize) -> LedMatrix {
+        let mut matrix = LedMatrix::new(width, height);
+        tick_clear(&mut matrix, false, false, true, 0, 0);
+        matrix
+    }
+
+    #[test]
+    fn test_led_matrix_default() {
+        let mut matrix = LedMatrix::default();
+        assert_eq!(matrix.get_pin_count(), (4 + 6 + 5, 1));
+        tick_clear(&mut matrix, false, false, true, 0, 0);
+        let frame = matrix.frame();
+        assert_eq!(frame.len(), 32);
+        assert!(
+            frame
+                .iter()
+                .all(|row| row.len() == 64 && row.iter().all(|p| !p))
+        );
+    }
+
+    #[test]
+    fn test_led_matrix_write_and_read() {
+        let mut matrix = cleared(8, 4);
+        assert!(tick(&mut matrix, true, true, 5, 2));
+        assert!(tick(&mut matrix, true, true, 0, 3));
+        assert!(!tick(&mut matrix, false, false, 2, 1));
+        assert!(tick(&mut matrix, false, false, 5, 2));
+        let frame = matrix.frame();
+        assert!(frame[2][5]);
+        assert!(frame[3][0]);
+        assert!(!frame[1][2]);
+        // clear a pixel
+        assert!(!tick(&mut matrix, true, false, 5, 2));
+        assert!(!matrix.frame()[2][5]);
+    }
+
+    #[test]
+    fn test_led_matrix_write_without_clock() {
+        let mut matrix = cleared(2, 2);
+        matrix.input(&[false, true, true, false, true, true]);
+        assert!(!matrix.frame()[1][1]);
+        assert_eq!(matrix.output(), vec![false]);
+    }
+
+    #[test]
+    fn test_led_matrix_render_text() {
+        let mut matrix = cleared(4, 2);
+        tick(&mut matrix, true, true, 0, 0);
+        tick(&mut matrix, true, true, 3, 1);
+        tick(&mut matrix, true, true, 2, 1);
+        assert_eq!(matrix.render_text(), "#...\n..##\n");
+    }
+
+    #[test]
+    fn test_led_matrix_clear() {
+        let mut matrix = cleared(4, 2);
+        tick(&mut matrix, true, true, 1, 0);
+        tick(&mut matrix, true, true, 2, 1);
+        // clear wins over a write in the same cycle
+        assert!(!tick_clear(&mut matrix, true, true, true, 2, 1));
+        assert_eq!(matrix.render_text(), "....\n....\n");
+    }
+}
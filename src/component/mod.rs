@@ -1,13 +1,50 @@
-use std::vec;
-
-use crate::circuit::Potential;
+use crate::circuit::{Endianness, Potential, Potentials};
+use datasheet::Metadata;
+use fidelity::Fidelity;
+use port::{Direction, Port};
 
 pub mod adder;
+pub mod alu;
+pub mod beeper;
 pub mod big_gates;
+pub mod bus;
+pub mod clock_gate;
+pub mod comparator;
+pub mod cosim;
+pub mod counter;
+pub mod datasheet;
 pub mod decoder;
+pub mod digital_clock;
+pub mod edge;
 pub mod enabler;
 pub mod encoder;
+pub mod fanout;
+pub mod fidelity;
+pub mod i2c;
+pub mod jtag;
+pub mod latch;
+pub mod led_matrix;
+pub mod mpu;
+pub mod multiplier;
 pub mod mux;
+pub mod peripheral;
+pub mod port;
+pub mod pwm;
+pub mod quadrature;
+pub mod ram;
+pub mod register;
+pub mod registry;
+pub mod rom;
+pub mod rtc;
+pub mod scan;
+pub mod stepper;
+pub mod storage;
+pub mod subtractor;
+pub mod timer;
+pub mod tmr;
+pub mod truth_table;
+pub mod vga;
+pub mod wishbone;
 
 /// A trait representing a component with input and output pins.
 pub trait Component {
@@ -40,17 +77,17 @@ pub trait Component {
     ///
     /// # Arguments
     /// * `vec` - A reference to a vector of potential values.
-    fn prepare_input(&mut self, vec: &Vec<Potential>) {
+    fn prepare_input(&mut self, vec: &[Potential]) {
         assert!(vec.len() <= self.get_pin_count().0);
-        for i in 0..vec.len() {
-            self.set_pin_input(i, &vec[i]);
+        for (i, value) in vec.iter().enumerate() {
+            self.set_pin_input(i, value);
         }
     }
     /// Perform batch input for the component, and update the state of the component.
     ///
     /// # Arguments
     /// * `vec` - A reference to a vector of potential values.
-    fn input(&mut self, vec: &Vec<Potential>) {
+    fn input(&mut self, vec: &[Potential]) {
         self.prepare_input(vec);
         self.update_state();
     }
@@ -62,9 +99,84 @@ pub trait Component {
     fn output(&self) -> Vec<Potential> {
         let len: usize = self.get_pin_count().1;
         let mut vec: Vec<Potential> = vec![false; len];
-        for i in 0..vec.len() {
-            vec[i] = self.get_pin_output(i);
+        for (i, value) in vec.iter_mut().enumerate() {
+            *value = self.get_pin_output(i);
         }
         vec
     }
+
+    /// Get the bit order of the multi-bit buses of the component.
+    ///
+    /// the components of this crate use [`Endianness::BUS`], the first pin of a bus
+    /// is the lowest bit.
+    fn endianness(&self) -> Endianness {
+        Endianness::BUS
+    }
+
+    /// Set the input pins of a bus from `start`, in the bit order of the component.
+    ///
+    /// # Arguments
+    /// * `start` - The position of the first pin of the bus.
+    /// * `value` - The value of the bus, in any endianness.
+    fn set_bus_input(&mut self, start: usize, value: &Potentials) {
+        assert!(
+            start + value.len() <= self.get_pin_count().0,
+            "bus must end before {}",
+            self.get_pin_count().0
+        );
+        for (i, bit) in value.bits(self.endianness().is_little()).enumerate() {
+            self.set_pin_input(start + i, &bit);
+        }
+    }
+
+    /// Get the output pins of a bus from `start`, in the bit order of the component.
+    ///
+    /// # Arguments
+    /// * `start` - The position of the first pin of the bus.
+    /// * `width` - The number of pins of the bus.
+    fn get_bus_output(&self, start: usize, width: usize) -> Potentials {
+        assert!(
+            start + width <= self.get_pin_count().1,
+            "bus must end before {}",
+            self.get_pin_count().1
+        );
+        let bits = (start..start + width)
+            .map(|i| self.get_pin_output(i))
+            .collect();
+        Potentials::new(bits, self.endianness())
+    }
+
+    /// Get the level of detail the component is simulated at, gate level by default.
+    fn fidelity(&self) -> Fidelity {
+        Fidelity::Gate
+    }
+
+    /// Get the metadata of the component for its datasheet, none by default.
+    fn metadata(&self) -> Option<Metadata> {
+        None
+    }
+
+    /// Get the named ports of the component, no port by default.
+    fn ports(&self) -> Vec<Port> {
+        Vec::new()
+    }
+
+    /// Set an input port by its name, the width of the value must be the width of
+    /// the port.
+    fn set_port(&mut self, name: &str, value: &Potentials) {
+        let port = port::find(self, name, Direction::Input);
+        assert!(
+            value.len() == port.width(),
+            "port `{}` is {} bits",
+            name,
+            port.width()
+        );
+        self.set_bus_input(port.pins.start, value);
+    }
+
+    /// Get an output port by its name.
+    fn get_port(&self, name: &str) -> Potentials {
+        let port = port::find(self, name, Direction::Output);
+        self.get_bus_output(port.pins.start, port.width())
+    }
 }
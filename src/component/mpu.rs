@@ -0,0 +1,401 @@
+use crate::{
+    circuit::{ANDGate, NOTGate, ORGate, Potential, Wire, XORGate, init, layout::Layout},
+    component::{
+        Component,
+        big_gates::{ANDGate3, ORGateN},
+        decoder::DecoderN,
+        edge::EdgeDetector,
+    },
+};
+
+/// a comparator of two n-bit numbers, `a < b`, rippled from the lowest bit.
+#[derive(Debug, Clone)]
+struct LessThan {
+    not_gates: Vec<NOTGate>,
+    less_gates: Vec<ANDGate>,
+    xor_gates: Vec<XORGate>,
+    equal_gates: Vec<NOTGate>,
+    carry_gates: Vec<ANDGate>,
+    or_gates: Vec<ORGate>,
+}
+
+impl LessThan {
+    fn new(n_way: usize) -> Self {
+        Self {
+            not_gates: vec![NOTGate::default(); n_way],
+            less_gates: vec![ANDGate::default(); n_way],
+            xor_gates: vec![XORGate::default(); n_way],
+            equal_gates: vec![NOTGate::default(); n_way],
+            carry_gates: vec![ANDGate::default(); n_way],
+            or_gates: vec![ORGate::default(); n_way],
+        }
+    }
+
+    // less(i) = (not ai and bi) or (ai xnor bi and less(i-1))
+    fn compare(&mut self, a: &[Potential], b: &[Potential]) -> Potential {
+        let mut less = false;
+        for i in 0..a.len() {
+            self.not_gates[i].input(&a[i]);
+            self.less_gates[i].input(&self.not_gates[i].output(), &b[i]);
+            self.xor_gates[i].input(&a[i], &b[i]);
+            self.equal_gates[i].input(&self.xor_gates[i].output());
+            self.carry_gates[i].input(&self.equal_gates[i].output(), &less);
+            self.or_gates[i].input(&self.less_gates[i].output(), &self.carry_gates[i].output());
+            less = self.or_gates[i].output();
+        }
+        less
+    }
+}
+
+/// a region of [`MPU`], with its registers and its checker.
+#[derive(Debug, Clone)]
+struct Region {
+    base: Vec<Wire>,
+    limit: Vec<Wire>,
+    read: Wire,
+    write: Wire,
+    enable: Wire,
+    below_base: LessThan,
+    below_limit: LessThan,
+    not_below_base: NOTGate,
+    // the permission of the access, write ? w : r
+    not_write: NOTGate,
+    read_gate: ANDGate,
+    write_gate: ANDGate,
+    permission: ORGate,
+    in_range: ANDGate,
+    allowed: ANDGate3,
+}
+
+impl Region {
+    fn new(n_way: usize) -> Self {
+        Self {
+            base: init::power_up(n_way),
+            limit: init::power_up(n_way),
+            read: init::power_up_wire(),
+            write: init::power_up_wire(),
+            enable: init::power_up_wire(),
+            below_base: LessThan::new(n_way),
+            below_limit: LessThan::new(n_way),
+            not_below_base: NOTGate::default(),
+            not_write: NOTGate::default(),
+            read_gate: ANDGate::default(),
+            write_gate: ANDGate::default(),
+            permission: ORGate::default(),
+            in_range: ANDGate::default(),
+            allowed: ANDGate3::default(),
+        }
+    }
+
+    /// Whether the region allows the access, base <= address < limit.
+    fn allows(&mut self, address: &[Potential], write: &Potential) -> Potential {
+        let base: Vec<Potential> = self.base.iter().map(|wire| wire.output()).collect();
+        let limit: Vec<Potential> = self.limit.iter().map(|wire| wire.output()).collect();
+        self.not_below_base
+            .input(&self.below_base.compare(address, &base));
+        let below_limit = self.below_limit.compare(address, &limit);
+        self.in_range
+            .input(&self.not_below_base.output(), &below_limit);
+
+        self.not_write.input(write);
+        self.read_gate
+            .input(&self.not_write.output(), &self.read.output());
+        self.write_gate.input(write, &self.write.output());
+        self.permission
+            .input(&self.read_gate.output(), &self.write_gate.output());
+
+        self.allowed.input(&[
+            self.enable.output(),
+            self.in_range.output(),
+            self.permission.output(),
+        ]);
+        self.allowed.get_pin_output(0)
+    }
+}
+
+/// a memory protection unit in circuit.
+/// the input is 3+r+2n+3+n+2 bits, and the output is 1 bit, where n is the width of
+/// the address and 2^r regions can be selected.
+///
+/// every region has a base register, a limit register, a read permission, a write
+/// permission and an enable bit. an access is allowed when an enabled region covers
+/// the address (base <= address < limit) and permits the kind of the access,
+/// otherwise the fault is raised. while reset is high the rising edge of the clock
+/// disables every region, so every access faults until a region is configured.
+///
+/// ```ascii
+///                  region registers
+///                         │
+///                 ┌───────┴───────┐
+/// clock───────────┤               │
+/// reset───────────┤               │
+/// address─────────┤      MPU      ├─────fault
+/// access──────────┤               │
+/// write───────────┤               │
+///                 └───────────────┘
+/// ```
+///
+/// # input
+/// the clock and the reset.
+///
+/// then the region registers: the config write, the region index (r bits),
+/// the base (n bits), the limit (n bits), the read permission, the write permission
+/// and the enable bit. the selected region loads them on the rising edge of the
+/// clock while config write is high.
+///
+/// then the access: the address (n bits), the access strobe and the write bit,
+/// which is low for a read. all the numbers are little endian.
+///
+/// # output
+/// the fault, which is high while a disallowed access is on the inputs.
+#[derive(Debug, Clone)]
+pub struct MPU {
+    n_way: usize,
+    layout: Layout,
+    input: Vec<Wire>,
+    output: Wire,
+    regions: Vec<Region>,
+    clock_edge: EdgeDetector,
+    select: DecoderN,
+    load_gates: Vec<ANDGate>,
+    // the enable loads on config write or reset, and is 0 on reset
+    not_reset: NOTGate,
+    enable_data: ANDGate,
+    enable_gates: Vec<ORGate>,
+    allowed: ORGateN,
+    not_allowed: NOTGate,
+    fault: ANDGate,
+}
+
+impl MPU {
+    pub fn new(n_way: usize, regions: usize) -> Self {
+        assert!(n_way > 0, "n_way must be greater than 0");
+        assert!(regions > 0, "regions must be greater than 0");
+        let index_width = ((usize::BITS - (regions - 1).leading_zeros()) as usize).max(1);
+        let layout = Layout::default()
+            .field("clock", 1)
+            .field("reset", 1)
+            .field("config_write", 1)
+            .field("index", index_width)
+            .field("base", n_way)
+            .field("limit", n_way)
+            .field("read", 1)
+            .field("write", 1)
+            .field("enable", 1)
+            .field("address", n_way)
+            .field("access", 1)
+            .field("access_write", 1);
+        Self {
+            n_way,
+            input: vec![Wire::default(); layout.width()],
+            layout,
+            output: Wire::default(),
+            regions: (0..regions).map(|_| Region::new(n_way)).collect(),
+            clock_edge: EdgeDetector::default(),
+            select: DecoderN::new(index_width),
+            load_gates: vec![ANDGate::default(); regions],
+            not_reset: NOTGate::default(),
+            enable_data: ANDGate::default(),
+            enable_gates: vec![ORGate::default(); regions],
+            allowed: ORGateN::new(regions),
+            not_allowed: NOTGate::default(),
+            fault: ANDGate::default(),
+        }
+    }
+
+    fn values(&self, field: &str) -> Vec<Potential> {
+        self.input[self.layout.range(field)]
+            .iter()
+            .map(|wire| wire.output())
+            .collect()
+    }
+}
+
+impl Component for MPU {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.input.len(), 1)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output.output()
+    }
+    fn update_state(&mut self) {
+        let n = self.n_way;
+        self.clock_edge.input(&self.values("clock"));
+
+        // load the selected region registers
+        self.select.input(&self.values("index"));
+        let config_write = self.values("config_write")[0];
+        let base = self.values("base");
+        let limit = self.values("limit");
+        let flags = [self.values("read")[0], self.values("write")[0]];
+        let reset = self.values("reset")[0];
+        self.not_reset.input(&reset);
+        self.enable_data
+            .input(&self.values("enable")[0], &self.not_reset.output());
+        for (i, region) in self.regions.iter_mut().enumerate() {
+            self.load_gates[i].input(&config_write, &self.select.get_pin_output(i));
+            self.enable_gates[i].input(&self.load_gates[i].output(), &reset);
+            if !self.clock_edge.is_rising() {
+                continue;
+            }
+            if self.load_gates[i].output() {
+                for j in 0..n {
+                    region.base[j].input(&base[j]);
+                    region.limit[j].input(&limit[j]);
+                }
+                region.read.input(&flags[0]);
+                region.write.input(&flags[1]);
+            }
+            if self.enable_gates[i].output() {
+                region.enable.input(&self.enable_data.output());
+            }
+        }
+
+        // check the access against every region
+        let address = self.values("address");
+        let write = self.values("access_write")[0];
+        for (i, region) in self.regions.iter_mut().enumerate() {
+            self.allowed
+                .set_pin_input(i, &region.allows(&address, &write));
+        }
+        self.allowed.update_state();
+        self.not_allowed.input(&self.allowed.get_pin_output(0));
+        self.fault
+            .input(&self.values("access")[0], &self.not_allowed.output());
+        self.output.input(&self.fault.output());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn input(
+        mpu: &MPU,
+        clock: bool,
+        config: Option<(usize, usize, usize, [bool; 3])>,
+        access: Option<(usize, bool)>,
+    ) -> Vec<Potential> {
+        let (index, base, limit, flags) = config.unwrap_or((0, 0, 0, [false; 3]));
+        let (address, write) = access.unwrap_or((0, false));
+        mpu.layout
+            .pack(&[
+                ("clock", clock as u64),
+                ("config_write", config.is_some() as u64),
+                ("index", index as u64),
+                ("base", base as u64),
+                ("limit", limit as u64),
+                ("read", flags[0] as u64),
+                ("write", flags[1] as u64),
+                ("enable", flags[2] as u64),
+                ("address", address as u64),
+                ("access", access.is_some() as u64),
+                ("access_write", write as u64),
+            ])
+            .get_data(true)
+    }
+
+    /// create a mpu, and disable every region by a clock cycle with reset.
+    fn reset(n_way: usize, regions: usize) -> MPU {
+        let mut mpu = MPU::new(n_way, regions);
+        for clock in [false, true] {
+            let input = mpu.layout.pack(&[("clock", clock as u64), ("reset", 1)]);
+            mpu.input(&input.get_data(true));
+        }
+        mpu
+    }
+
+    /// write the registers of a region with a full clock cycle.
+    fn configure(mpu: &mut MPU, index: usize, base: usize, limit: usize, flags: [bool; 3]) {
+        let config = Some((index, base, limit, flags));
+        mpu.input(&input(mpu, false, config, None));
+        mpu.input(&input(mpu, true, config, None));
+    }
+
+    fn fault(mpu: &mut MPU, address: usize, write: bool) -> Potential {
+        mpu.input(&input(mpu, false, None, Some((address, write))));
+        mpu.output()[0]
+    }
+
+    #[test]
+    fn test_mpu_default() {
+        let mpu = MPU::new(8, 4);
+        assert_eq!(mpu.get_pin_count(), (3 + 2 + 24 + 5, 1));
+        assert_eq!(mpu.output(), vec![false]);
+    }
+
+    #[test]
+    fn test_mpu_no_access_no_fault() {
+        let mut mpu = reset(8, 2);
+        mpu.input(&input(&mpu, false, None, None));
+        assert_eq!(mpu.output(), vec![false]);
+        // every access faults before a region is enabled
+        assert!(fault(&mut mpu, 0, false));
+    }
+
+    #[rstest]
+    /// a read-only code region [0x00, 0x40) and a read-write data region [0x80, 0xc0)
+    #[case(0x00, false, false)]
+    #[case(0x3f, false, false)]
+    #[case(0x3f, true, true)]
+    #[case(0x40, false, true)]
+    #[case(0x7f, false, true)]
+    #[case(0x80, false, false)]
+    #[case(0x80, true, false)]
+    #[case(0xbf, true, false)]
+    #[case(0xc0, true, true)]
+    #[case(0xff, false, true)]
+    fn test_mpu_regions(#[case] address: usize, #[case] write: bool, #[case] expected: bool) {
+        let mut mpu = reset(8, 4);
+        configure(&mut mpu, 0, 0x00, 0x40, [true, false, true]);
+        configure(&mut mpu, 3, 0x80, 0xc0, [true, true, true]);
+        assert_eq!(fault(&mut mpu, address, write), expected);
+    }
+
+    #[test]
+    fn test_mpu_disable_region() {
+        let mut mpu = reset(4, 1);
+        configure(&mut mpu, 0, 2, 6, [true, true, true]);
+        assert!(!fault(&mut mpu, 4, true));
+        configure(&mut mpu, 0, 2, 6, [true, true, false]);
+        assert!(fault(&mut mpu, 4, true));
+    }
+
+    #[test]
+    fn test_mpu_config_without_clock() {
+        let mut mpu = reset(4, 1);
+        mpu.input(&input(&mpu, false, Some((0, 0, 15, [true; 3])), None));
+        assert!(fault(&mut mpu, 1, false));
+    }
+
+    #[test]
+    fn test_mpu_reset() {
+        let mut mpu = reset(4, 2);
+        configure(&mut mpu, 0, 0, 8, [true, true, true]);
+        configure(&mut mpu, 1, 8, 15, [true, true, true]);
+        assert!(!fault(&mut mpu, 3, true));
+        assert!(!fault(&mut mpu, 12, false));
+        // reset disables both regions, and wins over a config write in the same cycle
+        for clock in [false, true] {
+            let mut bits = input(&mpu, clock, Some((0, 0, 8, [true; 3])), None);
+            bits[mpu.layout.range("reset").start] = true;
+            mpu.input(&bits);
+        }
+        assert!(fault(&mut mpu, 3, true));
+        assert!(fault(&mut mpu, 12, false));
+    }
+}
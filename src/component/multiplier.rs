@@ -0,0 +1,163 @@
+use crate::{
+    circuit::{ANDGate, Potential, Wire},
+    component::{
+        Component,
+        adder::RippleCarryAdderN,
+        datasheet::{Metadata, Timing},
+        port::Port,
+    },
+};
+
+/// a n×n array multiplier in circuit.
+/// the input is 2*n bits, and the output is 2*n bits.
+///
+/// every partial product `a_j & b_i` is an and gate, and the rows of the partial
+/// products are summed by a [`RippleCarryAdderN`] per row: the lowest bit of every
+/// sum is a bit of the product, and the higher bits and the carry are added to the
+/// next row, like a multiplication by hand.
+///
+/// ```ascii
+///              ┌──────────────┐
+/// a────────────┤              │
+///              │  Multiplier  ├──────product
+/// b────────────┤              │
+///              └──────────────┘
+/// ```
+///
+/// # input
+/// the n bits of a and the n bits of b (the first bit is the lowest bit).
+///
+/// # output
+/// the 2*n bits of the product (the first bit is the lowest bit).
+#[derive(Debug, Clone)]
+pub struct ArrayMultiplier {
+    width: usize,
+    input: Vec<Wire>,
+    output: Vec<Wire>,
+    // the partial products of every bit of b
+    and_gates: Vec<Vec<ANDGate>>,
+    // the adders of the rows after the first one
+    adders: Vec<RippleCarryAdderN>,
+}
+
+impl ArrayMultiplier {
+    pub fn new(width: usize) -> Self {
+        assert!(width > 0, "width must be greater than 0");
+        Self {
+            width,
+            input: vec![Wire::default(); 2 * width],
+            output: vec![Wire::default(); 2 * width],
+            and_gates: vec![vec![ANDGate::default(); width]; width],
+            adders: vec![RippleCarryAdderN::new(width); width - 1],
+        }
+    }
+}
+
+impl Component for ArrayMultiplier {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (2 * self.width, 2 * self.width)
+    }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "array multiplier",
+            description: "multiplies two n-bit numbers to a 2n-bit product, with and gates and rows of adders.",
+            timing: Timing::Combinational,
+        })
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+    fn ports(&self) -> Vec<Port> {
+        let n = self.width;
+        vec![
+            Port::input("a", 0..n),
+            Port::input("b", n..2 * n),
+            Port::output("product", 0..2 * n),
+        ]
+    }
+    fn update_state(&mut self) {
+        let n = self.width;
+        for i in 0..n {
+            let b = self.input[n + i].output();
+            for j in 0..n {
+                self.and_gates[i][j].input(&self.input[j].output(), &b);
+            }
+        }
+        self.output[0].input(&self.and_gates[0][0].output());
+        // the higher bits of the row, which are added to the next row
+        let mut running: Vec<Potential> = self.and_gates[0][1..]
+            .iter()
+            .map(|gate| gate.output())
+            .collect();
+        running.push(false);
+        for i in 1..n {
+            let mut adder_input = vec![false];
+            adder_input.extend(&running);
+            adder_input.extend(self.and_gates[i].iter().map(|gate| gate.output()));
+            let adder = &mut self.adders[i - 1];
+            adder.input(&adder_input);
+            self.output[i].input(&adder.get_pin_output(0));
+            running = (1..=n).map(|k| adder.get_pin_output(k)).collect();
+        }
+        for (k, value) in running.iter().enumerate() {
+            self.output[n + k].input(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::{Pad, Potentials};
+    use rstest::rstest;
+
+    #[test]
+    fn test_array_multiplier_default() {
+        let multiplier = ArrayMultiplier::new(4);
+        assert_eq!(multiplier.output(), vec![false; 8]);
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    #[case(4)]
+    fn test_array_multiplier_exhaustive(#[case] width: usize) {
+        let mut multiplier = ArrayMultiplier::new(width);
+        for a in 0..1i64 << width {
+            for b in 0..1i64 << width {
+                multiplier.set_port("a", &Potentials::with_width(a, width, Pad::Zero));
+                multiplier.set_port("b", &Potentials::with_width(b, width, Pad::Zero));
+                multiplier.update_state();
+                assert_eq!(
+                    multiplier.get_port("product").get_data(true),
+                    Potentials::with_width(a * b, 2 * width, Pad::Zero).get_data(true)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_array_multiplier_8() {
+        let mut multiplier = ArrayMultiplier::new(8);
+        multiplier.set_port("a", &Potentials::with_width(0xff, 8, Pad::Zero));
+        multiplier.set_port("b", &Potentials::with_width(0xc3, 8, Pad::Zero));
+        multiplier.update_state();
+        assert_eq!(
+            multiplier.get_port("product").get_data(true),
+            Potentials::with_width(0xff * 0xc3, 16, Pad::Zero).get_data(true)
+        );
+    }
+}
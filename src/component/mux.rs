@@ -1 +1,144 @@
+use crate::{
+    circuit::{ANDGate, Potential, Wire},
+    component::{
+        Component,
+        datasheet::{Metadata, Timing},
+        decoder::DecoderN,
+    },
+};
 
+/// a 1-2^n demultiplexer in circuit.
+/// the input is n+1 bits, and the output is 2^n bits.
+///
+/// the select lines are decoded by a [`DecoderN`], and every output is the and of
+/// its decoded line and the data.
+///
+/// ```ascii
+///             ┌─────────┐
+/// data────────┤         ├──────O0
+///             │  Demux  │  ...
+/// select──────┤         ├──────O(2^n-1)
+///             └─────────┘
+/// ```
+///
+/// # input
+/// the data, then the n bits of the select, the first bit is the lowest bit.
+///
+/// # output
+/// the output at the position of the select is the data, the others are low.
+///
+/// # truth table (n = 1)
+/// | D | S0 | O0 | O1 |
+/// |---|---|---|---|
+/// | 0 | 0 | 0 | 0 |
+/// | 1 | 0 | 1 | 0 |
+/// | 0 | 1 | 0 | 0 |
+/// | 1 | 1 | 0 | 1 |
+#[derive(Debug, Clone)]
+pub struct DemuxN {
+    width: usize,
+    data: Wire,
+    output: Vec<Wire>,
+    decoder: DecoderN,
+    and_gates: Vec<ANDGate>,
+}
+
+impl DemuxN {
+    pub fn new(width: usize) -> Self {
+        assert!(width > 0, "width must be greater than 0");
+        Self {
+            width,
+            data: Wire::default(),
+            output: vec![Wire::default(); 1 << width],
+            decoder: DecoderN::new(width),
+            and_gates: vec![ANDGate::default(); 1 << width],
+        }
+    }
+}
+
+impl Component for DemuxN {
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        if position == 0 {
+            self.data.input(value);
+        } else {
+            self.decoder.set_pin_input(position - 1, value);
+        }
+    }
+
+    fn update_state(&mut self) {
+        self.decoder.update_state();
+        for (j, and_gate) in self.and_gates.iter_mut().enumerate() {
+            and_gate.input(&self.decoder.get_pin_output(j), &self.data.output());
+            self.output[j].input(&and_gate.output());
+        }
+    }
+
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.width + 1, 1 << self.width)
+    }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "demultiplexer",
+            description: "routes the data to the output at the position of the select.",
+            timing: Timing::Combinational,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::{Pad, Padding, Potentials};
+    use rstest::rstest;
+
+    #[test]
+    fn test_demux_n_default() {
+        let demux = DemuxN::new(2);
+        assert_eq!(demux.output(), vec![false; 4]);
+    }
+
+    #[rstest]
+    #[case("00", "00")]
+    #[case("10", "10")]
+    #[case("01", "00")]
+    #[case("11", "01")]
+    fn test_demux_n_1_with_truth_table(#[case] input: &str, #[case] output: &str) {
+        let mut demux = DemuxN::new(1);
+        demux.input(&Potentials::from_little_endian(input, Padding::Keep).get_data(true));
+        assert_eq!(
+            demux.output(),
+            Potentials::from_little_endian(output, Padding::Keep).get_data(true)
+        );
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(3)]
+    fn test_demux_n_routes_data(#[case] width: usize) {
+        let mut demux = DemuxN::new(width);
+        for select in 0..1 << width {
+            for data in [false, true] {
+                let mut input = vec![data];
+                input.extend(Potentials::with_width(select, width, Pad::Zero).get_data(true));
+                demux.input(&input);
+                let expected: Vec<Potential> =
+                    (0..1 << width).map(|j| data && j == select).collect();
+                assert_eq!(demux.output(), expected);
+            }
+        }
+    }
+}
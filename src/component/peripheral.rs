@@ -0,0 +1,383 @@
+use crate::{
+    circuit::{Potential, Wire, layout::Layout},
+    component::{
+        Component,
+        edge::EdgeDetector,
+        wishbone::{slave_inputs, slave_outputs},
+    },
+};
+
+/// the value driven on a read, from the stored value.
+type ReadHook = Box<dyn FnMut(u64) -> u64>;
+/// the value stored on a write, from the stored value and the written value.
+type WriteHook = Box<dyn FnMut(u64, u64) -> u64>;
+
+/// a register of a [`Peripheral`].
+struct Register {
+    name: String,
+    offset: u64,
+    fields: Layout,
+    value: u64,
+    read: ReadHook,
+    write: WriteHook,
+}
+
+/// a builder of the register map of a [`Peripheral`].
+///
+/// a register is defined by [`register`](Self::register), and the methods after it
+/// change the last register defined, so the map reads like a datasheet.
+pub struct PeripheralBuilder {
+    address_width: usize,
+    data_width: usize,
+    registers: Vec<Register>,
+}
+
+impl PeripheralBuilder {
+    /// Define a register at the offset, which stores the written value and reads
+    /// it back.
+    pub fn register(mut self, name: &str, offset: u64) -> Self {
+        assert!(
+            offset < 1 << self.address_width,
+            "offset must be less than {}",
+            1u64 << self.address_width
+        );
+        assert!(
+            self.registers.iter().all(|register| register.name != name),
+            "register `{}` is already defined",
+            name
+        );
+        assert!(
+            self.registers
+                .iter()
+                .all(|register| register.offset != offset),
+            "offset {:#x} is already mapped",
+            offset
+        );
+        self.registers.push(Register {
+            name: name.to_string(),
+            offset,
+            fields: Layout::default(),
+            value: 0,
+            read: Box::new(|value| value),
+            write: Box::new(|_, value| value),
+        });
+        self
+    }
+
+    fn last(&mut self) -> &mut Register {
+        self.registers
+            .last_mut()
+            .expect("a register must be defined first")
+    }
+
+    /// Set the bit fields of the last register.
+    pub fn fields(mut self, fields: Layout) -> Self {
+        assert!(
+            fields.width() <= self.data_width,
+            "fields must not be wider than {}",
+            self.data_width
+        );
+        self.last().fields = fields;
+        self
+    }
+
+    /// Set the reset value of the last register, the bits above the data width are dropped.
+    pub fn reset(mut self, value: u64) -> Self {
+        let mask = u64::MAX >> (64 - self.data_width);
+        self.last().value = value & mask;
+        self
+    }
+
+    /// Make the last register ignore the writes.
+    pub fn read_only(self) -> Self {
+        self.on_write(|value, _| value)
+    }
+
+    /// Compute the value driven on a read of the last register from its stored
+    /// value, e.g. a status of the host side.
+    pub fn on_read(mut self, read: impl FnMut(u64) -> u64 + 'static) -> Self {
+        self.last().read = Box::new(read);
+        self
+    }
+
+    /// Compute the value stored on a write of the last register from its stored
+    /// value and the written value, e.g. `|value, data| value & !data` for a write 1
+    /// to clear register, or send the data to the host side.
+    pub fn on_write(mut self, write: impl FnMut(u64, u64) -> u64 + 'static) -> Self {
+        self.last().write = Box::new(write);
+        self
+    }
+
+    pub fn build(self) -> Peripheral {
+        let inputs = slave_inputs(self.address_width, self.data_width);
+        let outputs = slave_outputs(self.data_width);
+        Peripheral {
+            input: vec![Wire::default(); inputs.width()],
+            output: vec![Wire::default(); outputs.width()],
+            inputs,
+            outputs,
+            data_width: self.data_width,
+            registers: self.registers,
+            clock_edge: EdgeDetector::default(),
+        }
+    }
+}
+
+/// a register-mapped device on a wishbone style bus, defined by a
+/// [`PeripheralBuilder`] instead of by hand.
+/// the pins follow [`slave_inputs`] and [`slave_outputs`].
+///
+/// a transfer is acked like [`WishboneRegisters`](crate::component::wishbone::WishboneRegisters).
+/// a read of an address which is not mapped is 0, and a write of it is ignored.
+///
+/// # Examples
+///
+/// ```
+/// use std::{cell::RefCell, rc::Rc};
+///
+/// use simulation_computer_rust::{
+///     circuit::layout::Layout,
+///     component::{peripheral::Peripheral, wishbone::WishboneMaster},
+/// };
+///
+/// let sent = Rc::new(RefCell::new(Vec::new()));
+/// let log = sent.clone();
+/// let mut uart = Peripheral::builder(2, 8)
+///     .register("data", 0)
+///     .on_write(move |_, data| {
+///         log.borrow_mut().push(data as u8);
+///         data
+///     })
+///     .register("status", 1)
+///     .fields(Layout::default().field("ready", 1).field("error", 1))
+///     .reset(0b01)
+///     .read_only()
+///     .build();
+/// let mut master = WishboneMaster::new(&mut uart, 2, 8);
+/// master.write(0, b'h' as u64).unwrap();
+/// master.write(1, 0xff).unwrap();
+/// assert_eq!(master.read(1), Ok(0b01));
+/// assert_eq!(*sent.borrow(), b"h");
+/// assert_eq!(uart.field("status", "ready"), 1);
+/// ```
+pub struct Peripheral {
+    inputs: Layout,
+    outputs: Layout,
+    input: Vec<Wire>,
+    output: Vec<Wire>,
+    data_width: usize,
+    registers: Vec<Register>,
+    clock_edge: EdgeDetector,
+}
+
+impl Peripheral {
+    /// Start the register map of a peripheral with `2^address_width` addresses of
+    /// `data_width` bits.
+    pub fn builder(address_width: usize, data_width: usize) -> PeripheralBuilder {
+        assert!(
+            address_width > 0 && address_width <= 16,
+            "address_width must be between 1 and 16"
+        );
+        assert!(
+            data_width > 0 && data_width <= 64,
+            "data_width must be between 1 and 64"
+        );
+        PeripheralBuilder {
+            address_width,
+            data_width,
+            registers: Vec::new(),
+        }
+    }
+
+    fn find(&self, name: &str) -> &Register {
+        self.registers
+            .iter()
+            .find(|register| register.name == name)
+            .unwrap_or_else(|| panic!("register `{}` is not defined", name))
+    }
+
+    fn find_mut(&mut self, name: &str) -> &mut Register {
+        self.registers
+            .iter_mut()
+            .find(|register| register.name == name)
+            .unwrap_or_else(|| panic!("register `{}` is not defined", name))
+    }
+
+    fn mask(&self) -> u64 {
+        u64::MAX >> (64 - self.data_width)
+    }
+
+    /// Get the stored value of a register, without the read hook.
+    pub fn get(&self, name: &str) -> u64 {
+        self.find(name).value
+    }
+
+    /// Set the stored value of a register from the host side, without the write
+    /// hook.
+    pub fn set(&mut self, name: &str, value: u64) {
+        let mask = self.mask();
+        self.find_mut(name).value = value & mask;
+    }
+
+    /// Get the value of a field of a register.
+    pub fn field(&self, name: &str, field: &str) -> u64 {
+        let register = self.find(name);
+        register
+            .fields
+            .read(&bits(register.value, self.data_width), field)
+    }
+
+    /// Set the value of a field of a register from the host side.
+    pub fn set_field(&mut self, name: &str, field: &str, value: u64) {
+        let data_width = self.data_width;
+        let register = self.find_mut(name);
+        let mut bits = bits(register.value, data_width);
+        register.fields.set(&mut bits, field, value);
+        register.value = bits
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (i, bit)| acc | (*bit as u64) << i);
+    }
+
+    fn value(&self, field: &str) -> u64 {
+        let bits: Vec<Potential> = self.input.iter().map(|wire| wire.output()).collect();
+        self.inputs.read(&bits, field)
+    }
+}
+
+/// Get the little endian bits of a value.
+fn bits(value: u64, width: usize) -> Vec<Potential> {
+    (0..width).map(|i| (value >> i) & 1 == 1).collect()
+}
+
+impl Component for Peripheral {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.input.len(), self.output.len())
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+    fn update_state(&mut self) {
+        self.clock_edge.input(&[self.value("clock") == 1]);
+        if !self.clock_edge.is_rising() {
+            return;
+        }
+        let acked = self.output[0].output();
+        let mut bits = vec![false; self.outputs.width()];
+        // a transfer is acked once, the master drops the strobe after the ack
+        if self.value("cyc") == 1 && self.value("stb") == 1 && !acked {
+            let address = self.value("adr");
+            let write = self.value("we") == 1;
+            let data = self.value("dat_w");
+            let mask = self.mask();
+            let mut read = 0;
+            if let Some(register) = self
+                .registers
+                .iter_mut()
+                .find(|register| register.offset == address)
+            {
+                if write {
+                    register.value = (register.write)(register.value, data) & mask;
+                } else {
+                    read = (register.read)(register.value) & mask;
+                }
+            }
+            self.outputs.set(&mut bits, "ack", 1);
+            self.outputs.set(&mut bits, "dat_r", read);
+        }
+        for (wire, bit) in self.output.iter_mut().zip(bits) {
+            wire.input(&bit);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::*;
+    use crate::component::wishbone::WishboneMaster;
+
+    #[test]
+    fn test_peripheral_registers() {
+        let mut timer = Peripheral::builder(3, 16)
+            .register("control", 0)
+            .fields(Layout::default().field("enable", 1).field("prescale", 4))
+            .register("compare", 2)
+            .reset(0x1000)
+            .register("flags", 4)
+            .reset(0b111)
+            .on_write(|value, data| value & !data)
+            .build();
+        let mut master = WishboneMaster::new(&mut timer, 3, 16);
+        assert_eq!(master.read(2), Ok(0x1000));
+        master.write(0, 0b10111).unwrap();
+        master.write(2, 0xffff).unwrap();
+        // write 1 to clear
+        master.write(4, 0b010).unwrap();
+        assert_eq!(master.read(4), Ok(0b101));
+        // not mapped
+        master.write(7, 0x1234).unwrap();
+        assert_eq!(master.read(7), Ok(0));
+        master.checker().assert_clean();
+        assert_eq!(timer.field("control", "enable"), 1);
+        assert_eq!(timer.field("control", "prescale"), 0b1011);
+        assert_eq!(timer.get("compare"), 0xffff);
+    }
+
+    #[test]
+    fn test_peripheral_host_side() {
+        let status = Rc::new(Cell::new(0));
+        let host = status.clone();
+        let mut device = Peripheral::builder(1, 8)
+            .register("status", 0)
+            .read_only()
+            .on_read(move |_| host.get())
+            .register("config", 1)
+            .fields(Layout::default().field("mode", 2).field("level", 3))
+            .build();
+        device.set_field("config", "level", 5);
+        status.set(0x42);
+        let mut master = WishboneMaster::new(&mut device, 1, 8);
+        assert_eq!(master.read(0), Ok(0x42));
+        assert_eq!(master.read(1), Ok(0b10100));
+        master.write(1, 0xff).unwrap();
+        assert_eq!(device.field("config", "mode"), 0b11);
+        device.set("status", 0x3);
+        assert_eq!(device.get("status"), 0x3);
+    }
+
+    #[test]
+    fn test_peripheral_reset_masked() {
+        let device = Peripheral::builder(1, 8)
+            .register("data", 0)
+            .reset(0x1ff)
+            .build();
+        assert_eq!(device.get("data"), 0xff);
+    }
+
+    #[test]
+    #[should_panic(expected = "offset 0x1 is already mapped")]
+    fn test_peripheral_overlapping_offset() {
+        let _ = Peripheral::builder(2, 8).register("a", 1).register("b", 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "a register must be defined first")]
+    fn test_peripheral_without_register() {
+        let _ = Peripheral::builder(2, 8).read_only();
+    }
+}
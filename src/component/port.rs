@@ -0,0 +1,141 @@
+use std::ops::Range;
+
+use crate::component::Component;
+
+/// the direction of a [`Port`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Input,
+    Output,
+}
+
+/// a named group of the pins of a component, e.g. the `a` bus of an adder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Port {
+    pub name: &'static str,
+    pub direction: Direction,
+    /// the positions of the pins, the first pin is the lowest bit.
+    pub pins: Range<usize>,
+}
+
+impl Port {
+    /// Create an input port of the pins.
+    pub fn input(name: &'static str, pins: Range<usize>) -> Self {
+        Self {
+            name,
+            direction: Direction::Input,
+            pins,
+        }
+    }
+
+    /// Create an output port of the pins.
+    pub fn output(name: &'static str, pins: Range<usize>) -> Self {
+        Self {
+            name,
+            direction: Direction::Output,
+            pins,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.pins.len()
+    }
+}
+
+/// Find a port of the component by its name and direction.
+pub(crate) fn find<C: Component + ?Sized>(component: &C, name: &str, direction: Direction) -> Port {
+    component
+        .ports()
+        .into_iter()
+        .find(|port| port.name == name && port.direction == direction)
+        .unwrap_or_else(|| panic!("component has no {:?} port `{}`", direction, name))
+}
+
+/// Render the ports of a component as a markdown table, for the doc comments.
+///
+/// # Examples
+///
+/// ```
+/// use simulation_computer_rust::component::{adder::RippleCarryAdderN, port::markdown};
+///
+/// let table = markdown(&RippleCarryAdderN::new(4));
+/// assert!(table.contains("| a | input | 1..5 | 4 |"));
+/// ```
+pub fn markdown<C: Component + ?Sized>(component: &C) -> String {
+    let mut table = String::from("| port | direction | pins | width |\n|---|---|---|---|\n");
+    for port in component.ports() {
+        let direction = match port.direction {
+            Direction::Input => "input",
+            Direction::Output => "output",
+        };
+        table.push_str(&format!(
+            "| {} | {} | {}..{} | {} |\n",
+            port.name,
+            direction,
+            port.pins.start,
+            port.pins.end,
+            port.width()
+        ));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        circuit::{Pad, Potentials},
+        component::adder::{FullAdder, RippleCarryAdderN},
+    };
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(3, 4, false, 7)]
+    #[case(9, 9, true, 19)]
+    #[case(15, 15, true, 31)]
+    fn test_ports_adder(#[case] a: i64, #[case] b: i64, #[case] carry: bool, #[case] sum: i64) {
+        let mut adder = RippleCarryAdderN::new(4);
+        adder.set_port("a", &Potentials::with_width(a, 4, Pad::Zero));
+        adder.set_port("b", &Potentials::with_width(b, 4, Pad::Zero));
+        adder.set_port(
+            "carry_in",
+            &Potentials::with_width(carry as i64, 1, Pad::Zero),
+        );
+        adder.update_state();
+        let mut bits = adder.get_port("sum").get_data(true);
+        bits.extend(adder.get_port("carry_out").get_data(true));
+        assert_eq!(
+            Potentials::with_width(sum, 5, Pad::Zero).get_data(true),
+            bits
+        );
+    }
+
+    #[test]
+    fn test_ports_markdown() {
+        assert_eq!(
+            markdown(&FullAdder::default()),
+            concat!(
+                "| port | direction | pins | width |\n",
+                "|---|---|---|---|\n",
+                "| a | input | 0..1 | 1 |\n",
+                "| b | input | 1..2 | 1 |\n",
+                "| carry_in | input | 2..3 | 1 |\n",
+                "| sum | output | 0..1 | 1 |\n",
+                "| carry_out | output | 1..2 | 1 |\n",
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "port `a` is 4 bits")]
+    fn test_ports_width() {
+        let mut adder = RippleCarryAdderN::new(4);
+        adder.set_port("a", &Potentials::with_width(1, 5, Pad::Zero));
+    }
+
+    #[test]
+    #[should_panic(expected = "component has no Output port `a`")]
+    fn test_ports_unknown() {
+        RippleCarryAdderN::new(4).get_port("a");
+    }
+}
@@ -0,0 +1,243 @@
+use std::collections::VecDeque;
+
+use crate::{
+    circuit::{ANDGate, NOTGate, ORGate, Potential, Wire, XORGate, init},
+    component::{Component, counter::Incrementer, edge::EdgeDetector},
+};
+
+/// a n-bit pwm generator in circuit.
+/// the input is n+2 bits, and the output is 1 bit.
+///
+/// a free running n-bit counter increases on every rising edge of the clock,
+/// and the output is high while the counter is less than the duty register.
+/// so the period is 2^n clock cycles and the output is high for `duty` of them.
+/// while reset is high the rising edge clears the counter instead.
+///
+/// ```ascii
+///                d0  d1  d2  d3
+///                │   │   │   │
+///            ┌───┴───┴───┴───┴───┐
+///            │                   │
+/// clock──────┤        PWM        ├──────out
+///            │                   │
+/// reset──────┤                   │
+///            └───────────────────┘
+/// ```
+///
+/// # input
+/// the first n bit is the duty register (the first bit is the lowest bit),
+/// the next bit is the clock, and the last bit is reset.
+///
+/// # output
+/// the pwm output.
+#[derive(Debug, Clone)]
+pub struct PwmN {
+    n_way: usize,
+    input: Vec<Wire>,
+    output: Wire,
+    counter: Vec<Wire>,
+    clock_edge: EdgeDetector,
+    incrementer: Incrementer,
+    not_reset: NOTGate,
+    // counter < duty, rippled from the lowest bit
+    not_gates: Vec<NOTGate>,
+    less_gates: Vec<ANDGate>,
+    xor_gates: Vec<XORGate>,
+    equal_gates: Vec<NOTGate>,
+    carry_gates: Vec<ANDGate>,
+    or_gates: Vec<ORGate>,
+}
+
+impl PwmN {
+    pub fn new(n_way: usize) -> Self {
+        assert!(n_way > 0, "n_way must be greater than 0");
+        Self {
+            n_way,
+            input: vec![Wire::default(); n_way + 2],
+            output: Wire::default(),
+            counter: init::power_up(n_way),
+            clock_edge: EdgeDetector::default(),
+            incrementer: Incrementer::new(n_way),
+            not_reset: NOTGate::default(),
+            not_gates: vec![NOTGate::default(); n_way],
+            less_gates: vec![ANDGate::default(); n_way],
+            xor_gates: vec![XORGate::default(); n_way],
+            equal_gates: vec![NOTGate::default(); n_way],
+            carry_gates: vec![ANDGate::default(); n_way],
+            or_gates: vec![ORGate::default(); n_way],
+        }
+    }
+}
+
+impl Component for PwmN {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.n_way + 2, 1)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output.output()
+    }
+    fn update_state(&mut self) {
+        self.clock_edge.input(&[self.input[self.n_way].output()]);
+        self.not_reset.input(&self.input[self.n_way + 1].output());
+        if self.clock_edge.is_rising() {
+            // counter + 1, the carry out is dropped so the counter wraps around
+            self.incrementer.increment(&self.counter, &true);
+            let next = self.incrementer.next(&self.not_reset.output());
+            for (wire, bit) in self.counter.iter_mut().zip(next) {
+                wire.input(&bit);
+            }
+        }
+
+        // less(i) = (not ci and di) or (ci xnor di and less(i-1))
+        let mut less = false;
+        for i in 0..self.n_way {
+            let c = self.counter[i].output();
+            let d = self.input[i].output();
+            self.not_gates[i].input(&c);
+            self.less_gates[i].input(&self.not_gates[i].output(), &d);
+            self.xor_gates[i].input(&c, &d);
+            self.equal_gates[i].input(&self.xor_gates[i].output());
+            self.carry_gates[i].input(&self.equal_gates[i].output(), &less);
+            self.or_gates[i].input(&self.less_gates[i].output(), &self.carry_gates[i].output());
+            less = self.or_gates[i].output();
+        }
+        self.output.input(&less);
+    }
+}
+
+/// a simple dac model which averages a digital signal.
+///
+/// the observer keeps the last `window` samples and reports the fraction of
+/// high samples, which is the effective "analog" level of a pwm output.
+#[derive(Debug, Clone)]
+pub struct AveragingObserver {
+    window: usize,
+    samples: VecDeque<Potential>,
+    high: usize,
+}
+
+impl AveragingObserver {
+    pub fn new(window: usize) -> Self {
+        assert!(window > 0, "window must be greater than 0");
+        Self {
+            window,
+            samples: VecDeque::with_capacity(window),
+            high: 0,
+        }
+    }
+
+    /// Record a sample of the observed signal.
+    pub fn sample(&mut self, value: &Potential) {
+        if self.samples.len() == self.window && self.samples.pop_front() == Some(true) {
+            self.high -= 1;
+        }
+        self.samples.push_back(*value);
+        if *value {
+            self.high += 1;
+        }
+    }
+
+    /// Get the average level over the recorded samples, between `0.0` and `1.0`.
+    pub fn level(&self) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        self.high as f64 / self.samples.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    /// run a full clock cycle and return the output after the rising edge.
+    fn tick(pwm: &mut PwmN, duty: &[Potential]) -> Potential {
+        tick_reset(pwm, duty, false)
+    }
+
+    /// run a full clock cycle with reset and return the output after the rising edge.
+    fn tick_reset(pwm: &mut PwmN, duty: &[Potential], reset: bool) -> Potential {
+        let mut input = duty.to_vec();
+        input.extend([false, reset]);
+        pwm.input(&input);
+        input[duty.len()] = true;
+        pwm.input(&input);
+        pwm.output()[0]
+    }
+
+    /// create a pwm generator, and clear its counter.
+    fn reset(n_way: usize) -> PwmN {
+        let mut pwm = PwmN::new(n_way);
+        tick_reset(&mut pwm, &vec![false; n_way], true);
+        pwm
+    }
+
+    #[test]
+    fn test_pwm_default() {
+        let pwm = PwmN::new(4);
+        assert_eq!(pwm.output(), vec![false]);
+    }
+
+    #[test]
+    fn test_pwm_reset() {
+        let mut pwm = reset(2);
+        let duty = [true, false];
+        // the counter reads 0 after reset
+        assert!(tick_reset(&mut pwm, &duty, true));
+        assert!(!tick(&mut pwm, &duty));
+        assert!(tick_reset(&mut pwm, &duty, true));
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(5)]
+    #[case(8)]
+    #[case(15)]
+    fn test_pwm_duty(#[case] duty: usize) {
+        let mut pwm = reset(4);
+        let duty_bits: Vec<Potential> = (0..4).map(|i| (duty >> i) & 1 == 1).collect();
+        let mut observer = AveragingObserver::new(16);
+        for _ in 0..32 {
+            observer.sample(&tick(&mut pwm, &duty_bits));
+        }
+        assert_eq!(observer.level(), duty as f64 / 16.0);
+    }
+
+    #[test]
+    fn test_pwm_waveform() {
+        let mut pwm = reset(2);
+        let duty = [true, false];
+        // the counter reads 1, 2, 3, 0, 1 ...
+        let waveform: Vec<Potential> = (0..5).map(|_| tick(&mut pwm, &duty)).collect();
+        assert_eq!(waveform, vec![false, false, false, true, false]);
+    }
+
+    #[test]
+    fn test_averaging_observer_window() {
+        let mut observer = AveragingObserver::new(4);
+        assert_eq!(observer.level(), 0.0);
+        observer.sample(&true);
+        assert_eq!(observer.level(), 1.0);
+        observer.sample(&false);
+        assert_eq!(observer.level(), 0.5);
+        for _ in 0..4 {
+            observer.sample(&false);
+        }
+        assert_eq!(observer.level(), 0.0);
+    }
+}
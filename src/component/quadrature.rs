@@ -0,0 +1,187 @@
+use crate::{
+    circuit::{Potential, Wire, XORGate},
+    component::{Component, edge::EdgeDetector},
+};
+
+/// a quadrature decoder of a rotary encoder in circuit.
+/// the input is 3 bits, and the output is 2 bits.
+///
+/// the decoder samples the a and b phases on the rising edge of the clock. a step
+/// changes one phase, and its direction is `a xor previous b`.
+///
+/// ```ascii
+///            ┌──────────────┐
+/// clock──────┤              ├──────direction
+/// a──────────┤  Quadrature  │
+/// b──────────┤              ├──────count enable
+///            └──────────────┘
+/// ```
+///
+/// # input
+/// the clock, the a phase and the b phase.
+///
+/// # output
+/// the direction, which is high for clockwise, and the count enable, which is high
+/// for the clock cycle after a step. both phases changing at once is a lost step, and
+/// is not counted.
+///
+/// # truth table
+/// | A | B | A' | B' | DIR | EN |
+/// |---|---|---|---|---|---|
+/// | 0 | 0 | 1 | 0 | 1 | 1 |
+/// | 1 | 0 | 1 | 1 | 1 | 1 |
+/// | 1 | 1 | 0 | 1 | 1 | 1 |
+/// | 0 | 1 | 0 | 0 | 1 | 1 |
+/// | 0 | 0 | 0 | 1 | 0 | 1 |
+/// | 0 | 0 | 1 | 1 | any | 0 |
+#[derive(Debug, Default, Clone)]
+pub struct QuadratureDecoder {
+    input: [Wire; 3],
+    output: [Wire; 2],
+    previous: [Wire; 2],
+    clock_edge: EdgeDetector,
+    a_changed: XORGate,
+    b_changed: XORGate,
+    one_changed: XORGate,
+    direction: XORGate,
+}
+
+impl Component for QuadratureDecoder {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (3, 2)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+    fn update_state(&mut self) {
+        self.clock_edge.input(&[self.input[0].output()]);
+        if !self.clock_edge.is_rising() {
+            return;
+        }
+        let a = self.input[1].output();
+        let b = self.input[2].output();
+        self.a_changed.input(&a, &self.previous[0].output());
+        self.b_changed.input(&b, &self.previous[1].output());
+        self.one_changed
+            .input(&self.a_changed.output(), &self.b_changed.output());
+        self.direction.input(&a, &self.previous[1].output());
+        self.output[0].input(&self.direction.output());
+        self.output[1].input(&self.one_changed.output());
+        self.previous[0].input(&a);
+        self.previous[1].input(&b);
+    }
+}
+
+/// a host-side generator of the a and b phases of a rotary encoder, to drive a
+/// [`QuadratureDecoder`] from a test or an interactive demo.
+///
+/// # Examples
+///
+/// ```
+/// use simulation_computer_rust::component::quadrature::QuadratureGenerator;
+///
+/// let mut knob = QuadratureGenerator::default();
+/// assert_eq!(knob.step(true), [true, false]);
+/// assert_eq!(knob.step(true), [true, true]);
+/// assert_eq!(knob.step(false), [true, false]);
+/// assert_eq!(knob.position(), 1);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct QuadratureGenerator {
+    position: i64,
+}
+
+impl QuadratureGenerator {
+    /// the phases (a, b) of the positions, clockwise.
+    const PHASES: [[Potential; 2]; 4] =
+        [[false, false], [true, false], [true, true], [false, true]];
+
+    /// Get the phases of the current position.
+    pub fn phases(&self) -> [Potential; 2] {
+        Self::PHASES[self.position.rem_euclid(4) as usize]
+    }
+
+    /// Turn the knob a step, and get the new phases.
+    pub fn step(&mut self, clockwise: bool) -> [Potential; 2] {
+        self.position += if clockwise { 1 } else { -1 };
+        self.phases()
+    }
+
+    /// Get the steps turned clockwise, a counterclockwise step counts -1.
+    pub fn position(&self) -> i64 {
+        self.position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn tick(decoder: &mut QuadratureDecoder, phases: [Potential; 2]) -> Vec<Potential> {
+        decoder.input(&[false, phases[0], phases[1]]);
+        decoder.input(&[true, phases[0], phases[1]]);
+        decoder.output()
+    }
+
+    #[test]
+    fn test_quadrature_default() {
+        let mut decoder = QuadratureDecoder::default();
+        assert_eq!(decoder.output(), vec![false, false]);
+        // no step, no count
+        assert_eq!(tick(&mut decoder, [false, false]), vec![false, false]);
+    }
+
+    #[rstest]
+    // [a, b] before, [a, b] after => [direction, count enable]
+    #[case([false, false], [true, false], [true, true])]
+    #[case([true, false], [true, true], [true, true])]
+    #[case([true, true], [false, true], [true, true])]
+    #[case([false, true], [false, false], [true, true])]
+    #[case([false, false], [false, true], [false, true])]
+    #[case([false, true], [true, true], [false, true])]
+    #[case([false, false], [true, true], [true, false])]
+    // the direction is not used without a step
+    #[case([true, false], [true, false], [true, false])]
+    fn test_quadrature_step(
+        #[case] before: [Potential; 2],
+        #[case] after: [Potential; 2],
+        #[case] expected: [Potential; 2],
+    ) {
+        let mut decoder = QuadratureDecoder::default();
+        tick(&mut decoder, before);
+        assert_eq!(tick(&mut decoder, after), expected.to_vec());
+    }
+
+    #[test]
+    fn test_quadrature_knob() {
+        let mut decoder = QuadratureDecoder::default();
+        let mut knob = QuadratureGenerator::default();
+        tick(&mut decoder, knob.phases());
+        let mut count = 0;
+        for clockwise in [
+            true, true, true, true, true, false, false, true, false, false,
+        ] {
+            let output = tick(&mut decoder, knob.step(clockwise));
+            assert!(output[1]);
+            count += if output[0] { 1 } else { -1 };
+            // the knob rests between the steps
+            assert!(!tick(&mut decoder, knob.phases())[1]);
+        }
+        assert_eq!(count, knob.position());
+        assert_eq!(count, 2);
+    }
+}
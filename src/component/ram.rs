@@ -0,0 +1,132 @@
+use crate::{
+    circuit::{Potential, Wire},
+    component::{Component, edge::EdgeDetector, fidelity::Fidelity},
+};
+
+/// a random-access memory of bytes, a model of [`Fidelity::Behavioral`].
+/// the input is n+10 bits, and the output is 8 bits, where 2^n is the size.
+///
+/// the bytes are a host vector instead of a register of gates per bit, so a memory
+/// of 64KB is as cheap as its bytes. the byte at the address is read at once, and
+/// the data is written on the rising edge of the clock while write is high.
+///
+/// ```ascii
+///            ┌─────────┐
+/// clock──────┤         │
+/// write──────┤   RAM   ├──────data out
+/// address────┤         │
+/// data in────┤         │
+///            └─────────┘
+/// ```
+///
+/// # input
+/// the clock, the write, the n bits of the address and the 8 bits of the data in
+/// (the first bit is the lowest bit).
+///
+/// # output
+/// the byte at the address (the first bit is the lowest bit).
+#[derive(Debug, Clone)]
+pub struct Ram {
+    width: usize,
+    memory: Vec<u8>,
+    input: Vec<Wire>,
+    output: [Wire; 8],
+    clock_edge: EdgeDetector,
+}
+
+impl Ram {
+    /// Create a ram of `2^width` bytes, which are 0.
+    pub fn new(width: usize) -> Self {
+        assert!(width > 0 && width <= 24, "width must be between 1 and 24");
+        Self {
+            width,
+            memory: vec![0; 1 << width],
+            input: vec![Wire::default(); width + 10],
+            output: [Wire::default(); 8],
+            clock_edge: EdgeDetector::default(),
+        }
+    }
+
+    /// Get the bytes of the ram.
+    pub fn memory(&self) -> &[u8] {
+        &self.memory
+    }
+
+    /// Get the bytes of the ram to preload them.
+    pub fn memory_mut(&mut self) -> &mut [u8] {
+        &mut self.memory
+    }
+
+    fn value(&self, start: usize, width: usize) -> usize {
+        (0..width).fold(0, |acc, i| {
+            acc | (self.input[start + i].output() as usize) << i
+        })
+    }
+}
+
+impl Component for Ram {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.width + 10, 8)
+    }
+    fn fidelity(&self) -> Fidelity {
+        Fidelity::Behavioral
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+    fn update_state(&mut self) {
+        self.clock_edge.input(&[self.input[0].output()]);
+        let address = self.value(2, self.width);
+        if self.clock_edge.is_rising() && self.input[1].output() {
+            self.memory[address] = self.value(2 + self.width, 8) as u8;
+        }
+        let byte = self.memory[address];
+        for bit in 0..8 {
+            self.output[bit].input(&((byte >> bit) & 1 == 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access(ram: &mut Ram, write: bool, address: usize, data: u8) -> u8 {
+        let mut input = vec![false, write];
+        input.extend((0..ram.width).map(|i| (address >> i) & 1 == 1));
+        input.extend((0..8).map(|i| (data >> i) & 1 == 1));
+        ram.input(&input);
+        input[0] = true;
+        ram.input(&input);
+        ram.output()
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (i, bit)| acc | (*bit as u8) << i)
+    }
+
+    #[test]
+    fn test_ram_read_write() {
+        let mut ram = Ram::new(16);
+        assert_eq!(ram.get_pin_count(), (26, 8));
+        assert_eq!(ram.memory().len(), 65536);
+        ram.memory_mut()[0x1234] = 0x5a;
+        assert_eq!(access(&mut ram, false, 0x1234, 0xff), 0x5a);
+        assert_eq!(access(&mut ram, true, 0xfffe, 0xc3), 0xc3);
+        assert_eq!(access(&mut ram, false, 0xfffe, 0), 0xc3);
+        assert_eq!(ram.memory()[0xfffe], 0xc3);
+        assert_eq!(ram.fidelity(), Fidelity::Behavioral);
+    }
+}
@@ -0,0 +1,496 @@
+use crate::{
+    circuit::{ANDGate, NOTGate, ORGate, Potential, Wire, init},
+    component::{
+        Component,
+        big_gates::ORGateN,
+        datasheet::{Metadata, Timing},
+        decoder::Decoder2_4,
+        enabler::EnablerN,
+        fidelity::Fidelity,
+        latch::{DFlipFlop, DLatch},
+        port::Port,
+    },
+};
+
+/// a n-bit register in circuit.
+/// the input is n+2 bits, and the output is n bits.
+///
+/// every bit is a [`DLatch`], which stores the data while set is high, and the
+/// stored bits are driven to the outputs by an [`EnablerN`] while enable is high,
+/// so many registers can share a bus.
+///
+/// ```ascii
+///               d0  d1  d2  d3
+///               │   │   │   │
+///           ┌───┴───┴───┴───┴───┐
+/// set───────┤      D Latches    │
+///           └───┬───┬───┬───┬───┘
+///           ┌───┴───┴───┴───┴───┐
+/// enable────┤      Enabler      │
+///           └───┬───┬───┬───┬───┘
+///               │   │   │   │
+///               o0  o1  o2  o3
+/// ```
+///
+/// a register of [`Fidelity::Behavioral`] stores the bits in wires, without the
+/// latches and the enabler.
+///
+/// # input
+/// the n bits of the data, the set and the enable.
+///
+/// # output
+/// the stored bits while enable is high, otherwise low.
+#[derive(Debug, Clone)]
+pub struct RegisterN {
+    width: usize,
+    input: Vec<Wire>,
+    latches: Vec<DLatch>,
+    enabler: EnablerN,
+    // the stored bits and the outputs of the behavioral model
+    stored: Vec<Wire>,
+    output: Vec<Wire>,
+}
+
+impl RegisterN {
+    pub fn new(width: usize) -> Self {
+        Self::with_fidelity(width, Fidelity::Gate)
+    }
+
+    /// Create a register of `width` bits, simulated at the fidelity.
+    pub fn with_fidelity(width: usize, fidelity: Fidelity) -> Self {
+        assert!(width > 0, "width must be greater than 0");
+        let gates = match fidelity {
+            Fidelity::Gate => width,
+            Fidelity::Behavioral => 0,
+        };
+        Self {
+            width,
+            input: vec![Wire::default(); width + 2],
+            latches: vec![DLatch::default(); gates],
+            enabler: EnablerN::new(gates),
+            stored: init::power_up(width - gates),
+            output: vec![Wire::default(); width - gates],
+        }
+    }
+
+    /// Get the stored bits, whatever the enable is.
+    pub fn stored(&self) -> Vec<Potential> {
+        if self.fidelity() == Fidelity::Behavioral {
+            return self.stored.iter().map(|wire| wire.output()).collect();
+        }
+        self.latches
+            .iter()
+            .map(|latch| latch.get_pin_output(0))
+            .collect()
+    }
+}
+
+impl Component for RegisterN {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.width + 2, self.width)
+    }
+    fn fidelity(&self) -> Fidelity {
+        if self.latches.is_empty() {
+            Fidelity::Behavioral
+        } else {
+            Fidelity::Gate
+        }
+    }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "register",
+            description: "stores n bits while set is high, and drives them while enable is high.",
+            timing: Timing::Level,
+        })
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        if self.fidelity() == Fidelity::Behavioral {
+            return self.output[position].output();
+        }
+        self.enabler.get_pin_output(position)
+    }
+    fn ports(&self) -> Vec<Port> {
+        let n = self.width;
+        vec![
+            Port::input("data", 0..n),
+            Port::input("set", n..n + 1),
+            Port::input("enable", n + 1..n + 2),
+            Port::output("out", 0..n),
+        ]
+    }
+    fn update_state(&mut self) {
+        let set = self.input[self.width].output();
+        if self.fidelity() == Fidelity::Behavioral {
+            let enable = self.input[self.width + 1].output();
+            for i in 0..self.width {
+                if set {
+                    let bit = self.input[i].output();
+                    self.stored[i].input(&bit);
+                }
+                self.output[i].input(&(enable && self.stored[i].output()));
+            }
+            return;
+        }
+        for (i, latch) in self.latches.iter_mut().enumerate() {
+            latch.input(&[set, self.input[i].output()]);
+            self.enabler.set_pin_input(i, &latch.get_pin_output(0));
+        }
+        self.enabler
+            .set_pin_input(self.width, &self.input[self.width + 1].output());
+        self.enabler.update_state();
+    }
+}
+
+/// the modes of a [`ShiftRegisterN`], which are the values of its two mode pins.
+pub mod mode {
+    pub const HOLD: usize = 0;
+    /// every bit moves to the lower bit, and the serial in is the highest bit.
+    pub const SHIFT_RIGHT: usize = 1;
+    /// every bit moves to the higher bit, and the serial in is the lowest bit.
+    pub const SHIFT_LEFT: usize = 2;
+    pub const LOAD: usize = 3;
+}
+
+/// a n-bit universal shift register in circuit.
+/// the input is n+4 bits, and the output is n+1 bits.
+///
+/// every bit is a [`DFlipFlop`], whose data is picked by the mode from the bit itself,
+/// its higher bit, its lower bit or the parallel data. the mode is decoded by a
+/// [`Decoder2_4`], and every bit is the or of the and of a decoded line and its
+/// source, so a word is loaded in parallel and sent over a single wire (piso), or
+/// received from a single wire and read in parallel (sipo).
+///
+/// ```ascii
+///                  d0  d1  d2  d3
+///                  │   │   │   │
+///              ┌───┴───┴───┴───┴───┐
+/// clock────────┤                   │
+/// mode─────────┤  Shift Register   ├──────serial out
+/// serial in────┤                   │
+///              └───┬───┬───┬───┬───┘
+///                  │   │   │   │
+///                  q0  q1  q2  q3
+/// ```
+///
+/// # input
+/// the clock, the two bits of the mode (the first bit is the lowest bit), the
+/// serial in and the n bits of the data.
+///
+/// # output
+/// the n stored bits, and the serial out, which is the highest bit while shifting
+/// left, otherwise the lowest bit.
+///
+/// # truth table
+/// on the rising edge of the clock, the bits are from the highest bit.
+///
+/// | mode | Q |
+/// |---|---|
+/// | 0 | Q |
+/// | 1 | serial in, Q(n-1) .. Q1 |
+/// | 2 | Q(n-2) .. Q0, serial in |
+/// | 3 | D |
+#[derive(Debug, Clone)]
+pub struct ShiftRegisterN {
+    width: usize,
+    input: Vec<Wire>,
+    decoder: Decoder2_4,
+    // the and gates of hold, shift right, shift left and load of every bit
+    and_gates: Vec<[ANDGate; 4]>,
+    or_gates: Vec<ORGateN>,
+    flip_flops: Vec<DFlipFlop>,
+    serial_out: [ANDGate; 2],
+    not_left: NOTGate,
+    serial_or: ORGate,
+}
+
+impl ShiftRegisterN {
+    pub fn new(width: usize) -> Self {
+        assert!(width > 1, "width must be greater than 1");
+        Self {
+            width,
+            input: vec![Wire::default(); width + 4],
+            decoder: Decoder2_4::default(),
+            and_gates: (0..width).map(|_| Default::default()).collect(),
+            or_gates: vec![ORGateN::new(4); width],
+            flip_flops: vec![DFlipFlop::default(); width],
+            serial_out: Default::default(),
+            not_left: NOTGate::default(),
+            serial_or: ORGate::default(),
+        }
+    }
+}
+
+impl Component for ShiftRegisterN {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.width + 4, self.width + 1)
+    }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "shift register",
+            description: "holds, shifts left or right, or loads n bits on the rising edge of the clock.",
+            timing: Timing::RisingEdge { clock: 0 },
+        })
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        if position == self.width {
+            return self.serial_or.output();
+        }
+        self.flip_flops[position].get_pin_output(0)
+    }
+    fn ports(&self) -> Vec<Port> {
+        let n = self.width;
+        vec![
+            Port::input("clock", 0..1),
+            Port::input("mode", 1..3),
+            Port::input("serial_in", 3..4),
+            Port::input("data", 4..n + 4),
+            Port::output("out", 0..n),
+            Port::output("serial_out", n..n + 1),
+        ]
+    }
+    fn update_state(&mut self) {
+        let n = self.width;
+        let clock = self.input[0].output();
+        let serial_in = self.input[3].output();
+        self.decoder
+            .input(&[self.input[1].output(), self.input[2].output()]);
+        // the sources are read before any flip-flop is clocked
+        let q: Vec<Potential> = self
+            .flip_flops
+            .iter()
+            .map(|flip_flop| flip_flop.get_pin_output(0))
+            .collect();
+        for i in 0..n {
+            let sources = [
+                q[i],
+                if i + 1 < n { q[i + 1] } else { serial_in },
+                if i > 0 { q[i - 1] } else { serial_in },
+                self.input[4 + i].output(),
+            ];
+            for (line, and_gate) in self.and_gates[i].iter_mut().enumerate() {
+                and_gate.input(&self.decoder.get_pin_output(line), &sources[line]);
+                self.or_gates[i].set_pin_input(line, &and_gate.output());
+            }
+            self.or_gates[i].update_state();
+            self.flip_flops[i].input(&[clock, self.or_gates[i].get_pin_output(0)]);
+        }
+        let left = self.decoder.get_pin_output(mode::SHIFT_LEFT);
+        self.not_left.input(&left);
+        self.serial_out[0].input(&left, &self.flip_flops[n - 1].get_pin_output(0));
+        self.serial_out[1].input(
+            &self.not_left.output(),
+            &self.flip_flops[0].get_pin_output(0),
+        );
+        self.serial_or
+            .input(&self.serial_out[0].output(), &self.serial_out[1].output());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::{Pad, Potentials};
+    use crate::component::truth_table::TruthTable;
+    use rstest::rstest;
+
+    #[test]
+    fn test_register_default() {
+        let register = RegisterN::new(4);
+        assert_eq!(register.output(), vec![false; 4]);
+    }
+
+    #[rstest]
+    // data, set, enable => output
+    #[case(0b1010, true, true, 0b1010)]
+    #[case(0b1010, true, false, 0b0000)]
+    #[case(0b1111, false, true, 0b0110)]
+    #[case(0b1111, false, false, 0b0000)]
+    fn test_register_set_enable(
+        #[case] data: i64,
+        #[case] set: bool,
+        #[case] enable: bool,
+        #[case] expected: i64,
+    ) {
+        let mut register = RegisterN::new(4);
+        // store 0b0110 first
+        register.set_port("data", &Potentials::with_width(0b0110, 4, Pad::Zero));
+        register.set_port("set", &Potentials::with_width(1, 1, Pad::Zero));
+        register.update_state();
+        register.set_port("data", &Potentials::with_width(data, 4, Pad::Zero));
+        register.set_port("set", &Potentials::with_width(set as i64, 1, Pad::Zero));
+        register.set_port(
+            "enable",
+            &Potentials::with_width(enable as i64, 1, Pad::Zero),
+        );
+        register.update_state();
+        assert_eq!(
+            register.get_port("out").get_data(true),
+            Potentials::with_width(expected, 4, Pad::Zero).get_data(true)
+        );
+    }
+
+    #[test]
+    fn test_register_holds() {
+        let mut register = RegisterN::new(3);
+        register.input(&[true, false, true, true, false]);
+        assert_eq!(register.output(), vec![false; 3]);
+        assert_eq!(register.stored(), vec![true, false, true]);
+        // the data changes after set is low
+        register.input(&[false, true, false, false, true]);
+        assert_eq!(register.output(), vec![true, false, true]);
+    }
+
+    fn clock(register: &mut ShiftRegisterN, mode: usize, serial_in: bool, data: u64) {
+        let n = register.width;
+        let mut input = vec![false, mode & 1 == 1, mode & 2 == 2, serial_in];
+        input.extend((0..n).map(|i| (data >> i) & 1 == 1));
+        register.input(&input);
+        input[0] = true;
+        register.input(&input);
+    }
+
+    fn value(register: &ShiftRegisterN) -> u64 {
+        register
+            .get_port("out")
+            .get_data(true)
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (i, bit)| acc | (*bit as u64) << i)
+    }
+
+    /// the bits of a cell of the doc truth table from the highest bit, e.g.
+    /// `serial in, Q(n-1) .. Q1`.
+    fn doc_bits(cell: &str, n: usize, q: u64, data: u64, serial_in: bool) -> Vec<Potential> {
+        let index = |name: &str| match name.trim_start_matches("Q(n-").strip_suffix(')') {
+            Some(k) => n - k.parse::<usize>().unwrap(),
+            None => name.trim_start_matches('Q').parse().unwrap(),
+        };
+        let bits = |value: u64, high: usize, low: usize| -> Vec<Potential> {
+            (low..=high).rev().map(|i| (value >> i) & 1 == 1).collect()
+        };
+        cell.split(", ")
+            .flat_map(|part| match part {
+                "Q" => bits(q, n - 1, 0),
+                "D" => bits(data, n - 1, 0),
+                "serial in" => vec![serial_in],
+                _ => {
+                    let (high, low) = part.split_once(" .. ").unwrap();
+                    bits(q, index(high), index(low))
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_shift_register_docs() {
+        // the truth table in the doc comment is the behavior of the shift register
+        let source = include_str!("register.rs");
+        let rows: Vec<(usize, &str)> = source
+            .lines()
+            .skip_while(|line| *line != "/// | mode | Q |")
+            .skip(2)
+            .take_while(|line| line.starts_with("/// |"))
+            .map(|line| {
+                let (mode, cell) = line
+                    .trim_start_matches("/// | ")
+                    .trim_end_matches(" |")
+                    .split_once(" | ")
+                    .unwrap();
+                (mode.parse().unwrap(), cell)
+            })
+            .collect();
+        assert_eq!(rows.len(), 4);
+        let n = 4;
+        for (mode, cell) in rows {
+            for (q, data, serial_in) in [(0b0110, 0b1001, true), (0b1001, 0b0110, false)] {
+                let mut register = ShiftRegisterN::new(n);
+                clock(&mut register, mode::LOAD, false, q);
+                let mut input = vec![false, mode & 1 == 1, mode & 2 == 2, serial_in];
+                input.extend((0..n).map(|i| (data >> i) & 1 == 1));
+                let high = [&[true], &input[1..]].concat();
+                let table = TruthTable::of_rows(&mut register, &[input, high]);
+                let output: Vec<Potential> = table.rows()[1].1[..n].iter().rev().copied().collect();
+                assert_eq!(
+                    output,
+                    doc_bits(cell, n, q, data, serial_in),
+                    "mode {}",
+                    mode
+                );
+            }
+        }
+    }
+
+    #[rstest]
+    // mode, serial in => stored after 0b0110
+    #[case(mode::HOLD, true, 0b0110)]
+    #[case(mode::SHIFT_RIGHT, true, 0b1011)]
+    #[case(mode::SHIFT_RIGHT, false, 0b0011)]
+    #[case(mode::SHIFT_LEFT, true, 0b1101)]
+    #[case(mode::SHIFT_LEFT, false, 0b1100)]
+    #[case(mode::LOAD, false, 0b1001)]
+    fn test_shift_register_modes(
+        #[case] mode: usize,
+        #[case] serial_in: bool,
+        #[case] expected: u64,
+    ) {
+        let mut register = ShiftRegisterN::new(4);
+        clock(&mut register, mode::LOAD, false, 0b0110);
+        assert_eq!(value(&register), 0b0110);
+        clock(&mut register, mode, serial_in, 0b1001);
+        assert_eq!(value(&register), expected);
+    }
+
+    #[test]
+    fn test_shift_register_serial_out() {
+        let mut register = ShiftRegisterN::new(4);
+        clock(&mut register, mode::LOAD, false, 0b1000);
+        assert!(!register.get_pin_output(4));
+        clock(&mut register, mode::SHIFT_LEFT, false, 0);
+        assert!(!register.get_pin_output(4));
+        // the highest bit is out before it is shifted
+        clock(&mut register, mode::LOAD, false, 0b1000);
+        register.input(&[false, false, true, false, false, false, false, false]);
+        assert!(register.get_pin_output(4));
+    }
+
+    #[test]
+    fn test_shift_register_serialize() {
+        // a word is sent over a single wire, the lowest bit first
+        let mut sender = ShiftRegisterN::new(8);
+        let mut receiver = ShiftRegisterN::new(8);
+        clock(&mut sender, mode::LOAD, false, 0xa7);
+        for _ in 0..8 {
+            let wire = sender.get_pin_output(8);
+            clock(&mut receiver, mode::SHIFT_RIGHT, wire, 0);
+            clock(&mut sender, mode::SHIFT_RIGHT, false, 0);
+        }
+        assert_eq!(value(&receiver), 0xa7);
+        assert_eq!(value(&sender), 0);
+    }
+}
@@ -0,0 +1,821 @@
+use std::{collections::BTreeMap, fmt, ops::RangeInclusive, str::FromStr};
+
+use crate::component::{
+    Component,
+    adder::{AddSubN, FullAdder, HalfAdder, LookAheadCarryAdderN, RippleCarryAdderN},
+    alu::ALU,
+    beeper::BeeperN,
+    big_gates::{ANDGate3, ANDGateN, MajorityGate3, ORGate3, ORGateN, ThresholdGate},
+    comparator::ComparatorN,
+    counter::{BcdCounter, CounterN, FrequencyCounter, JohnsonCounterN, RingCounterN, Stepper},
+    decoder::{Decoder1_2, Decoder2_4, DecoderN, SevenSegmentDecoder},
+    digital_clock::DigitalClock,
+    edge::{Debouncer, EdgeDetector},
+    enabler::EnablerN,
+    encoder::{Encoder2_1, Encoder4_2, OneHotToBinary, PriorityEncoder4_2},
+    fanout::Fanout,
+    led_matrix::LedMatrix,
+    mpu::MPU,
+    multiplier::ArrayMultiplier,
+    mux::DemuxN,
+    pwm::PwmN,
+    register::{RegisterN, ShiftRegisterN},
+    rtc::Rtc,
+    subtractor::{FullSubtractor, HalfSubtractor, RippleBorrowSubtractorN},
+    timer::TimerN,
+    vga::{VGA_640X480, VgaTiming},
+};
+
+/// the separator of the name and the version in a component id, e.g. `adder.ripple@1`.
+pub const VERSION_SEPARATOR: char = '@';
+
+/// the largest width of a bus parameter of the builtin components, e.g. `n_way`.
+pub const MAX_WIDTH: usize = 1024;
+
+/// the largest width of an address of the builtin components, which is decoded
+/// into 2^width lines, e.g. the `width` of `decoder.n`.
+pub const MAX_ADDRESS_WIDTH: usize = 16;
+
+/// the named parameters of a constructor, e.g. `n_way`.
+pub type Params = BTreeMap<String, usize>;
+
+/// the constructor of a component from its parameters.
+pub type Constructor = fn(&Params) -> Result<Box<dyn Component>, RegistryError>;
+
+/// the error of registering or creating a component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    /// no component is registered under the name.
+    UnknownComponent(String),
+    /// the component is registered, but not with the version.
+    UnknownVersion { name: String, version: u32 },
+    /// the name of the id is empty or the version is not a number.
+    InvalidId(String),
+    /// a parameter the constructor needs is not given.
+    MissingParam { name: String, param: String },
+    /// a parameter is out of the range the constructor accepts.
+    InvalidParam {
+        name: String,
+        param: String,
+        value: usize,
+    },
+    /// the name is already registered with the version.
+    Duplicate { name: String, version: u32 },
+    /// the text of a [`ComponentSpec`] is invalid.
+    InvalidSpec(String),
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownComponent(name) => write!(f, "component `{}` is not registered", name),
+            Self::UnknownVersion { name, version } => {
+                write!(f, "component `{}` has no version {}", name, version)
+            }
+            Self::InvalidId(id) => write!(f, "component id `{}` is invalid", id),
+            Self::MissingParam { name, param } => {
+                write!(f, "component `{}` needs parameter `{}`", name, param)
+            }
+            Self::InvalidParam { name, param, value } => {
+                write!(
+                    f,
+                    "component `{}` does not accept {} for parameter `{}`",
+                    name, value, param
+                )
+            }
+            Self::Duplicate { name, version } => {
+                write!(
+                    f,
+                    "component `{}` version {} is already registered",
+                    name, version
+                )
+            }
+            Self::InvalidSpec(text) => write!(f, "component spec `{}` is invalid", text),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+/// Get a parameter of the constructor of `name`.
+pub fn param(params: &Params, name: &str, param: &str) -> Result<usize, RegistryError> {
+    params
+        .get(param)
+        .copied()
+        .ok_or_else(|| RegistryError::MissingParam {
+            name: name.to_string(),
+            param: param.to_string(),
+        })
+}
+
+/// Get a parameter of the constructor of `name`, which must be accepted by `valid`.
+pub fn param_where(
+    params: &Params,
+    name: &str,
+    param: &str,
+    valid: impl Fn(usize) -> bool,
+) -> Result<usize, RegistryError> {
+    let value = self::param(params, name, param)?;
+    if !valid(value) {
+        return Err(RegistryError::InvalidParam {
+            name: name.to_string(),
+            param: param.to_string(),
+            value,
+        });
+    }
+    Ok(value)
+}
+
+/// Get a parameter of the constructor of `name`, which must be in `range`.
+pub fn param_in(
+    params: &Params,
+    name: &str,
+    param: &str,
+    range: RangeInclusive<usize>,
+) -> Result<usize, RegistryError> {
+    param_where(params, name, param, |value| range.contains(&value))
+}
+
+/// a library of components, which registers its constructors into a registry.
+///
+/// a downstream crate ships its own [`Component`] implementations as a library, and
+/// the users install it next to the builtin components. the names of a library should
+/// be prefixed by the name of the library, e.g. `mylib.uart`, to avoid the conflicts.
+///
+/// # Examples
+///
+/// ```
+/// use simulation_computer_rust::component::{
+///     big_gates::ORGate3,
+///     registry::{ComponentLibrary, ComponentRegistry, Params, RegistryError},
+/// };
+///
+/// struct MyLibrary;
+///
+/// impl ComponentLibrary for MyLibrary {
+///     fn register(&self, registry: &mut ComponentRegistry) -> Result<(), RegistryError> {
+///         registry.register("mylib.or3", 1, |_| Ok(Box::new(ORGate3::default())))
+///     }
+/// }
+///
+/// let mut registry = ComponentRegistry::builtin();
+/// registry.install(&MyLibrary).unwrap();
+/// assert!(registry.create("mylib.or3", &Params::new()).is_ok());
+/// ```
+pub trait ComponentLibrary {
+    /// Register the constructors of the library.
+    fn register(&self, registry: &mut ComponentRegistry) -> Result<(), RegistryError>;
+}
+
+/// the serialized form of a component in a netlist, the id and the parameters.
+///
+/// the text form is the id and then the parameters as `name=value` sorted by name,
+/// separated by spaces, e.g. `adder.ripple@1 n_way=8`. the form is stable, so a
+/// netlist written by one version of a library is read back by the later ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentSpec {
+    pub id: String,
+    pub params: Params,
+}
+
+impl fmt::Display for ComponentSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id)?;
+        for (name, value) in self.params.iter() {
+            write!(f, " {}={}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ComponentSpec {
+    type Err = RegistryError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || RegistryError::InvalidSpec(text.to_string());
+        let mut fields = text.split_whitespace();
+        let id = fields.next().ok_or_else(invalid)?.to_string();
+        let mut params = Params::new();
+        for field in fields {
+            let (name, value) = field.split_once('=').ok_or_else(invalid)?;
+            let value = value.parse().map_err(|_| invalid())?;
+            if name.is_empty() || params.insert(name.to_string(), value).is_some() {
+                return Err(invalid());
+            }
+        }
+        Ok(Self { id, params })
+    }
+}
+
+/// a registry of component constructors by name and version.
+///
+/// a netlist file, a script or the gui creates a component by its id, which is the
+/// name, e.g. `adder.ripple`, for the latest version, or the name and the version,
+/// e.g. `adder.ripple@1`. a new version of a component is registered next to the old
+/// one, so the files written for the old version keep working.
+///
+/// # Examples
+///
+/// ```
+/// use simulation_computer_rust::component::registry::{ComponentRegistry, Params};
+///
+/// let registry = ComponentRegistry::builtin();
+/// let params = Params::from([("n_way".to_string(), 8)]);
+/// let adder = registry.create("adder.ripple", &params).unwrap();
+/// assert_eq!(adder.get_pin_count(), (17, 9));
+/// assert!(registry.create("adder.ripple", &Params::new()).is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ComponentRegistry {
+    constructors: BTreeMap<String, BTreeMap<u32, Constructor>>,
+}
+
+impl ComponentRegistry {
+    /// Create a registry of the components of this crate.
+    pub fn builtin() -> Self {
+        let mut registry = Self::default();
+        let builtins: [(&str, Constructor); 46] = [
+            ("gate.and3", |_| Ok(Box::new(ANDGate3::default()))),
+            ("gate.or3", |_| Ok(Box::new(ORGate3::default()))),
+            ("gate.and", |p| {
+                Ok(Box::new(ANDGateN::new(param_in(
+                    p,
+                    "gate.and",
+                    "n_way",
+                    1..=MAX_WIDTH,
+                )?)))
+            }),
+            ("gate.or", |p| {
+                Ok(Box::new(ORGateN::new(param_in(
+                    p,
+                    "gate.or",
+                    "n_way",
+                    1..=MAX_WIDTH,
+                )?)))
+            }),
+            ("gate.majority3", |_| Ok(Box::new(MajorityGate3::default()))),
+            ("gate.threshold", |p| {
+                let n_way = param_in(p, "gate.threshold", "n_way", 1..=MAX_WIDTH)?;
+                let k = param_in(p, "gate.threshold", "k", 0..=n_way)?;
+                Ok(Box::new(ThresholdGate::new(n_way, k)))
+            }),
+            ("adder.half", |_| Ok(Box::new(HalfAdder::default()))),
+            ("adder.full", |_| Ok(Box::new(FullAdder::default()))),
+            ("adder.ripple", |p| {
+                Ok(Box::new(RippleCarryAdderN::new(param_in(
+                    p,
+                    "adder.ripple",
+                    "n_way",
+                    1..=MAX_WIDTH,
+                )?)))
+            }),
+            ("adder.lookahead", |p| {
+                Ok(Box::new(LookAheadCarryAdderN::new(param_in(
+                    p,
+                    "adder.lookahead",
+                    "n_way",
+                    1..=MAX_WIDTH,
+                )?)))
+            }),
+            ("adder.add_sub", |p| {
+                Ok(Box::new(AddSubN::new(param_in(
+                    p,
+                    "adder.add_sub",
+                    "n_way",
+                    1..=MAX_WIDTH,
+                )?)))
+            }),
+            ("alu", |p| {
+                Ok(Box::new(ALU::new(param_in(
+                    p,
+                    "alu",
+                    "width",
+                    2..=MAX_WIDTH,
+                )?)))
+            }),
+            ("multiplier.array", |p| {
+                Ok(Box::new(ArrayMultiplier::new(param_in(
+                    p,
+                    "multiplier.array",
+                    "width",
+                    1..=MAX_WIDTH,
+                )?)))
+            }),
+            ("subtractor.half", |_| {
+                Ok(Box::new(HalfSubtractor::default()))
+            }),
+            ("subtractor.full", |_| {
+                Ok(Box::new(FullSubtractor::default()))
+            }),
+            ("subtractor.ripple", |p| {
+                Ok(Box::new(RippleBorrowSubtractorN::new(param_in(
+                    p,
+                    "subtractor.ripple",
+                    "n_way",
+                    1..=MAX_WIDTH,
+                )?)))
+            }),
+            ("comparator.n", |p| {
+                Ok(Box::new(ComparatorN::new(param_in(
+                    p,
+                    "comparator.n",
+                    "width",
+                    1..=MAX_WIDTH,
+                )?)))
+            }),
+            ("fanout", |p| {
+                Ok(Box::new(Fanout::new(param_in(
+                    p,
+                    "fanout",
+                    "n_way",
+                    1..=MAX_WIDTH,
+                )?)))
+            }),
+            ("decoder.1_2", |_| Ok(Box::new(Decoder1_2::default()))),
+            ("decoder.2_4", |_| Ok(Box::new(Decoder2_4::default()))),
+            ("decoder.n", |p| {
+                Ok(Box::new(DecoderN::new(param_in(
+                    p,
+                    "decoder.n",
+                    "width",
+                    1..=MAX_ADDRESS_WIDTH,
+                )?)))
+            }),
+            ("decoder.seven_segment", |_| {
+                Ok(Box::new(SevenSegmentDecoder::default()))
+            }),
+            ("demux.n", |p| {
+                Ok(Box::new(DemuxN::new(param_in(
+                    p,
+                    "demux.n",
+                    "width",
+                    1..=MAX_ADDRESS_WIDTH,
+                )?)))
+            }),
+            ("register.n", |p| {
+                Ok(Box::new(RegisterN::new(param_in(
+                    p,
+                    "register.n",
+                    "width",
+                    1..=MAX_WIDTH,
+                )?)))
+            }),
+            ("shift_register.n", |p| {
+                Ok(Box::new(ShiftRegisterN::new(param_in(
+                    p,
+                    "shift_register.n",
+                    "width",
+                    2..=MAX_WIDTH,
+                )?)))
+            }),
+            ("encoder.2_1", |_| Ok(Box::new(Encoder2_1::default()))),
+            ("encoder.4_2", |_| Ok(Box::new(Encoder4_2::default()))),
+            ("encoder.priority_4_2", |_| {
+                Ok(Box::new(PriorityEncoder4_2::default()))
+            }),
+            ("encoder.one_hot", |p| {
+                Ok(Box::new(OneHotToBinary::new(param_in(
+                    p,
+                    "encoder.one_hot",
+                    "width",
+                    1..=MAX_ADDRESS_WIDTH,
+                )?)))
+            }),
+            ("enabler", |p| {
+                Ok(Box::new(EnablerN::new(param_in(
+                    p,
+                    "enabler",
+                    "n_way",
+                    1..=MAX_WIDTH,
+                )?)))
+            }),
+            ("edge.detector", |_| Ok(Box::new(EdgeDetector::default()))),
+            ("edge.debouncer", |p| {
+                Ok(Box::new(Debouncer::new(param_in(
+                    p,
+                    "edge.debouncer",
+                    "threshold",
+                    1..=usize::MAX,
+                )?)))
+            }),
+            ("counter.ring", |p| {
+                Ok(Box::new(RingCounterN::new(param_in(
+                    p,
+                    "counter.ring",
+                    "n_way",
+                    2..=MAX_WIDTH,
+                )?)))
+            }),
+            ("counter.johnson", |p| {
+                Ok(Box::new(JohnsonCounterN::new(param_in(
+                    p,
+                    "counter.johnson",
+                    "n_way",
+                    2..=MAX_WIDTH,
+                )?)))
+            }),
+            ("counter.bcd", |_| Ok(Box::new(BcdCounter::default()))),
+            ("counter.frequency", |p| {
+                Ok(Box::new(FrequencyCounter::new(
+                    param_in(p, "counter.frequency", "width", 1..=MAX_WIDTH)?,
+                    param_in(p, "counter.frequency", "gate_cycles", 1..=usize::MAX)?,
+                )))
+            }),
+            ("counter.n", |p| {
+                Ok(Box::new(CounterN::new(param_in(
+                    p,
+                    "counter.n",
+                    "width",
+                    1..=MAX_WIDTH,
+                )?)))
+            }),
+            ("counter.stepper", |p| {
+                Ok(Box::new(Stepper::new(param_in(
+                    p,
+                    "counter.stepper",
+                    "steps",
+                    2..=MAX_WIDTH,
+                )?)))
+            }),
+            ("clock.digital", |_| Ok(Box::new(DigitalClock::default()))),
+            ("clock.rtc", |p| {
+                Ok(Box::new(Rtc::from_ticks(param_in(
+                    p,
+                    "clock.rtc",
+                    "ticks_per_second",
+                    1..=usize::MAX,
+                )?)))
+            }),
+            ("timer", |p| {
+                Ok(Box::new(TimerN::new(param_in(
+                    p,
+                    "timer",
+                    "n_way",
+                    1..=MAX_WIDTH,
+                )?)))
+            }),
+            ("pwm", |p| {
+                Ok(Box::new(PwmN::new(param_in(
+                    p,
+                    "pwm",
+                    "n_way",
+                    1..=MAX_WIDTH,
+                )?)))
+            }),
+            ("beeper", |p| {
+                Ok(Box::new(BeeperN::new(param_in(
+                    p,
+                    "beeper",
+                    "n_way",
+                    1..=MAX_WIDTH,
+                )?)))
+            }),
+            ("mpu", |p| {
+                Ok(Box::new(MPU::new(
+                    param_in(p, "mpu", "n_way", 1..=MAX_WIDTH)?,
+                    param_in(p, "mpu", "regions", 1..=MAX_WIDTH)?,
+                )))
+            }),
+            ("video.led_matrix", |p| {
+                // both lines are decoded from the address of a pixel
+                let lines = |value: usize| value > 1 && value.is_power_of_two();
+                let width = param_where(p, "video.led_matrix", "width", |width| {
+                    lines(width) && width < 1 << MAX_ADDRESS_WIDTH
+                })?;
+                let height = param_where(p, "video.led_matrix", "height", |height| {
+                    lines(height) && width * height <= 1 << MAX_ADDRESS_WIDTH
+                })?;
+                Ok(Box::new(LedMatrix::new(width, height)))
+            }),
+            ("video.vga_640x480", |_| {
+                Ok(Box::new(VgaTiming::new(VGA_640X480)))
+            }),
+        ];
+        for (name, constructor) in builtins {
+            registry
+                .register(name, 1, constructor)
+                .expect("builtin names are unique");
+        }
+        registry
+    }
+
+    /// Register a constructor under the name and the version.
+    pub fn register(
+        &mut self,
+        name: &str,
+        version: u32,
+        constructor: Constructor,
+    ) -> Result<(), RegistryError> {
+        if name.is_empty() || name.contains(VERSION_SEPARATOR) {
+            return Err(RegistryError::InvalidId(name.to_string()));
+        }
+        let versions = self.constructors.entry(name.to_string()).or_default();
+        if versions.contains_key(&version) {
+            return Err(RegistryError::Duplicate {
+                name: name.to_string(),
+                version,
+            });
+        }
+        versions.insert(version, constructor);
+        Ok(())
+    }
+
+    /// Get the constructor of an id, `name` for the latest version or `name@version`.
+    pub fn get(&self, id: &str) -> Result<Constructor, RegistryError> {
+        let (name, version) = match id.split_once(VERSION_SEPARATOR) {
+            Some((name, version)) => (
+                name,
+                Some(
+                    version
+                        .parse::<u32>()
+                        .map_err(|_| RegistryError::InvalidId(id.to_string()))?,
+                ),
+            ),
+            None => (id, None),
+        };
+        let versions = self
+            .constructors
+            .get(name)
+            .ok_or_else(|| RegistryError::UnknownComponent(name.to_string()))?;
+        let constructor = match version {
+            Some(version) => versions.get(&version),
+            None => versions.values().next_back(),
+        };
+        constructor
+            .copied()
+            .ok_or_else(|| RegistryError::UnknownVersion {
+                name: name.to_string(),
+                version: version.unwrap_or_default(),
+            })
+    }
+
+    /// Create a component by its id with the parameters.
+    pub fn create(&self, id: &str, params: &Params) -> Result<Box<dyn Component>, RegistryError> {
+        (self.get(id)?)(params)
+    }
+
+    /// Create a component from its serialized form.
+    pub fn build(&self, spec: &ComponentSpec) -> Result<Box<dyn Component>, RegistryError> {
+        self.create(&spec.id, &spec.params)
+    }
+
+    /// Install the constructors of a library.
+    pub fn install(&mut self, library: &dyn ComponentLibrary) -> Result<(), RegistryError> {
+        library.register(self)
+    }
+
+    /// Get the versions of a name, from the oldest.
+    pub fn versions(&self, name: &str) -> Vec<u32> {
+        self.constructors
+            .get(name)
+            .map(|versions| versions.keys().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Iterate the registered names in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.constructors.keys().map(|name| name.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.constructors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.constructors.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::Potential;
+    use rstest::rstest;
+
+    fn params(pairs: &[(&str, usize)]) -> Params {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), *value))
+            .collect()
+    }
+
+    #[rstest]
+    #[case("gate.and3", &[], (3, 1))]
+    #[case("gate.and", &[("n_way", 5)], (5, 1))]
+    #[case("gate.threshold", &[("n_way", 4), ("k", 2)], (4, 1))]
+    #[case("adder.full", &[], (3, 2))]
+    #[case("adder.ripple@1", &[("n_way", 4)], (9, 5))]
+    #[case("adder.add_sub", &[("n_way", 8)], (17, 10))]
+    #[case("subtractor.ripple", &[("n_way", 4)], (9, 5))]
+    #[case("multiplier.array", &[("width", 8)], (16, 16))]
+    #[case("alu", &[("width", 8)], (19, 14))]
+    #[case("comparator.n", &[("width", 4)], (8, 3))]
+    #[case("fanout", &[("n_way", 3)], (1, 3))]
+    #[case("decoder.n", &[("width", 3)], (3, 8))]
+    #[case("demux.n", &[("width", 2)], (3, 4))]
+    #[case("register.n", &[("width", 8)], (10, 8))]
+    #[case("shift_register.n", &[("width", 8)], (12, 9))]
+    #[case("counter.bcd", &[], (4, 5))]
+    #[case("counter.n", &[("width", 8)], (3, 8))]
+    #[case("counter.stepper", &[("steps", 6)], (2, 6))]
+    fn test_registry_create(
+        #[case] id: &str,
+        #[case] pairs: &[(&str, usize)],
+        #[case] pins: (usize, usize),
+    ) {
+        let registry = ComponentRegistry::builtin();
+        let component = registry.create(id, &params(pairs)).unwrap();
+        assert_eq!(component.get_pin_count(), pins);
+    }
+
+    #[test]
+    fn test_registry_component_works() {
+        let registry = ComponentRegistry::builtin();
+        let mut adder = registry.create("adder.half", &Params::new()).unwrap();
+        adder.input(&[true, true]);
+        assert_eq!(adder.output(), vec![false, true]);
+    }
+
+    #[rstest]
+    #[case("gate.nope", RegistryError::UnknownComponent("gate.nope".to_string()))]
+    #[case("adder.half@2", RegistryError::UnknownVersion { name: "adder.half".to_string(), version: 2 })]
+    #[case("adder.half@x", RegistryError::InvalidId("adder.half@x".to_string()))]
+    #[case("gate.or", RegistryError::MissingParam { name: "gate.or".to_string(), param: "n_way".to_string() })]
+    fn test_registry_errors(#[case] id: &str, #[case] expected: RegistryError) {
+        let registry = ComponentRegistry::builtin();
+        assert_eq!(registry.create(id, &Params::new()).err(), Some(expected));
+    }
+
+    #[rstest]
+    #[case("gate.and", &[("n_way", 0)], "n_way", 0)]
+    #[case("gate.threshold", &[("n_way", 3), ("k", 4)], "k", 4)]
+    #[case("alu", &[("width", 1)], "width", 1)]
+    #[case("adder.ripple", &[("n_way", 0)], "n_way", 0)]
+    #[case("decoder.n", &[("width", 64)], "width", 64)]
+    #[case("counter.stepper", &[("steps", 1)], "steps", 1)]
+    #[case("video.led_matrix", &[("width", 6), ("height", 4)], "width", 6)]
+    #[case("video.led_matrix", &[("width", 256), ("height", 512)], "height", 512)]
+    fn test_registry_invalid_param(
+        #[case] id: &str,
+        #[case] pairs: &[(&str, usize)],
+        #[case] param: &str,
+        #[case] value: usize,
+    ) {
+        let registry = ComponentRegistry::builtin();
+        assert_eq!(
+            registry.create(id, &params(pairs)).err(),
+            Some(RegistryError::InvalidParam {
+                name: id.to_string(),
+                param: param.to_string(),
+                value,
+            })
+        );
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(usize::MAX)]
+    fn test_registry_builtin_does_not_panic(#[case] value: usize) {
+        let registry = ComponentRegistry::builtin();
+        let names = [
+            "n_way",
+            "width",
+            "height",
+            "k",
+            "threshold",
+            "gate_cycles",
+            "steps",
+            "ticks_per_second",
+            "regions",
+        ];
+        let params = names.iter().map(|name| (name.to_string(), value)).collect();
+        for name in registry.names() {
+            // an invalid parameter is an error instead of a panic
+            let _ = registry.create(name, &params);
+        }
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("gate.and@2")]
+    fn test_registry_register_invalid_name(#[case] name: &str) {
+        let mut registry = ComponentRegistry::default();
+        assert_eq!(
+            registry.register(name, 1, |_| Ok(Box::new(ANDGate3::default()))),
+            Err(RegistryError::InvalidId(name.to_string()))
+        );
+        assert!(registry.is_empty());
+    }
+
+    #[test]
+    fn test_registry_versions() {
+        let mut registry = ComponentRegistry::default();
+        assert!(registry.is_empty());
+        registry
+            .register("gate.and", 1, |_| Ok(Box::new(ANDGate3::default())))
+            .unwrap();
+        registry
+            .register("gate.and", 2, |p| {
+                Ok(Box::new(ANDGateN::new(param(p, "gate.and", "n_way")?)))
+            })
+            .unwrap();
+        assert_eq!(
+            registry.register("gate.and", 2, |_| Ok(Box::new(ORGate3::default()))),
+            Err(RegistryError::Duplicate {
+                name: "gate.and".to_string(),
+                version: 2
+            })
+        );
+        assert_eq!(registry.versions("gate.and"), vec![1, 2]);
+        assert_eq!(registry.names().collect::<Vec<_>>(), vec!["gate.and"]);
+        let n_way = params(&[("n_way", 6)]);
+        assert_eq!(
+            registry.create("gate.and", &n_way).unwrap().get_pin_count(),
+            (6, 1)
+        );
+        assert_eq!(
+            registry
+                .create("gate.and@1", &n_way)
+                .unwrap()
+                .get_pin_count(),
+            (3, 1)
+        );
+    }
+
+    /// a component of a downstream library, which inverts its input.
+    #[derive(Debug, Default)]
+    struct Inverter {
+        input: bool,
+    }
+
+    impl Component for Inverter {
+        fn get_pin_output(&self, _position: usize) -> Potential {
+            !self.input
+        }
+        fn set_pin_input(&mut self, _position: usize, value: &Potential) {
+            self.input = *value;
+        }
+        fn update_state(&mut self) {}
+        fn get_pin_count(&self) -> (usize, usize) {
+            (1, 1)
+        }
+    }
+
+    struct TestLibrary;
+
+    impl ComponentLibrary for TestLibrary {
+        fn register(&self, registry: &mut ComponentRegistry) -> Result<(), RegistryError> {
+            registry.register("test.inverter", 1, |_| Ok(Box::new(Inverter::default())))
+        }
+    }
+
+    #[test]
+    fn test_registry_install() {
+        let mut registry = ComponentRegistry::builtin();
+        registry.install(&TestLibrary).unwrap();
+        let spec: ComponentSpec = "test.inverter@1".parse().unwrap();
+        let mut inverter = registry.build(&spec).unwrap();
+        inverter.input(&[false]);
+        assert_eq!(inverter.output(), vec![true]);
+        // a library is installed once
+        assert!(registry.install(&TestLibrary).is_err());
+    }
+
+    #[rstest]
+    #[case("adder.ripple@1 n_way=8", "adder.ripple@1", &[("n_way", 8)])]
+    #[case("gate.threshold  n_way=4 k=2", "gate.threshold", &[("k", 2), ("n_way", 4)])]
+    #[case("counter.bcd", "counter.bcd", &[])]
+    fn test_component_spec(#[case] text: &str, #[case] id: &str, #[case] pairs: &[(&str, usize)]) {
+        let spec: ComponentSpec = text.parse().unwrap();
+        assert_eq!(spec.id, id);
+        assert_eq!(spec.params, params(pairs));
+        // the parameters are written sorted by name
+        assert_eq!(spec.to_string().parse::<ComponentSpec>().unwrap(), spec);
+        assert!(ComponentRegistry::builtin().build(&spec).is_ok());
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("adder.ripple n_way")]
+    #[case("adder.ripple n_way=x")]
+    #[case("adder.ripple =8")]
+    #[case("adder.ripple n_way=8 n_way=4")]
+    fn test_component_spec_invalid(#[case] text: &str) {
+        assert_eq!(
+            text.parse::<ComponentSpec>(),
+            Err(RegistryError::InvalidSpec(text.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_registry_builtin_names() {
+        let registry = ComponentRegistry::builtin();
+        assert_eq!(registry.len(), 46);
+        assert!(
+            registry
+                .names()
+                .all(|name| registry.versions(name) == vec![1])
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use std::{fs, io, path::Path};
+
+use crate::{
+    circuit::{Potential, Wire},
+    component::{Component, big_gates::ORGateN, decoder::DecoderN, fidelity::Fidelity},
+};
+
+/// a read-only memory of bytes in circuit.
+/// the input is n bits, and the output is 8 bits, where 2^n is the size of the image
+/// rounded up to a power of 2.
+///
+/// the rom is a diode matrix: the address is decoded into word lines, and every
+/// data bit is the OR of the word lines of the bytes which have that bit set.
+/// the bytes after the image read as 0.
+///
+/// a rom of [`Fidelity::Behavioral`] reads the image directly, without the decoder
+/// and the gates of the matrix.
+///
+/// ```ascii
+///                 ┌───────────┐
+/// address─────────┤    ROM    ├─────data
+///                 └───────────┘
+/// ```
+///
+/// # input
+/// the address (the first bit is the lowest bit).
+///
+/// # output
+/// the byte at the address (the first bit is the lowest bit).
+#[derive(Debug, Clone)]
+pub struct Rom {
+    image: Vec<u8>,
+    fidelity: Fidelity,
+    address: Vec<Wire>,
+    output: [Wire; 8],
+    decoder: Option<DecoderN>,
+    // the word lines of the bytes which have the bit set
+    bit_lines: Vec<Vec<usize>>,
+    bit_gates: Vec<ORGateN>,
+}
+
+impl Rom {
+    pub fn new(image: &[u8]) -> Self {
+        Self::with_fidelity(image, Fidelity::Gate)
+    }
+
+    /// Create a rom of the image, simulated at the fidelity.
+    pub fn with_fidelity(image: &[u8], fidelity: Fidelity) -> Self {
+        assert!(!image.is_empty(), "image must not be empty");
+        let width = (usize::BITS - (image.len() - 1).leading_zeros()).max(1) as usize;
+        if fidelity == Fidelity::Behavioral {
+            return Self {
+                image: image.to_vec(),
+                fidelity,
+                address: vec![Wire::default(); width],
+                output: [Wire::default(); 8],
+                decoder: None,
+                bit_lines: Vec::new(),
+                bit_gates: Vec::new(),
+            };
+        }
+        let bit_lines: Vec<Vec<usize>> = (0..8)
+            .map(|bit| {
+                image
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, byte)| (*byte >> bit) & 1 == 1)
+                    .map(|(address, _)| address)
+                    .collect()
+            })
+            .collect();
+        let bit_gates = bit_lines
+            .iter()
+            .map(|lines| ORGateN::new(lines.len().max(1)))
+            .collect();
+        Self {
+            image: image.to_vec(),
+            fidelity,
+            address: vec![Wire::default(); width],
+            output: [Wire::default(); 8],
+            decoder: Some(DecoderN::new(width)),
+            bit_lines,
+            bit_gates,
+        }
+    }
+
+    /// Load the image of the rom from a host file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let image = fs::read(path)?;
+        if image.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "image must not be empty",
+            ));
+        }
+        Ok(Self::new(&image))
+    }
+
+    /// Get the image of the rom.
+    pub fn image(&self) -> &[u8] {
+        &self.image
+    }
+}
+
+impl Component for Rom {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.address.len(), 8)
+    }
+    fn fidelity(&self) -> Fidelity {
+        self.fidelity
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.address[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+    fn update_state(&mut self) {
+        let Some(decoder) = self.decoder.as_mut() else {
+            let address = self
+                .address
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (i, wire)| acc | (wire.output() as usize) << i);
+            let byte = self.image.get(address).copied().unwrap_or(0);
+            for bit in 0..8 {
+                self.output[bit].input(&((byte >> bit) & 1 == 1));
+            }
+            return;
+        };
+        for (i, wire) in self.address.iter().enumerate() {
+            decoder.set_pin_input(i, &wire.output());
+        }
+        decoder.update_state();
+        for bit in 0..8 {
+            let gate = &mut self.bit_gates[bit];
+            if self.bit_lines[bit].is_empty() {
+                gate.set_pin_input(0, &false);
+            }
+            for (i, line) in self.bit_lines[bit].iter().enumerate() {
+                gate.set_pin_input(i, &decoder.get_pin_output(*line));
+            }
+            gate.update_state();
+            self.output[bit].input(&gate.get_pin_output(0));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn bits(value: usize, width: usize) -> Vec<Potential> {
+        (0..width).map(|i| (value >> i) & 1 == 1).collect()
+    }
+
+    fn byte(output: &[Potential]) -> u8 {
+        output
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (i, bit)| acc | ((*bit as u8) << i))
+    }
+
+    #[rstest]
+    #[case(vec![0xa5], 1)]
+    #[case(vec![1, 2], 1)]
+    #[case(vec![1, 2, 3], 2)]
+    #[case(vec![0; 256], 8)]
+    #[case(vec![0; 257], 9)]
+    fn test_rom_pin_count(#[case] image: Vec<u8>, #[case] width: usize) {
+        let rom = Rom::new(&image);
+        assert_eq!(rom.get_pin_count(), (width, 8));
+    }
+
+    #[test]
+    fn test_rom_read() {
+        let image: Vec<u8> = vec![0x00, 0xff, 0x5a, 0x81, 0x10];
+        let mut rom = Rom::new(&image);
+        for (address, expected) in image.iter().enumerate() {
+            rom.input(&bits(address, 3));
+            assert_eq!(byte(&rom.output()), *expected);
+        }
+        // the bytes after the image read as 0
+        for address in 5..8 {
+            rom.input(&bits(address, 3));
+            assert_eq!(byte(&rom.output()), 0);
+        }
+    }
+
+    #[test]
+    fn test_rom_behavioral() {
+        let image: Vec<u8> = (0..300).map(|i| (i * 7) as u8).collect();
+        let mut gates = Rom::new(&image);
+        let mut fast = Rom::with_fidelity(&image, Fidelity::Behavioral);
+        assert_eq!(fast.fidelity(), Fidelity::Behavioral);
+        assert_eq!(gates.get_pin_count(), fast.get_pin_count());
+        for address in 0..512 {
+            gates.input(&bits(address, 9));
+            fast.input(&bits(address, 9));
+            assert_eq!(gates.output(), fast.output());
+        }
+    }
+
+    #[test]
+    fn test_rom_from_file() {
+        let path =
+            std::env::temp_dir().join(format!("simulation-computer-rom-{}", std::process::id()));
+        std::fs::write(&path, [0xde, 0xad, 0xbe, 0xef]).unwrap();
+        let mut rom = Rom::from_file(&path).unwrap();
+        assert_eq!(rom.image(), &[0xde, 0xad, 0xbe, 0xef]);
+        rom.input(&bits(3, 2));
+        assert_eq!(byte(&rom.output()), 0xef);
+        std::fs::write(&path, []).unwrap();
+        assert!(Rom::from_file(&path).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+}
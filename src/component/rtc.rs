@@ -0,0 +1,250 @@
+use std::time::Instant;
+
+use crate::{
+    circuit::{ANDGate, NOTGate, Potential, Wire, init},
+    component::{Component, counter::Incrementer, edge::EdgeDetector},
+};
+
+/// the width of the seconds register of [`Rtc`].
+pub const RTC_SECONDS_WIDTH: usize = 32;
+
+/// the prescaler which divides the simulated ticks into seconds.
+#[derive(Debug, Clone)]
+struct Prescaler {
+    ticks_per_second: usize,
+    count: Vec<Wire>,
+    clock_edge: EdgeDetector,
+    incrementer: Incrementer,
+    not_reached: NOTGate,
+    // not reached and not reset
+    keep: ANDGate,
+}
+
+impl Prescaler {
+    fn new(ticks_per_second: usize) -> Self {
+        let width = (usize::BITS - ticks_per_second.leading_zeros()) as usize;
+        Self {
+            ticks_per_second,
+            count: init::power_up(width),
+            clock_edge: EdgeDetector::default(),
+            incrementer: Incrementer::new(width),
+            not_reached: NOTGate::default(),
+            keep: ANDGate::default(),
+        }
+    }
+
+    /// Sample the tick, return whether a second is elapsed at the rising edge,
+    /// or `None` without a rising edge. the count is cleared while `not_reset` is low.
+    fn tick(&mut self, tick: &Potential, not_reset: &Potential) -> Option<Potential> {
+        self.clock_edge.input(&[*tick]);
+        // count + 1 == ticks_per_second
+        self.incrementer.increment(&self.count, &true);
+        let reached = self.incrementer.reaches(self.ticks_per_second);
+        self.not_reached.input(&reached);
+        self.keep.input(&self.not_reached.output(), not_reset);
+        let count = self.incrementer.next(&self.keep.output());
+        if !self.clock_edge.is_rising() {
+            return None;
+        }
+        for (wire, bit) in self.count.iter_mut().zip(count) {
+            wire.input(&bit);
+        }
+        Some(reached)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Source {
+    Ticks(Box<Prescaler>),
+    Host { start: Instant, ratio: f64 },
+}
+
+/// a real-time clock peripheral in circuit.
+/// the input is 2 bits, and the output is 32 bits.
+///
+/// the clock counts the seconds since it is created, either from the simulated ticks
+/// or from the wall-clock time of the host.
+///
+/// ```ascii
+///          ┌─────────────────┐
+/// Tick─────┤                 │
+///          │       RTC       ├─────Seconds
+/// Reset────┤                 │
+///          └─────────────────┘
+/// ```
+///
+/// # input
+/// the first bit is the tick, which is ignored when the clock tracks the host time.
+/// the second bit is reset, which clears the seconds and the prescaler on the rising
+/// edge of the tick, or restarts the host time while it is high.
+///
+/// # output
+/// the seconds register (the first bit is the lowest bit), which wraps around
+/// after 2^32 seconds.
+#[derive(Debug, Clone)]
+pub struct Rtc {
+    input: [Wire; 2],
+    seconds: Vec<Wire>,
+    source: Source,
+    incrementer: Incrementer,
+    not_reset: NOTGate,
+}
+
+impl Rtc {
+    /// Create a clock which increases the seconds after `ticks_per_second` rising edges of the tick.
+    pub fn from_ticks(ticks_per_second: usize) -> Self {
+        assert!(
+            ticks_per_second > 0,
+            "ticks_per_second must be greater than 0"
+        );
+        Self::with_source(Source::Ticks(Box::new(Prescaler::new(ticks_per_second))))
+    }
+
+    /// Create a clock which tracks the host time, `ratio` simulated seconds pass in a host second.
+    pub fn from_host(ratio: f64) -> Self {
+        assert!(ratio > 0.0, "ratio must be greater than 0");
+        Self::with_source(Source::Host {
+            start: Instant::now(),
+            ratio,
+        })
+    }
+
+    fn with_source(source: Source) -> Self {
+        Self {
+            input: [Wire::default(); 2],
+            seconds: init::power_up(RTC_SECONDS_WIDTH),
+            source,
+            incrementer: Incrementer::new(RTC_SECONDS_WIDTH),
+            not_reset: NOTGate::default(),
+        }
+    }
+}
+
+impl Component for Rtc {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (2, RTC_SECONDS_WIDTH)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.seconds[position].output()
+    }
+    fn update_state(&mut self) {
+        self.not_reset.input(&self.input[1].output());
+        match &mut self.source {
+            Source::Ticks(prescaler) => {
+                let tick = self.input[0].output();
+                if let Some(elapsed) = prescaler.tick(&tick, &self.not_reset.output()) {
+                    self.incrementer.increment(&self.seconds, &elapsed);
+                    let next = self.incrementer.next(&self.not_reset.output());
+                    for (wire, bit) in self.seconds.iter_mut().zip(next) {
+                        wire.input(&bit);
+                    }
+                }
+            }
+            Source::Host { start, ratio } => {
+                if !self.not_reset.output() {
+                    *start = Instant::now();
+                }
+                let elapsed = (start.elapsed().as_secs_f64() * *ratio) as u64;
+                for (i, wire) in self.seconds.iter_mut().enumerate() {
+                    wire.input(&((elapsed >> i) & 1 == 1));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn seconds(rtc: &Rtc) -> u64 {
+        rtc.output()
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (i, bit)| acc | ((*bit as u64) << i))
+    }
+
+    /// create a clock from the ticks, and clear it by a tick with reset.
+    fn reset(ticks_per_second: usize) -> Rtc {
+        let mut rtc = Rtc::from_ticks(ticks_per_second);
+        rtc.input(&[true, true]);
+        rtc.input(&[false, false]);
+        rtc
+    }
+
+    #[test]
+    fn test_rtc_default() {
+        let rtc = reset(4);
+        assert_eq!(rtc.get_pin_count(), (2, 32));
+        assert_eq!(seconds(&rtc), 0);
+    }
+
+    #[test]
+    fn test_rtc_from_ticks() {
+        let mut rtc = reset(3);
+        for tick in 1..=10 {
+            rtc.input(&[true, false]);
+            rtc.input(&[false, false]);
+            assert_eq!(seconds(&rtc), tick / 3);
+        }
+    }
+
+    #[test]
+    fn test_rtc_from_ticks_one_per_second() {
+        let mut rtc = reset(1);
+        for tick in 1..=5 {
+            rtc.input(&[true, false]);
+            // a level without an edge does not count
+            rtc.input(&[true, false]);
+            rtc.input(&[false, false]);
+            assert_eq!(seconds(&rtc), tick);
+        }
+    }
+
+    #[test]
+    fn test_rtc_reset() {
+        let mut rtc = reset(2);
+        for _ in 0..5 {
+            rtc.input(&[true, false]);
+            rtc.input(&[false, false]);
+        }
+        assert_eq!(seconds(&rtc), 2);
+        // the half elapsed second is cleared as well
+        rtc.input(&[true, true]);
+        rtc.input(&[false, false]);
+        assert_eq!(seconds(&rtc), 0);
+        rtc.input(&[true, false]);
+        rtc.input(&[false, false]);
+        assert_eq!(seconds(&rtc), 0);
+        rtc.input(&[true, false]);
+        assert_eq!(seconds(&rtc), 1);
+    }
+
+    #[test]
+    fn test_rtc_from_host() {
+        let mut rtc = Rtc::from_host(10.0);
+        if let Source::Host { start, .. } = &mut rtc.source {
+            *start = Instant::now() - Duration::from_millis(1500);
+        }
+        rtc.input(&[false, false]);
+        assert!(seconds(&rtc) >= 15);
+        assert!(seconds(&rtc) < 60);
+        // reset restarts the host time
+        rtc.input(&[false, true]);
+        assert_eq!(seconds(&rtc), 0);
+    }
+}
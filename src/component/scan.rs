@@ -0,0 +1,243 @@
+use crate::{
+    circuit::{Potential, Wire, init},
+    component::{Component, edge::EdgeDetector},
+};
+
+/// a n-bit register with a scan path in circuit.
+/// the input is n+3 bits, and the output is n+1 bits.
+///
+/// on the rising edge of the clock, the register loads the data while scan enable is
+/// low, and shifts a bit from scan in towards scan out while scan enable is high, so
+/// the state of a design can be read and written without its functional paths.
+///
+/// ```ascii
+///                ┌──────────┐
+/// clock──────────┤          ├──────q0..qn-1
+/// scan enable────┤   Scan   │
+/// scan in────────┤ Register ├──────scan out
+/// d0..dn-1───────┤          │
+///                └──────────┘
+/// ```
+///
+/// # input
+/// the clock, the scan enable, the scan in and the n bits of data.
+///
+/// # output
+/// the n bits of the register and the scan out, which is the last bit.
+#[derive(Debug, Clone)]
+pub struct ScanRegisterN {
+    n_way: usize,
+    input: Vec<Wire>,
+    register: Vec<Wire>,
+    clock_edge: EdgeDetector,
+}
+
+impl ScanRegisterN {
+    pub fn new(n_way: usize) -> Self {
+        assert!(n_way > 0, "n_way must be greater than 0");
+        Self {
+            n_way,
+            input: vec![Wire::default(); n_way + 3],
+            register: init::power_up(n_way),
+            clock_edge: EdgeDetector::default(),
+        }
+    }
+
+    /// Get the width of the register.
+    pub fn width(&self) -> usize {
+        self.n_way
+    }
+}
+
+impl Component for ScanRegisterN {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.n_way + 3, self.n_way + 1)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        if position == self.n_way {
+            return self.register[self.n_way - 1].output();
+        }
+        self.register[position].output()
+    }
+    fn update_state(&mut self) {
+        self.clock_edge.input(&[self.input[0].output()]);
+        if !self.clock_edge.is_rising() {
+            return;
+        }
+        if self.input[1].output() {
+            for i in (1..self.n_way).rev() {
+                let bit = self.register[i - 1].output();
+                self.register[i].input(&bit);
+            }
+            self.register[0].input(&self.input[2].output());
+        } else {
+            for i in 0..self.n_way {
+                let bit = self.input[3 + i].output();
+                self.register[i].input(&bit);
+            }
+        }
+    }
+}
+
+/// a host-side scan chain, which stitches the scan out of a [`ScanRegisterN`] to the
+/// scan in of the next one, to shift the state of the registers in and out.
+///
+/// the state is in the order of the chain, the bit 0 of the first register first.
+///
+/// # Examples
+///
+/// ```
+/// use simulation_computer_rust::component::scan::{ScanChain, ScanRegisterN};
+///
+/// let mut chain = ScanChain::new(vec![ScanRegisterN::new(2), ScanRegisterN::new(3)]);
+/// chain.load(&[true, false, false, true, true]);
+/// assert_eq!(chain.dump(), vec![true, false, false, true, true]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScanChain {
+    registers: Vec<ScanRegisterN>,
+}
+
+impl ScanChain {
+    pub fn new(registers: Vec<ScanRegisterN>) -> Self {
+        assert!(!registers.is_empty(), "registers must not be empty");
+        Self { registers }
+    }
+
+    /// Get the bits of the chain.
+    pub fn len(&self) -> usize {
+        self.registers.iter().map(ScanRegisterN::width).sum()
+    }
+
+    /// Always false, a chain has a register at least.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn registers(&self) -> &[ScanRegisterN] {
+        &self.registers
+    }
+
+    pub fn registers_mut(&mut self) -> &mut [ScanRegisterN] {
+        &mut self.registers
+    }
+
+    fn scan_out(&self) -> Potential {
+        let last = self.registers.last().unwrap();
+        last.get_pin_output(last.width())
+    }
+
+    /// Shift the bits into the chain with a clock cycle each, and get the bits which
+    /// come out at the end of the chain. the scan enable is low after the shift.
+    pub fn shift(&mut self, bits: &[Potential]) -> Vec<Potential> {
+        let mut out = Vec::with_capacity(bits.len());
+        for bit in bits {
+            out.push(self.scan_out());
+            // every register sees the scan out of the one before the clock edge
+            let mut scan_in = *bit;
+            for register in self.registers.iter_mut() {
+                let next = register.get_pin_output(register.width());
+                register.set_pin_input(1, &true);
+                register.set_pin_input(2, &scan_in);
+                register.set_pin_input(0, &false);
+                register.update_state();
+                register.set_pin_input(0, &true);
+                register.update_state();
+                scan_in = next;
+            }
+        }
+        for register in self.registers.iter_mut() {
+            register.set_pin_input(1, &false);
+        }
+        out
+    }
+
+    /// Read the state of the chain, which is shifted back in and does not change.
+    pub fn dump(&mut self) -> Vec<Potential> {
+        let mut state = Vec::with_capacity(self.len());
+        for _ in 0..self.len() {
+            let bit = self.scan_out();
+            self.shift(&[bit]);
+            state.push(bit);
+        }
+        state.reverse();
+        state
+    }
+
+    /// Write the state of the chain.
+    pub fn load(&mut self, state: &[Potential]) {
+        assert!(
+            state.len() == self.len(),
+            "state must be {} bits",
+            self.len()
+        );
+        let bits: Vec<Potential> = state.iter().rev().copied().collect();
+        self.shift(&bits);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(register: &mut ScanRegisterN, scan: Option<Potential>, data: &[Potential]) {
+        for clock in [false, true] {
+            let mut input = vec![clock, scan.is_some(), scan.unwrap_or_default()];
+            input.extend(data);
+            register.input(&input);
+        }
+    }
+
+    #[test]
+    fn test_scan_register() {
+        let mut register = ScanRegisterN::new(3);
+        tick(&mut register, None, &[true, false, true]);
+        assert_eq!(register.output(), vec![true, false, true, true]);
+        // the data is ignored while scanning
+        tick(&mut register, Some(false), &[true, true, true]);
+        assert_eq!(register.output(), vec![false, true, false, false]);
+        tick(&mut register, Some(true), &[true, true, true]);
+        assert_eq!(register.output(), vec![true, false, true, true]);
+    }
+
+    #[test]
+    fn test_scan_chain() {
+        let mut chain = ScanChain::new(vec![ScanRegisterN::new(3), ScanRegisterN::new(2)]);
+        assert_eq!(chain.len(), 5);
+        let state = [true, true, false, false, true];
+        chain.load(&state);
+        assert_eq!(
+            chain.registers()[0].output(),
+            vec![true, true, false, false]
+        );
+        assert_eq!(chain.registers()[1].output(), vec![false, true, true]);
+        assert_eq!(chain.dump(), state.to_vec());
+        // the state comes out last bit first
+        assert_eq!(
+            chain.shift(&[false; 5]),
+            vec![true, false, false, true, true]
+        );
+        assert_eq!(chain.dump(), vec![false; 5]);
+    }
+
+    #[test]
+    fn test_scan_chain_capture() {
+        let mut chain = ScanChain::new(vec![ScanRegisterN::new(2)]);
+        let register = &mut chain.registers_mut()[0];
+        tick(register, None, &[false, true]);
+        assert_eq!(chain.dump(), vec![false, true]);
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{
+    circuit::{NOTGate, ORGate, Potential, Wire},
+    component::{Component, adder::RippleCarryAdderN, decoder::DecoderN, edge::EdgeDetector},
+};
+
+/// the coil patterns of a full step sequence with two coils on, clockwise.
+pub const STEPPER_SEQUENCE: [[Potential; 4]; 4] = [
+    [true, false, false, true],
+    [true, true, false, false],
+    [false, true, true, false],
+    [false, false, true, true],
+];
+
+/// a sequencer of a 4-phase stepper motor in circuit.
+/// the input is 3 bits, and the output is 4 bits.
+///
+/// the sequencer keeps the phase of the motor in a 2 bits register, which moves a
+/// step on the rising edge of the clock while step is high. the coils of the phase
+/// and of the phase before are on, see [`STEPPER_SEQUENCE`].
+///
+/// ```ascii
+///              ┌─────────────┐
+/// clock────────┤             ├──────coil a
+/// step─────────┤   Stepper   ├──────coil b
+/// direction────┤  Sequencer  ├──────coil c
+///              │             ├──────coil d
+///              └─────────────┘
+/// ```
+///
+/// # input
+/// the clock, the step and the direction, which is high for clockwise.
+///
+/// # output
+/// the 4 coils.
+#[derive(Debug, Clone)]
+pub struct StepperSequencer {
+    input: [Wire; 3],
+    output: [Wire; 4],
+    phase: [Wire; 2],
+    clock_edge: EdgeDetector,
+    not_direction: NOTGate,
+    // phase + 1 clockwise, phase + 3 counterclockwise
+    next: RippleCarryAdderN,
+    decoder: DecoderN,
+    coils: [ORGate; 4],
+}
+
+impl Default for StepperSequencer {
+    fn default() -> Self {
+        let mut sequencer = Self {
+            input: [Wire::default(); 3],
+            output: [Wire::default(); 4],
+            phase: [Wire::default(); 2],
+            clock_edge: EdgeDetector::default(),
+            not_direction: NOTGate::default(),
+            next: RippleCarryAdderN::new(2),
+            decoder: DecoderN::new(2),
+            coils: Default::default(),
+        };
+        sequencer.drive_coils();
+        sequencer
+    }
+}
+
+impl StepperSequencer {
+    fn drive_coils(&mut self) {
+        self.decoder
+            .input(&[self.phase[0].output(), self.phase[1].output()]);
+        // the coil `i` is on in the phase `i` and the phase `i + 1`
+        for i in 0..4 {
+            self.coils[i].input(
+                &self.decoder.get_pin_output(i),
+                &self.decoder.get_pin_output((i + 1) % 4),
+            );
+            self.output[i].input(&self.coils[i].output());
+        }
+    }
+}
+
+impl Component for StepperSequencer {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (3, 4)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+    fn update_state(&mut self) {
+        self.clock_edge.input(&[self.input[0].output()]);
+        self.not_direction.input(&self.input[2].output());
+        self.next.input(&[
+            false,
+            self.phase[0].output(),
+            self.phase[1].output(),
+            true,
+            self.not_direction.output(),
+        ]);
+        if self.clock_edge.is_rising() && self.input[1].output() {
+            for i in 0..2 {
+                self.phase[i].input(&self.next.get_pin_output(i));
+            }
+        }
+        self.drive_coils();
+    }
+}
+
+/// the error of a coil pattern which is not a step from the last one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissedStep {
+    pub from: [Potential; 4],
+    pub to: [Potential; 4],
+}
+
+/// a host-side observer of the coils of a stepper motor, which checks that the
+/// patterns follow [`STEPPER_SEQUENCE`] and counts the position of the motor.
+#[derive(Debug, Default, Clone)]
+pub struct StepperObserver {
+    last: Option<usize>,
+    position: i64,
+}
+
+impl StepperObserver {
+    /// Observe the coils, a pattern may stay, or move a step either way.
+    ///
+    /// # Returns
+    /// the steps moved, or an error when the pattern is not in the sequence or skips
+    /// a step, which loses the position of a real motor.
+    pub fn observe(&mut self, coils: &[Potential]) -> Result<i64, MissedStep> {
+        let to: [Potential; 4] = coils.try_into().expect("coils must be 4 bits");
+        let index = STEPPER_SEQUENCE.iter().position(|pattern| *pattern == to);
+        let from = self.last.map_or([false; 4], |last| STEPPER_SEQUENCE[last]);
+        let Some(index) = index else {
+            return Err(MissedStep { from, to });
+        };
+        let Some(last) = self.last.replace(index) else {
+            return Ok(0);
+        };
+        let moved = match (index + 4 - last) % 4 {
+            0 => 0,
+            1 => 1,
+            3 => -1,
+            _ => return Err(MissedStep { from, to }),
+        };
+        self.position += moved;
+        Ok(moved)
+    }
+
+    /// Get the steps moved clockwise since the first pattern.
+    pub fn position(&self) -> i64 {
+        self.position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn tick(sequencer: &mut StepperSequencer, step: bool, clockwise: bool) -> Vec<Potential> {
+        sequencer.input(&[false, step, clockwise]);
+        sequencer.input(&[true, step, clockwise]);
+        sequencer.output()
+    }
+
+    #[test]
+    fn test_stepper_default() {
+        let sequencer = StepperSequencer::default();
+        assert_eq!(sequencer.output(), STEPPER_SEQUENCE[0].to_vec());
+    }
+
+    #[rstest]
+    #[case(true, [1, 2, 3, 0, 1])]
+    #[case(false, [3, 2, 1, 0, 3])]
+    fn test_stepper_sequence(#[case] clockwise: bool, #[case] phases: [usize; 5]) {
+        let mut sequencer = StepperSequencer::default();
+        for phase in phases {
+            assert_eq!(
+                tick(&mut sequencer, true, clockwise),
+                STEPPER_SEQUENCE[phase].to_vec()
+            );
+        }
+    }
+
+    #[test]
+    fn test_stepper_observer() {
+        let mut sequencer = StepperSequencer::default();
+        let mut observer = StepperObserver::default();
+        assert_eq!(observer.observe(&sequencer.output()), Ok(0));
+        let moves = [(true, true), (true, true), (false, true), (true, false)];
+        for _ in 0..3 {
+            for (step, clockwise) in moves {
+                observer
+                    .observe(&tick(&mut sequencer, step, clockwise))
+                    .unwrap();
+            }
+        }
+        assert_eq!(observer.position(), 3);
+    }
+
+    #[test]
+    fn test_stepper_observer_missed_step() {
+        let mut observer = StepperObserver::default();
+        observer.observe(&STEPPER_SEQUENCE[0]).unwrap();
+        assert_eq!(
+            observer.observe(&STEPPER_SEQUENCE[2]),
+            Err(MissedStep {
+                from: STEPPER_SEQUENCE[0],
+                to: STEPPER_SEQUENCE[2],
+            })
+        );
+        assert!(observer.observe(&[true; 4]).is_err());
+        assert_eq!(observer.position(), 0);
+    }
+}
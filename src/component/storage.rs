@@ -0,0 +1,381 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::Path,
+};
+
+use crate::{
+    circuit::{Potential, Wire},
+    component::{Component, edge::EdgeDetector},
+};
+
+/// the width of the sector address of [`BlockStorage`].
+pub const STORAGE_ADDRESS_WIDTH: usize = 16;
+
+/// the commands of [`BlockStorage`], encoded on the command pins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageCommand {
+    /// do nothing.
+    Idle = 0,
+    /// read the addressed sector into the buffer, and rewind the data port.
+    ReadSector = 1,
+    /// write the buffer into the addressed sector.
+    WriteSector = 2,
+    /// move the data port to the next byte of the buffer.
+    NextByte = 3,
+    /// store the data input into the buffer, and move to the next byte.
+    PutByte = 4,
+    /// rewind the data port.
+    Rewind = 5,
+}
+
+impl StorageCommand {
+    /// Get the potentials of the command pins, the first bit is the lowest bit.
+    pub fn to_bits(self) -> [Potential; 3] {
+        let code = self as usize;
+        [code & 1 == 1, (code >> 1) & 1 == 1, (code >> 2) & 1 == 1]
+    }
+
+    fn from_code(code: usize) -> Self {
+        match code {
+            1 => Self::ReadSector,
+            2 => Self::WriteSector,
+            3 => Self::NextByte,
+            4 => Self::PutByte,
+            5 => Self::Rewind,
+            _ => Self::Idle,
+        }
+    }
+}
+
+/// a block storage device backed by a host file.
+/// the input is 28 bits, and the output is 10 bits.
+///
+/// the device works on a sector buffer: a sector is read into the buffer or written
+/// from the buffer, and the buffer is transferred a byte at a time through the data
+/// port. the commands are executed on the rising edge of the clock.
+///
+/// ```ascii
+///            ┌───────────────┐
+/// clock──────┤               ├──────data out
+/// command────┤    Storage    ├──────ready
+/// data in────┤               ├──────error
+/// address────┤               │
+///            └───────────────┘
+/// ```
+///
+/// # input
+/// the first bit is the clock, the next 3 bits are the [`StorageCommand`], then 8 bits
+/// of data in and 16 bits of sector address (the first bit is the lowest bit).
+///
+/// # output
+/// the first 8 bits are the byte of the buffer at the data port, the next bit is high
+/// when the last command is done, and the last bit is high when the last command fails,
+/// e.g. the sector is out of range or the host file can not be accessed.
+///
+/// the buffer can also be moved as a whole with [`BlockStorage::dma_read`] and
+/// [`BlockStorage::dma_write`], which skip the data port.
+#[derive(Debug)]
+pub struct BlockStorage {
+    file: File,
+    sector_size: usize,
+    sectors: usize,
+    input: Vec<Wire>,
+    ready: Wire,
+    error: Wire,
+    buffer: Vec<u8>,
+    position: usize,
+    clock_edge: EdgeDetector,
+}
+
+impl BlockStorage {
+    /// Open the host file as a storage of `sectors` sectors of `sector_size` bytes,
+    /// the file is created and extended when it is too short.
+    pub fn open<P: AsRef<Path>>(path: P, sector_size: usize, sectors: usize) -> io::Result<Self> {
+        assert!(sector_size > 0, "sector_size must be greater than 0");
+        assert!(
+            sectors > 0 && sectors <= 1 << STORAGE_ADDRESS_WIDTH,
+            "sectors must be between 1 and {}",
+            1 << STORAGE_ADDRESS_WIDTH
+        );
+        let size = sector_size
+            .checked_mul(sectors)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "storage is too large"))?
+            as u64;
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        if file.metadata()?.len() < size {
+            file.set_len(size)?;
+        }
+        Ok(Self {
+            file,
+            sector_size,
+            sectors,
+            input: vec![Wire::default(); 4 + 8 + STORAGE_ADDRESS_WIDTH],
+            ready: Wire::default(),
+            error: Wire::default(),
+            buffer: vec![0; sector_size],
+            position: 0,
+            clock_edge: EdgeDetector::default(),
+        })
+    }
+
+    /// Get the bytes of a sector.
+    pub fn sector_size(&self) -> usize {
+        self.sector_size
+    }
+
+    /// Get the number of sectors.
+    pub fn sectors(&self) -> usize {
+        self.sectors
+    }
+
+    /// Read consecutive sectors from `sector` into `memory`, bypassing the data port.
+    pub fn dma_read(&mut self, sector: usize, memory: &mut [u8]) -> io::Result<()> {
+        self.seek(sector, memory.len())?;
+        self.file.read_exact(memory)
+    }
+
+    /// Write `memory` into consecutive sectors from `sector`, bypassing the data port.
+    pub fn dma_write(&mut self, sector: usize, memory: &[u8]) -> io::Result<()> {
+        self.seek(sector, memory.len())?;
+        self.file.write_all(memory)?;
+        self.file.flush()
+    }
+
+    fn seek(&mut self, sector: usize, len: usize) -> io::Result<()> {
+        // check the range before the offset, a large sector would overflow it
+        if sector >= self.sectors || len > (self.sectors - sector) * self.sector_size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("sector {} is out of range", sector),
+            ));
+        }
+        let offset = sector * self.sector_size;
+        self.file.seek(SeekFrom::Start(offset as u64))?;
+        Ok(())
+    }
+
+    fn value(wires: &[Wire]) -> usize {
+        wires
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (i, wire)| acc | ((wire.output() as usize) << i))
+    }
+
+    fn execute(&mut self, command: StorageCommand) -> io::Result<()> {
+        let sector = Self::value(&self.input[12..]);
+        match command {
+            StorageCommand::Idle => {}
+            StorageCommand::ReadSector => {
+                // a failed read keeps the buffer, a short file may have filled a part
+                let mut buffer = vec![0; self.sector_size];
+                self.dma_read(sector, &mut buffer)?;
+                self.buffer = buffer;
+                self.position = 0;
+            }
+            StorageCommand::WriteSector => {
+                let buffer = std::mem::take(&mut self.buffer);
+                let result = self.dma_write(sector, &buffer);
+                self.buffer = buffer;
+                result?;
+            }
+            StorageCommand::NextByte => {
+                self.position = (self.position + 1) % self.sector_size;
+            }
+            StorageCommand::PutByte => {
+                self.buffer[self.position] = Self::value(&self.input[4..12]) as u8;
+                self.position = (self.position + 1) % self.sector_size;
+            }
+            StorageCommand::Rewind => {
+                self.position = 0;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Component for BlockStorage {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (4 + 8 + STORAGE_ADDRESS_WIDTH, 10)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        match position {
+            8 => self.ready.output(),
+            9 => self.error.output(),
+            _ => (self.buffer[self.position] >> position) & 1 == 1,
+        }
+    }
+    fn update_state(&mut self) {
+        self.clock_edge.input(&[self.input[0].output()]);
+        if !self.clock_edge.is_rising() {
+            return;
+        }
+        let command = StorageCommand::from_code(Self::value(&self.input[1..4]));
+        let failed = self.execute(command).is_err();
+        self.ready.input(&!failed);
+        self.error.input(&failed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_file(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "simulation-computer-storage-{}-{}",
+            std::process::id(),
+            name
+        ));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn bits(value: usize, width: usize) -> Vec<Potential> {
+        (0..width).map(|i| (value >> i) & 1 == 1).collect()
+    }
+
+    /// run a full clock cycle with the command, and return the output.
+    fn tick(
+        storage: &mut BlockStorage,
+        command: StorageCommand,
+        data: u8,
+        sector: usize,
+    ) -> Vec<Potential> {
+        let mut input = vec![false];
+        input.extend(command.to_bits());
+        input.extend(bits(data as usize, 8));
+        input.extend(bits(sector, STORAGE_ADDRESS_WIDTH));
+        storage.input(&input);
+        input[0] = true;
+        storage.input(&input);
+        storage.output()
+    }
+
+    fn byte(output: &[Potential]) -> u8 {
+        output[0..8]
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (i, bit)| acc | ((*bit as u8) << i))
+    }
+
+    #[test]
+    fn test_storage_open() {
+        let path = temp_file("open");
+        let storage = BlockStorage::open(&path, 16, 4).unwrap();
+        assert_eq!(storage.get_pin_count(), (28, 10));
+        assert_eq!((storage.sector_size(), storage.sectors()), (16, 4));
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), 64);
+        assert_eq!(storage.output(), vec![false; 10]);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_storage_write_and_read_sector() {
+        let path = temp_file("sector");
+        let mut storage = BlockStorage::open(&path, 4, 4).unwrap();
+        for data in [0x12, 0x34, 0x56, 0x78] {
+            tick(&mut storage, StorageCommand::PutByte, data, 0);
+        }
+        let output = tick(&mut storage, StorageCommand::WriteSector, 0, 2);
+        assert_eq!(output[8..], [true, false]);
+        drop(storage);
+
+        // the data persists in the host file
+        let mut storage = BlockStorage::open(&path, 4, 4).unwrap();
+        let output = tick(&mut storage, StorageCommand::ReadSector, 0, 2);
+        assert_eq!(output[8..], [true, false]);
+        let mut data = vec![byte(&output)];
+        for _ in 0..3 {
+            data.push(byte(&tick(&mut storage, StorageCommand::NextByte, 0, 0)));
+        }
+        assert_eq!(data, vec![0x12, 0x34, 0x56, 0x78]);
+        let output = tick(&mut storage, StorageCommand::Rewind, 0, 0);
+        assert_eq!(byte(&output), 0x12);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_storage_out_of_range() {
+        let path = temp_file("range");
+        let mut storage = BlockStorage::open(&path, 4, 4).unwrap();
+        let output = tick(&mut storage, StorageCommand::ReadSector, 0, 4);
+        assert_eq!(output[8..], [false, true]);
+        let output = tick(&mut storage, StorageCommand::Idle, 0, 0);
+        assert_eq!(output[8..], [true, false]);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_storage_too_large() {
+        let path = temp_file("large");
+        let error = BlockStorage::open(&path, usize::MAX / 2 + 1, 2).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_storage_failed_read_keeps_buffer() {
+        let path = temp_file("short");
+        let mut storage = BlockStorage::open(&path, 4, 4).unwrap();
+        for data in [0x12, 0x34] {
+            tick(&mut storage, StorageCommand::PutByte, data, 0);
+        }
+        // the file is cut in the middle of the last sector
+        std::fs::OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_len(14)
+            .unwrap();
+        let output = tick(&mut storage, StorageCommand::ReadSector, 0, 3);
+        assert_eq!(output[8..], [false, true]);
+        let output = tick(&mut storage, StorageCommand::PutByte, 0x56, 0);
+        assert_eq!(output[8..], [true, false]);
+        let output = tick(&mut storage, StorageCommand::Rewind, 0, 0);
+        assert_eq!(byte(&output), 0x12);
+        let output = tick(&mut storage, StorageCommand::NextByte, 0, 0);
+        assert_eq!(byte(&output), 0x34);
+        let output = tick(&mut storage, StorageCommand::NextByte, 0, 0);
+        assert_eq!(byte(&output), 0x56);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_storage_dma() {
+        let path = temp_file("dma");
+        let mut storage = BlockStorage::open(&path, 4, 4).unwrap();
+        let memory: Vec<u8> = (0..8).collect();
+        storage.dma_write(1, &memory).unwrap();
+        let output = tick(&mut storage, StorageCommand::ReadSector, 0, 2);
+        assert_eq!(byte(&output), 4);
+        let mut memory = vec![0; 12];
+        storage.dma_read(0, &mut memory).unwrap();
+        assert_eq!(memory, vec![0, 0, 0, 0, 0, 1, 2, 3, 4, 5, 6, 7]);
+        assert!(storage.dma_read(2, &mut [0; 12]).is_err());
+        assert_eq!(
+            storage.dma_write(usize::MAX, &[0]).unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+}
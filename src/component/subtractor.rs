@@ -0,0 +1,325 @@
+use crate::{
+    circuit::{ANDGate, NOTGate, ORGate, Potential, Wire, XORGate, layout::Layout},
+    component::{
+        Component,
+        datasheet::{Metadata, Timing},
+        port::Port,
+    },
+};
+
+/// a half subtractor in circuit, which subtracts b from a.
+/// the input is 2 bits, and the output is 2 bits.
+///
+/// ```ascii
+///       ┌──────────────────┐
+/// A─────┼                  ┼─────Difference
+///       │  Half Subtractor │
+/// B─────┼                  ┼─────Borrow
+///       └──────────────────┘
+/// ```
+/// # output
+/// the first bit is the difference bit, and the second bit is the borrow bit.
+/// the difference bit is the xor of the two bits.
+/// the borrow bit is the and of the not a and b.
+#[derive(Debug, Default, Clone)]
+pub struct HalfSubtractor {
+    input: [Wire; 2],
+    output: [Wire; 2],
+    not_gate: NOTGate,
+    and_gate: ANDGate,
+    xor_gate: XORGate,
+}
+
+impl Component for HalfSubtractor {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (2, 2)
+    }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "half subtractor",
+            description: "subtracts a bit from a bit, to a difference and a borrow.",
+            timing: Timing::Combinational,
+        })
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+    fn ports(&self) -> Vec<Port> {
+        vec![
+            Port::input("a", 0..1),
+            Port::input("b", 1..2),
+            Port::output("difference", 0..1),
+            Port::output("borrow_out", 1..2),
+        ]
+    }
+    fn update_state(&mut self) {
+        self.not_gate.input(&self.input[0].output());
+        self.and_gate
+            .input(&self.not_gate.output(), &self.input[1].output());
+        self.xor_gate
+            .input(&self.input[0].output(), &self.input[1].output());
+        // Difference
+        self.output[0].input(&self.xor_gate.output());
+        // Borrow
+        self.output[1].input(&self.and_gate.output());
+    }
+}
+
+/// a full subtractor in circuit, which subtracts b and a borrow from a.
+/// the input is 3 bits, and the output is 2 bits.
+///
+/// ```ascii
+///       ┌──────────────────┐                   ┌────┐
+/// A─────┼A                B┼───────────────────┼    │
+///       │  Half Subtractor │                   │ OR ┼───Borrow
+/// B─────┼B                D┼────┐            ┌─┼    │
+///       └──────────────────┘    │            │ └────┘
+///                     ┌─────────┘            │
+///                     │    ┌──────────────────┐
+///                     ┴────┼A                B┼
+///                          │  Half Subtractor │
+/// Borrow───────────────────┼B                D┼──────Difference
+///                          └──────────────────┘
+/// ```
+/// # input
+/// the first 1 bit is A , the next 1 bit is B and the last bit is borrow from another
+/// subtractor
+///
+/// # output
+/// the first bit is the difference bit, and the second bit is the borrow bit.
+#[derive(Debug, Default, Clone)]
+pub struct FullSubtractor {
+    half_subtractor: [HalfSubtractor; 2],
+    or_gate: ORGate,
+    input: [Wire; 3],
+    output: [Wire; 2],
+}
+
+impl Component for FullSubtractor {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (3, 2)
+    }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "full subtractor",
+            description: "subtracts a bit and a borrow in from a bit, to a difference and a borrow out.",
+            timing: Timing::Combinational,
+        })
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+    fn ports(&self) -> Vec<Port> {
+        vec![
+            Port::input("a", 0..1),
+            Port::input("b", 1..2),
+            Port::input("borrow_in", 2..3),
+            Port::output("difference", 0..1),
+            Port::output("borrow_out", 1..2),
+        ]
+    }
+    fn update_state(&mut self) {
+        self.half_subtractor[0].input(&[self.input[0].output(), self.input[1].output()]);
+        let out1 = self.half_subtractor[0].output();
+        self.half_subtractor[1].input(&[out1[0], self.input[2].output()]);
+        let out2 = self.half_subtractor[1].output();
+        self.or_gate.input(&out1[1], &out2[1]);
+        // Difference
+        self.output[0].input(&out2[0]);
+        // Borrow
+        self.output[1].input(&self.or_gate.output());
+    }
+}
+
+/// a ripple borrow subtractor in circuit, which subtracts b and a borrow from a.
+/// the input is 2*n+1 bits, and the output is n+1 bits.
+///
+/// a chain of [`FullSubtractor`]s, the borrow of every bit is the borrow in of the
+/// next bit, like the carry of a [`RippleCarryAdderN`](crate::component::adder::RippleCarryAdderN).
+///
+/// # input
+/// the first 1 bit is borrow from another subtractor, the next n bit is A and the
+/// last n bit is B (the first bit is the lowest bit).
+///
+/// # output
+/// the first n bit is the difference, which wraps around, and the next 1 bit is the
+/// borrow bit, which is high when a < b + borrow.
+#[derive(Debug, Clone)]
+pub struct RippleBorrowSubtractorN {
+    n_way: usize,
+    layout: Layout,
+    input: Vec<Wire>,
+    full_subtractors: Vec<FullSubtractor>,
+    output: Vec<Wire>,
+}
+
+impl RippleBorrowSubtractorN {
+    pub fn new(n_way: usize) -> Self {
+        assert!(n_way > 0, "n_way must be greater than 0");
+        let layout = Layout::default()
+            .field("borrow_in", 1)
+            .field("a", n_way)
+            .field("b", n_way);
+        Self {
+            n_way,
+            input: vec![Wire::default(); layout.width()],
+            layout,
+            full_subtractors: vec![FullSubtractor::default(); n_way],
+            output: vec![Wire::default(); n_way + 1],
+        }
+    }
+}
+
+impl Component for RippleBorrowSubtractorN {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (2 * self.n_way + 1, self.n_way + 1)
+    }
+    fn metadata(&self) -> Option<Metadata> {
+        Some(Metadata {
+            name: "ripple borrow subtractor",
+            description: "subtracts a n-bit number and a borrow in from a n-bit number, with a chain of full subtractors.",
+            timing: Timing::Combinational,
+        })
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+    fn ports(&self) -> Vec<Port> {
+        let n = self.n_way;
+        vec![
+            Port::input("borrow_in", self.layout.range("borrow_in")),
+            Port::input("a", self.layout.range("a")),
+            Port::input("b", self.layout.range("b")),
+            Port::output("difference", 0..n),
+            Port::output("borrow_out", n..n + 1),
+        ]
+    }
+    fn update_state(&mut self) {
+        let a = self.layout.range("a");
+        let b = self.layout.range("b");
+        let mut borrow = self.input[self.layout.range("borrow_in").start].output();
+        for i in 0..self.n_way {
+            self.full_subtractors[i].input(&[
+                self.input[a.start + i].output(),
+                self.input[b.start + i].output(),
+                borrow,
+            ]);
+            let out = self.full_subtractors[i].output();
+            self.output[i].input(&out[0]);
+            borrow = out[1];
+        }
+        self.output[self.n_way].input(&borrow);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::{Pad, Potentials};
+    use rstest::rstest;
+
+    #[test]
+    fn test_half_subtractor_default() {
+        let half_subtractor = HalfSubtractor::default();
+        assert_eq!(half_subtractor.output(), vec![false, false]);
+    }
+
+    #[rstest]
+    // a, b => difference, borrow
+    #[case(false, false, false, false)]
+    #[case(false, true, true, true)]
+    #[case(true, false, true, false)]
+    #[case(true, true, false, false)]
+    fn test_half_subtractor_input(
+        #[case] a: bool,
+        #[case] b: bool,
+        #[case] difference: bool,
+        #[case] borrow: bool,
+    ) {
+        let mut half_subtractor = HalfSubtractor::default();
+        half_subtractor.input(&[a, b]);
+        assert_eq!(half_subtractor.output(), vec![difference, borrow]);
+    }
+
+    #[rstest]
+    // a, b, borrow in => difference, borrow out
+    #[case(false, false, false, false, false)]
+    #[case(false, false, true, true, true)]
+    #[case(false, true, false, true, true)]
+    #[case(false, true, true, false, true)]
+    #[case(true, false, false, true, false)]
+    #[case(true, false, true, false, false)]
+    #[case(true, true, false, false, false)]
+    #[case(true, true, true, true, true)]
+    fn test_full_subtractor_input(
+        #[case] a: bool,
+        #[case] b: bool,
+        #[case] borrow_in: bool,
+        #[case] difference: bool,
+        #[case] borrow_out: bool,
+    ) {
+        let mut full_subtractor = FullSubtractor::default();
+        full_subtractor.input(&[a, b, borrow_in]);
+        assert_eq!(full_subtractor.output(), vec![difference, borrow_out]);
+    }
+
+    #[test]
+    fn test_ripple_borrow_subtractor_exhaustive() {
+        let mut subtractor = RippleBorrowSubtractorN::new(4);
+        for a in 0..16i64 {
+            for b in 0..16i64 {
+                for borrow in [0, 1] {
+                    subtractor.set_port("borrow_in", &Potentials::with_width(borrow, 1, Pad::Zero));
+                    subtractor.set_port("a", &Potentials::with_width(a, 4, Pad::Zero));
+                    subtractor.set_port("b", &Potentials::with_width(b, 4, Pad::Zero));
+                    subtractor.update_state();
+                    let difference = (a - b - borrow).rem_euclid(16);
+                    assert_eq!(
+                        subtractor.get_port("difference").get_data(true),
+                        Potentials::with_width(difference, 4, Pad::Zero).get_data(true)
+                    );
+                    assert_eq!(subtractor.get_pin_output(4), a < b + borrow);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use crate::{
+    circuit::{ANDGate, NOTGate, ORGate, Potential, Wire, init},
+    component::{Component, counter::Incrementer, edge::EdgeDetector, port::Port},
+};
+
+/// a n-bit timer peripheral with a capture register and an output compare in circuit.
+/// the input is n+4 bits, and the output is 2n+1 bits.
+///
+/// a free running counter increases on every rising edge of the clock. a rising edge
+/// of the capture input latches the count into the capture register, and the interrupt
+/// flag is set when the count reaches the compare register. a rising edge of the
+/// clock while clear is high clears the count, the capture register and the flag.
+///
+/// ```ascii
+///                 compare
+///                    │
+///            ┌───────┴───────┐
+/// clock──────┤               ├──────count
+/// capture────┤     Timer     ├──────capture
+/// ack────────┤               ├──────interrupt
+/// clear──────┤               │
+///            └───────────────┘
+/// ```
+///
+/// # input
+/// the first bit is the clock, the second bit is the capture input, the third bit
+/// acknowledges the interrupt, the fourth bit is the synchronous clear, and the last
+/// n bits are the compare register (the first bit is the lowest bit).
+///
+/// # output
+/// the first n bits are the count, the next n bits are the capture register,
+/// and the last bit is the interrupt flag.
+///
+/// the interrupt flag is sticky: it is loaded on the rising edge of the clock and
+/// stays high until it is acknowledged on a later rising edge. a new match wins over
+/// the acknowledge of the same edge. the capture register latches the count before
+/// the increment when both edges arrive together.
+#[derive(Debug, Clone)]
+pub struct TimerN {
+    n_way: usize,
+    input: Vec<Wire>,
+    count: Vec<Wire>,
+    capture: Vec<Wire>,
+    interrupt: Wire,
+    clock_edge: EdgeDetector,
+    capture_edge: EdgeDetector,
+    // count + 1 == compare
+    incrementer: Incrementer,
+    not_ack: NOTGate,
+    keep: ANDGate,
+    set: ORGate,
+    not_clear: NOTGate,
+    // the next capture register and interrupt flag, or 0 while clear is high
+    capture_gates: Vec<ANDGate>,
+    interrupt_gate: ANDGate,
+}
+
+impl TimerN {
+    pub fn new(n_way: usize) -> Self {
+        assert!(n_way > 0, "n_way must be greater than 0");
+        Self {
+            n_way,
+            input: vec![Wire::default(); n_way + 4],
+            count: init::power_up(n_way),
+            capture: init::power_up(n_way),
+            interrupt: init::power_up_wire(),
+            clock_edge: EdgeDetector::default(),
+            capture_edge: EdgeDetector::default(),
+            incrementer: Incrementer::new(n_way),
+            not_ack: NOTGate::default(),
+            keep: ANDGate::default(),
+            set: ORGate::default(),
+            not_clear: NOTGate::default(),
+            capture_gates: vec![ANDGate::default(); n_way],
+            interrupt_gate: ANDGate::default(),
+        }
+    }
+}
+
+impl Component for TimerN {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.n_way + 4, self.n_way * 2 + 1)
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        if position < self.n_way {
+            self.count[position].output()
+        } else if position < self.n_way * 2 {
+            self.capture[position - self.n_way].output()
+        } else {
+            self.interrupt.output()
+        }
+    }
+    fn ports(&self) -> Vec<Port> {
+        let n = self.n_way;
+        vec![
+            Port::input("clock", 0..1),
+            Port::input("capture", 1..2),
+            Port::input("ack", 2..3),
+            Port::input("clear", 3..4),
+            Port::input("compare", 4..n + 4),
+            Port::output("count", 0..n),
+            Port::output("capture", n..2 * n),
+            Port::output("interrupt", 2 * n..2 * n + 1),
+        ]
+    }
+    fn update_state(&mut self) {
+        self.clock_edge.input(&[self.input[0].output()]);
+        self.capture_edge.input(&[self.input[1].output()]);
+        if self.capture_edge.is_rising() {
+            for i in 0..self.n_way {
+                self.capture[i].input(&self.count[i].output());
+            }
+        }
+
+        // count + 1, the carry out is dropped so the counter wraps around
+        self.incrementer.increment(&self.count, &true);
+        let matched = self.incrementer.matches(&self.input[4..]);
+        // interrupt = (interrupt and not ack) or matched
+        self.not_ack.input(&self.input[2].output());
+        self.keep
+            .input(&self.interrupt.output(), &self.not_ack.output());
+        self.set.input(&self.keep.output(), &matched);
+        self.not_clear.input(&self.input[3].output());
+        let next = self.incrementer.next(&self.not_clear.output());
+        self.interrupt_gate
+            .input(&self.set.output(), &self.not_clear.output());
+
+        if self.clock_edge.is_rising() {
+            for (i, bit) in next.iter().enumerate() {
+                self.count[i].input(bit);
+                self.capture_gates[i].input(&self.capture[i].output(), &self.not_clear.output());
+                self.capture[i].input(&self.capture_gates[i].output());
+            }
+            self.interrupt.input(&self.interrupt_gate.output());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn bits(value: usize, width: usize) -> Vec<Potential> {
+        (0..width).map(|i| (value >> i) & 1 == 1).collect()
+    }
+
+    fn value(bits: &[Potential]) -> usize {
+        bits.iter()
+            .enumerate()
+            .fold(0, |acc, (i, bit)| acc | ((*bit as usize) << i))
+    }
+
+    /// run a full clock cycle with the given capture, ack and compare inputs.
+    fn tick(timer: &mut TimerN, capture: bool, ack: bool, compare: usize) {
+        tick_clear(timer, capture, ack, false, compare);
+    }
+
+    fn tick_clear(timer: &mut TimerN, capture: bool, ack: bool, clear: bool, compare: usize) {
+        let mut input = vec![false, capture, ack, clear];
+        input.extend(bits(compare, timer.n_way));
+        timer.input(&input);
+        input[0] = true;
+        timer.input(&input);
+    }
+
+    /// create a timer and clear whatever it powers up with.
+    fn cleared(n_way: usize) -> TimerN {
+        let mut timer = TimerN::new(n_way);
+        tick_clear(&mut timer, false, false, true, 0);
+        timer
+    }
+
+    #[test]
+    fn test_timer_default() {
+        let timer = cleared(4);
+        assert_eq!(timer.get_pin_count(), (8, 9));
+        assert_eq!(timer.output(), vec![false; 9]);
+    }
+
+    #[test]
+    fn test_timer_clear() {
+        let mut timer = cleared(4);
+        for _ in 0..3 {
+            tick(&mut timer, false, false, 2);
+        }
+        tick(&mut timer, true, false, 2);
+        assert_eq!(value(&timer.get_port("count").get_data(true)), 4);
+        assert_eq!(value(&timer.get_port("capture").get_data(true)), 3);
+        assert_eq!(timer.get_port("interrupt").get_data(true), vec![true]);
+        // clear wins over the increment and a new match of the same edge
+        tick_clear(&mut timer, false, false, true, 0);
+        assert_eq!(timer.output(), vec![false; 9]);
+        tick(&mut timer, false, false, 0);
+        assert_eq!(value(&timer.get_port("count").get_data(true)), 1);
+    }
+
+    #[test]
+    fn test_timer_counts_and_wraps() {
+        let mut timer = cleared(3);
+        for expected in [1, 2, 3, 4, 5, 6, 7, 0, 1] {
+            tick(&mut timer, false, false, 0);
+            assert_eq!(value(&timer.output()[0..3]), expected);
+        }
+    }
+
+    #[test]
+    fn test_timer_capture() {
+        let mut timer = cleared(4);
+        for _ in 0..5 {
+            tick(&mut timer, false, false, 0);
+        }
+        // the capture edge arrives before the rising edge of the clock
+        tick(&mut timer, true, false, 0);
+        assert_eq!(value(&timer.output()[0..4]), 6);
+        assert_eq!(value(&timer.output()[4..8]), 5);
+        // holding the capture input high does not latch again
+        tick(&mut timer, true, false, 0);
+        assert_eq!(value(&timer.output()[4..8]), 5);
+        tick(&mut timer, false, false, 0);
+        tick(&mut timer, true, false, 0);
+        assert_eq!(value(&timer.output()[4..8]), 8);
+    }
+
+    #[test]
+    fn test_timer_capture_without_clock() {
+        let mut timer = cleared(4);
+        tick(&mut timer, false, false, 0);
+        tick(&mut timer, false, false, 0);
+        let mut input = vec![false, true, false, false];
+        input.extend(bits(0, 4));
+        timer.input(&input);
+        assert_eq!(value(&timer.output()[0..4]), 2);
+        assert_eq!(value(&timer.output()[4..8]), 2);
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(6)]
+    #[case(15)]
+    fn test_timer_compare_interrupt(#[case] compare: usize) {
+        let mut timer = cleared(4);
+        for _ in 1..compare {
+            tick(&mut timer, false, false, compare);
+            assert!(!timer.output()[8]);
+        }
+        tick(&mut timer, false, false, compare);
+        assert!(timer.output()[8]);
+        // the flag stays high until it is acknowledged
+        tick(&mut timer, false, false, compare);
+        assert!(timer.output()[8]);
+        tick(&mut timer, false, true, compare);
+        assert!(!timer.output()[8]);
+        tick(&mut timer, false, false, compare);
+        assert!(!timer.output()[8]);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{
+    circuit::{Potential, Wire},
+    component::{Component, big_gates::MajorityGate3},
+};
+
+/// a triple modular redundancy wrapper in circuit.
+/// the input and output are the same as the wrapped component.
+///
+/// ```ascii
+///          ┌───────────┐   ┌──────────┐
+///      ┌───┤ replica 0 ├───┤          │
+///      │   └───────────┘   │          │
+///      │   ┌───────────┐   │ majority │
+/// in───┼───┤ replica 1 ├───┤  voters  ├───out
+///      │   └───────────┘   │          │
+///      │   ┌───────────┐   │          │
+///      └───┤ replica 2 ├───┤          │
+///          └───────────┘   └──────────┘
+/// ```
+///
+/// # output
+/// each output pin is the majority of the same output pin of the three replicas,
+/// so a fault in any single replica does not reach the output.
+#[derive(Debug, Clone)]
+pub struct Tmr<C: Component + Clone> {
+    replicas: [C; 3],
+    voters: Vec<MajorityGate3>,
+    output: Vec<Wire>,
+}
+
+impl<C: Component + Clone> Tmr<C> {
+    pub fn new(component: C) -> Self {
+        let output_count = component.get_pin_count().1;
+        Self {
+            replicas: [component.clone(), component.clone(), component],
+            voters: vec![MajorityGate3::default(); output_count],
+            output: vec![Wire::default(); output_count],
+        }
+    }
+
+    /// Get a replica of the wrapped component.
+    ///
+    /// # Arguments
+    /// * `index` - The index of the replica, must be less than 3.
+    pub fn replica(&self, index: usize) -> &C {
+        &self.replicas[index]
+    }
+
+    /// Get a mutable replica of the wrapped component, used to inject faults into one copy.
+    ///
+    /// # Arguments
+    /// * `index` - The index of the replica, must be less than 3.
+    pub fn replica_mut(&mut self, index: usize) -> &mut C {
+        &mut self.replicas[index]
+    }
+}
+
+impl<C: Component + Clone> Component for Tmr<C> {
+    fn get_pin_count(&self) -> (usize, usize) {
+        self.replicas[0].get_pin_count()
+    }
+
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        for replica in self.replicas.iter_mut() {
+            replica.set_pin_input(position, value);
+        }
+    }
+
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+
+    fn update_state(&mut self) {
+        for replica in self.replicas.iter_mut() {
+            replica.update_state();
+        }
+        for i in 0..self.output.len() {
+            self.voters[i].input(&[
+                self.replicas[0].get_pin_output(i),
+                self.replicas[1].get_pin_output(i),
+                self.replicas[2].get_pin_output(i),
+            ]);
+            self.output[i].input(&self.voters[i].get_pin_output(0));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        circuit::{Padding, Potentials},
+        component::adder::RippleCarryAdderN,
+    };
+    use rstest::rstest;
+
+    #[test]
+    fn test_tmr_default() {
+        let tmr = Tmr::new(RippleCarryAdderN::new(2));
+        assert_eq!(tmr.get_pin_count(), (5, 3));
+        assert_eq!(tmr.output(), vec![false, false, false]);
+    }
+
+    #[rstest]
+    /// carry | a | b  => sum | carry
+    #[case("0 10 10", "01 0")]
+    #[case("0 11 10", "00 1")]
+    #[case("1 11 11", "11 1")]
+    fn test_tmr_input(#[case] input: String, #[case] output: String) {
+        let mut tmr = Tmr::new(RippleCarryAdderN::new(2));
+        let i = Potentials::from_little_endian(&input, Padding::Keep);
+        tmr.input(&i.get_data(true));
+        let o = Potentials::from_little_endian(&output, Padding::Keep);
+        assert_eq!(tmr.output(), o.get_data(true));
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(2)]
+    fn test_tmr_single_fault(#[case] faulty: usize) {
+        let mut tmr = Tmr::new(RippleCarryAdderN::new(2));
+        let i = Potentials::from_little_endian("0 11 10", Padding::Keep);
+        tmr.prepare_input(&i.get_data(true));
+        // inject a fault: the faulty replica sees a different carry
+        tmr.replica_mut(faulty).set_pin_input(0, &true);
+        tmr.update_state();
+        assert_eq!(tmr.replica(faulty).output(), vec![true, false, true]);
+        let o = Potentials::from_little_endian("00 1", Padding::Keep);
+        assert_eq!(tmr.output(), o.get_data(true));
+    }
+}
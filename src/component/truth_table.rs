@@ -0,0 +1,154 @@
+use crate::{circuit::Potential, component::Component};
+
+/// the truth table of a combinational component, run from the component itself.
+///
+/// the markdown of the table is the one in the doc comments of the components, so a
+/// test can check that the doc comment of a component is still its behavior.
+///
+/// # Examples
+///
+/// ```
+/// use simulation_computer_rust::component::{
+///     adder::HalfAdder, truth_table::TruthTable,
+/// };
+///
+/// let table = TruthTable::exhaustive(&mut HalfAdder::default()).with_names(&["A", "B"], &["S", "C"]);
+/// assert_eq!(
+///     table.to_markdown(),
+///     "| A | B | S | C |\n\
+///      |---|---|---|---|\n\
+///      | 0 | 0 | 0 | 0 |\n\
+///      | 1 | 0 | 1 | 0 |\n\
+///      | 0 | 1 | 1 | 0 |\n\
+///      | 1 | 1 | 0 | 1 |\n"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TruthTable {
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+    rows: Vec<(Vec<Potential>, Vec<Potential>)>,
+}
+
+impl TruthTable {
+    /// Run the component with every input, counting up with the pin 0 as the lowest bit.
+    pub fn exhaustive<C: Component + ?Sized>(component: &mut C) -> Self {
+        let count = component.get_pin_count().0;
+        assert!(count <= 16, "component must have at most 16 input pins");
+        let rows: Vec<Vec<Potential>> = (0..1usize << count)
+            .map(|row| (0..count).map(|i| (row >> i) & 1 == 1).collect())
+            .collect();
+        Self::of_rows(component, &rows)
+    }
+
+    /// Run the component with the given inputs, e.g. only the valid one-hot inputs of
+    /// an encoder.
+    pub fn of_rows<C: Component + ?Sized>(component: &mut C, rows: &[Vec<Potential>]) -> Self {
+        let (input_count, output_count) = component.get_pin_count();
+        let rows = rows
+            .iter()
+            .map(|input| {
+                assert!(
+                    input.len() == input_count,
+                    "input must be {} bits",
+                    input_count
+                );
+                component.input(input);
+                (input.clone(), component.output())
+            })
+            .collect();
+        let outputs = if output_count == 1 {
+            vec!["OUT".to_string()]
+        } else {
+            (0..output_count).map(|i| format!("OUT{}", i)).collect()
+        };
+        Self {
+            inputs: (0..input_count).map(|i| format!("I{}", i)).collect(),
+            outputs,
+            rows,
+        }
+    }
+
+    /// Rename the columns, the default names are `I0`.. and `OUT0`.., or `OUT` for a
+    /// single output.
+    pub fn with_names(mut self, inputs: &[&str], outputs: &[&str]) -> Self {
+        assert!(
+            inputs.len() == self.inputs.len() && outputs.len() == self.outputs.len(),
+            "names must be {} inputs and {} outputs",
+            self.inputs.len(),
+            self.outputs.len()
+        );
+        self.inputs = inputs.iter().map(|name| name.to_string()).collect();
+        self.outputs = outputs.iter().map(|name| name.to_string()).collect();
+        self
+    }
+
+    /// Get the rows of the inputs and the outputs.
+    pub fn rows(&self) -> &[(Vec<Potential>, Vec<Potential>)] {
+        &self.rows
+    }
+
+    /// Render the table as markdown.
+    pub fn to_markdown(&self) -> String {
+        let names: Vec<&str> = self
+            .inputs
+            .iter()
+            .chain(self.outputs.iter())
+            .map(|name| name.as_str())
+            .collect();
+        let mut table = format!("| {} |\n", names.join(" | "));
+        table.push_str(&format!("|{}\n", "---|".repeat(names.len())));
+        for (input, output) in self.rows.iter() {
+            let cells: Vec<&str> = input
+                .iter()
+                .chain(output.iter())
+                .map(|bit| if *bit { "1" } else { "0" })
+                .collect();
+            table.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+        table
+    }
+
+    /// Render the table as the lines of a doc comment.
+    pub fn to_doc_comment(&self) -> String {
+        self.to_markdown()
+            .lines()
+            .map(|line| format!("/// {}\n", line))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::big_gates::{ANDGate3, MajorityGate3};
+
+    #[test]
+    fn test_truth_table_exhaustive() {
+        let table = TruthTable::exhaustive(&mut ANDGate3::default());
+        assert_eq!(table.rows().len(), 8);
+        let high: Vec<usize> = (0..8).filter(|i| table.rows()[*i].1[0]).collect();
+        assert_eq!(high, vec![7]);
+    }
+
+    #[test]
+    fn test_truth_table_of_rows() {
+        let rows = vec![vec![true, true, false], vec![false, false, true]];
+        let table = TruthTable::of_rows(&mut MajorityGate3::default(), &rows);
+        assert_eq!(
+            table.to_doc_comment(),
+            concat!(
+                "/// | I0 | I1 | I2 | OUT |\n",
+                "/// |---|---|---|---|\n",
+                "/// | 1 | 1 | 0 | 1 |\n",
+                "/// | 0 | 0 | 1 | 0 |\n",
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "names must be 3 inputs and 1 outputs")]
+    fn test_truth_table_names() {
+        let _ = TruthTable::exhaustive(&mut ANDGate3::default()).with_names(&["A"], &["Y"]);
+    }
+}
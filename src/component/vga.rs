@@ -0,0 +1,381 @@
+use crate::{
+    circuit::{ANDGate, NOTGate, ORGate, Potential, Wire, init},
+    component::{Component, counter::Incrementer, edge::EdgeDetector},
+};
+
+/// the timing of one direction of a video signal, in pixels or lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VideoTiming {
+    pub visible: usize,
+    pub front_porch: usize,
+    pub sync: usize,
+    pub back_porch: usize,
+}
+
+impl VideoTiming {
+    /// Get the pixels or lines of a whole period.
+    pub fn total(&self) -> usize {
+        self.visible + self.front_porch + self.sync + self.back_porch
+    }
+}
+
+/// the horizontal and vertical timing of a video mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VgaMode {
+    pub horizontal: VideoTiming,
+    pub vertical: VideoTiming,
+}
+
+/// the 640x480 at 60 hz mode, with a 25.175 mhz pixel clock.
+pub const VGA_640X480: VgaMode = VgaMode {
+    horizontal: VideoTiming {
+        visible: 640,
+        front_porch: 16,
+        sync: 96,
+        back_porch: 48,
+    },
+    vertical: VideoTiming {
+        visible: 480,
+        front_porch: 10,
+        sync: 2,
+        back_porch: 33,
+    },
+};
+
+/// a comparator of a number with a constant, `x < constant`.
+///
+/// the comparison ripples from the lowest bit, with a constant bit the
+/// `less(i) = (not xi and ci) or (xi xnor ci and less(i-1))` becomes
+/// `not xi or less(i-1)` when ci is 1, and `not xi and less(i-1)` when ci is 0.
+#[derive(Debug, Clone)]
+struct LessThanConstant {
+    constant: usize,
+    not_gates: Vec<NOTGate>,
+    and_gates: Vec<ANDGate>,
+    or_gates: Vec<ORGate>,
+}
+
+impl LessThanConstant {
+    fn new(width: usize, constant: usize) -> Self {
+        assert!(constant < 1 << width, "constant must fit in the width");
+        Self {
+            constant,
+            not_gates: vec![NOTGate::default(); width],
+            and_gates: vec![ANDGate::default(); width],
+            or_gates: vec![ORGate::default(); width],
+        }
+    }
+
+    fn compare(&mut self, bits: &[Wire]) -> Potential {
+        let mut less = false;
+        for (i, bit) in bits.iter().enumerate() {
+            self.not_gates[i].input(&bit.output());
+            if (self.constant >> i) & 1 == 1 {
+                self.or_gates[i].input(&self.not_gates[i].output(), &less);
+                less = self.or_gates[i].output();
+            } else {
+                self.and_gates[i].input(&self.not_gates[i].output(), &less);
+                less = self.and_gates[i].output();
+            }
+        }
+        less
+    }
+}
+
+/// a counter of one direction of [`VgaTiming`], with its comparators.
+#[derive(Debug, Clone)]
+struct TimingCounter {
+    count: Vec<Wire>,
+    // count + 1 == total
+    incrementer: Incrementer,
+    wrap: ANDGate,
+    not_wrap: NOTGate,
+    // the count is kept unless it wraps or resets
+    keep: ANDGate,
+    next: Vec<Potential>,
+    visible: LessThanConstant,
+    sync_start: LessThanConstant,
+    sync_end: LessThanConstant,
+    not_sync_start: NOTGate,
+    sync: ANDGate,
+    timing: VideoTiming,
+}
+
+impl TimingCounter {
+    fn new(timing: VideoTiming) -> Self {
+        assert!(timing.visible > 0, "visible must be greater than 0");
+        assert!(timing.sync > 0, "sync must be greater than 0");
+        let total = timing.total();
+        // the width holds the total, so the end of the sync pulse always fits
+        let width = (usize::BITS - total.leading_zeros()) as usize;
+        Self {
+            count: init::power_up(width),
+            incrementer: Incrementer::new(width),
+            wrap: ANDGate::default(),
+            not_wrap: NOTGate::default(),
+            keep: ANDGate::default(),
+            next: vec![false; width],
+            visible: LessThanConstant::new(width, timing.visible),
+            sync_start: LessThanConstant::new(width, timing.visible + timing.front_porch),
+            sync_end: LessThanConstant::new(width, total - timing.back_porch),
+            not_sync_start: NOTGate::default(),
+            sync: ANDGate::default(),
+            timing,
+        }
+    }
+
+    /// Evaluate the next count, the counter only advances when `enable` is high, and
+    /// the next count is 0 when `not_reset` is low.
+    /// return whether the counter wraps around.
+    fn evaluate(&mut self, enable: &Potential, not_reset: &Potential) -> Potential {
+        self.incrementer.increment(&self.count, enable);
+        // count + enable == total only when the count is the last one and it advances
+        let last = self.incrementer.reaches(self.timing.total());
+        self.wrap.input(&last, enable);
+        self.not_wrap.input(&self.wrap.output());
+        self.keep.input(&self.not_wrap.output(), not_reset);
+        self.next = self.incrementer.next(&self.keep.output());
+        self.wrap.output()
+    }
+
+    fn load(&mut self) {
+        for (wire, bit) in self.count.iter_mut().zip(&self.next) {
+            wire.input(bit);
+        }
+    }
+
+    /// Evaluate whether the count is visible and whether the count is in the sync pulse.
+    fn compare(&mut self) -> (Potential, Potential) {
+        let visible = self.visible.compare(&self.count);
+        self.not_sync_start
+            .input(&self.sync_start.compare(&self.count));
+        let end = self.sync_end.compare(&self.count);
+        self.sync.input(&self.not_sync_start.output(), &end);
+        (visible, self.sync.output())
+    }
+}
+
+/// a vga style timing generator in circuit.
+/// the input is 2 bits, and the output is 3 + x + y bits, where x and y are the
+/// widths of the horizontal and vertical counters.
+///
+/// the horizontal counter increases on every rising edge of the pixel clock, and the
+/// vertical counter increases when the horizontal counter wraps around. a rising edge
+/// of the clock while reset is high moves both counters to 0.
+///
+/// ```ascii
+///            ┌───────────────┐
+///            │               ├──────hsync
+///            │               ├──────vsync
+/// clock──────┤  VGA Timing   ├──────visible
+/// reset──────┤               ├──────x
+///            │               ├──────y
+///            └───────────────┘
+/// ```
+///
+/// # input
+/// the first bit is the pixel clock, and the second bit is the synchronous reset.
+///
+/// # output
+/// the first bit is the horizontal sync, the second bit is the vertical sync,
+/// the third bit is high while the pixel is visible, then the x and y coordinates
+/// (the first bit is the lowest bit). the sync pulses are active high.
+#[derive(Debug, Clone)]
+pub struct VgaTiming {
+    mode: VgaMode,
+    input: [Wire; 2],
+    output: [Wire; 3],
+    clock_edge: EdgeDetector,
+    horizontal: TimingCounter,
+    vertical: TimingCounter,
+    visible: ANDGate,
+    not_reset: NOTGate,
+}
+
+impl VgaTiming {
+    pub fn new(mode: VgaMode) -> Self {
+        Self {
+            mode,
+            input: [Wire::default(); 2],
+            output: [Wire::default(); 3],
+            clock_edge: EdgeDetector::default(),
+            horizontal: TimingCounter::new(mode.horizontal),
+            vertical: TimingCounter::new(mode.vertical),
+            visible: ANDGate::default(),
+            not_reset: NOTGate::default(),
+        }
+    }
+
+    /// Get the video mode of the generator.
+    pub fn mode(&self) -> VgaMode {
+        self.mode
+    }
+}
+
+impl Component for VgaTiming {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (
+            2,
+            3 + self.horizontal.count.len() + self.vertical.count.len(),
+        )
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        let x_width = self.horizontal.count.len();
+        if position < 3 {
+            self.output[position].output()
+        } else if position < 3 + x_width {
+            self.horizontal.count[position - 3].output()
+        } else {
+            self.vertical.count[position - 3 - x_width].output()
+        }
+    }
+    fn update_state(&mut self) {
+        self.clock_edge.input(&[self.input[0].output()]);
+        if self.clock_edge.is_rising() {
+            self.not_reset.input(&self.input[1].output());
+            let not_reset = self.not_reset.output();
+            let line_end = self.horizontal.evaluate(&true, &not_reset);
+            self.vertical.evaluate(&line_end, &not_reset);
+            self.horizontal.load();
+            self.vertical.load();
+        }
+        let (x_visible, hsync) = self.horizontal.compare();
+        let (y_visible, vsync) = self.vertical.compare();
+        self.visible.input(&x_visible, &y_visible);
+        self.output[0].input(&hsync);
+        self.output[1].input(&vsync);
+        self.output[2].input(&self.visible.output());
+    }
+}
+
+// the timing has no reset, so the tests read the count it powers up with
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const SMALL: VgaMode = VgaMode {
+        horizontal: VideoTiming {
+            visible: 4,
+            front_porch: 1,
+            sync: 2,
+            back_porch: 1,
+        },
+        vertical: VideoTiming {
+            visible: 3,
+            front_porch: 1,
+            sync: 1,
+            back_porch: 1,
+        },
+    };
+
+    fn value(bits: &[Potential]) -> usize {
+        bits.iter()
+            .enumerate()
+            .fold(0, |acc, (i, bit)| acc | ((*bit as usize) << i))
+    }
+
+    fn tick(vga: &mut VgaTiming) -> Vec<Potential> {
+        vga.input(&[false, false]);
+        vga.input(&[true, false]);
+        vga.output()
+    }
+
+    /// create a generator and move the beam to (0, 0), whatever it powers up with.
+    fn reset(mode: VgaMode) -> VgaTiming {
+        let mut vga = VgaTiming::new(mode);
+        vga.input(&[false, true]);
+        vga.input(&[true, true]);
+        vga
+    }
+
+    /// the expected outputs of a position.
+    fn expected(mode: &VgaMode, x: usize, y: usize) -> (bool, bool, bool) {
+        let in_sync = |timing: &VideoTiming, v: usize| {
+            v >= timing.visible + timing.front_porch
+                && v < timing.visible + timing.front_porch + timing.sync
+        };
+        (
+            in_sync(&mode.horizontal, x),
+            in_sync(&mode.vertical, y),
+            x < mode.horizontal.visible && y < mode.vertical.visible,
+        )
+    }
+
+    #[test]
+    fn test_vga_default() {
+        let vga = reset(VGA_640X480);
+        assert_eq!(VGA_640X480.horizontal.total(), 800);
+        assert_eq!(VGA_640X480.vertical.total(), 525);
+        assert_eq!(vga.get_pin_count(), (2, 3 + 10 + 10));
+        assert_eq!(vga.output()[0..3], [false, false, true]);
+        assert_eq!(value(&vga.output()[3..]), 0);
+    }
+
+    #[rstest]
+    #[case(SMALL)]
+    #[case(VgaMode {
+        horizontal: VideoTiming { visible: 5, front_porch: 0, sync: 3, back_porch: 0 },
+        vertical: VideoTiming { visible: 2, front_porch: 2, sync: 2, back_porch: 3 },
+    })]
+    fn test_vga_frames(#[case] mode: VgaMode) {
+        let mut vga = reset(mode);
+        let x_width = vga.horizontal.count.len();
+        let (h_total, v_total) = (mode.horizontal.total(), mode.vertical.total());
+        // the first edge moves the beam from (0, 0) to (1, 0)
+        for n in 1..=h_total * v_total * 2 {
+            let output = tick(&mut vga);
+            let (x, y) = (n % h_total, (n / h_total) % v_total);
+            assert_eq!(value(&output[3..3 + x_width]), x);
+            assert_eq!(value(&output[3 + x_width..]), y);
+            let (hsync, vsync, visible) = expected(&mode, x, y);
+            assert_eq!(output[0..3], [hsync, vsync, visible], "at ({}, {})", x, y);
+        }
+    }
+
+    #[test]
+    fn test_vga_reset() {
+        let mut vga = reset(SMALL);
+        for _ in 0..13 {
+            tick(&mut vga);
+        }
+        assert_eq!(value(&vga.output()[3..]), 13 % 8 + (1 << 4));
+        vga.input(&[false, true]);
+        vga.input(&[true, true]);
+        assert_eq!(value(&vga.output()[3..]), 0);
+        assert_eq!(value(&tick(&mut vga)[3..]), 1);
+    }
+
+    #[test]
+    fn test_vga_640x480_line() {
+        let mut vga = reset(VGA_640X480);
+        let mut hsync = Vec::new();
+        let mut visible = 0;
+        for n in 1..=800 {
+            let output = tick(&mut vga);
+            if output[0] {
+                hsync.push(n);
+            }
+            if output[2] {
+                visible += 1;
+            }
+        }
+        assert_eq!(hsync, (656..752).collect::<Vec<_>>());
+        // the pixel 0 is visible again after the line wraps around
+        assert_eq!(visible, 640);
+        assert_eq!(value(&vga.output()[13..]), 1);
+    }
+}
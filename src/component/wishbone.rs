@@ -0,0 +1,307 @@
+use std::fmt;
+
+use crate::{
+    circuit::{Endianness, Potential, Wire, layout::Layout},
+    component::{Component, edge::EdgeDetector},
+    simulation::protocol::{BusChecker, BusSample, Violation},
+};
+
+/// Get the layout of the inputs of a wishbone style slave.
+///
+/// the clock, the cycle, the strobe, the write enable, the address and the data to
+/// write. the cycle is high for a whole bus cycle, the strobe for a transfer in it.
+pub fn slave_inputs(address_width: usize, data_width: usize) -> Layout {
+    Layout::default()
+        .field("clock", 1)
+        .field("cyc", 1)
+        .field("stb", 1)
+        .field("we", 1)
+        .field("adr", address_width)
+        .field("dat_w", data_width)
+}
+
+/// Get the layout of the outputs of a wishbone style slave.
+///
+/// the ack, which ends a transfer, and the data read.
+pub fn slave_outputs(data_width: usize) -> Layout {
+    Layout::default().field("ack", 1).field("dat_r", data_width)
+}
+
+/// a slave of registers on a wishbone style bus.
+/// the pins follow [`slave_inputs`] and [`slave_outputs`].
+///
+/// a transfer is acked on the rising edge of the clock after the strobe, and the
+/// ack is high for a cycle. a write stores the data in the register at the address,
+/// a read drives the data of the register with the ack.
+///
+/// ```ascii
+///          ┌───────────┐
+/// clock────┤           │
+/// cyc──────┤           ├──────ack
+/// stb──────┤ Wishbone  │
+/// we───────┤ Registers ├──────dat_r
+/// adr──────┤           │
+/// dat_w────┤           │
+///          └───────────┘
+/// ```
+#[derive(Debug, Clone)]
+pub struct WishboneRegisters {
+    inputs: Layout,
+    outputs: Layout,
+    input: Vec<Wire>,
+    output: Vec<Wire>,
+    registers: Vec<u64>,
+    clock_edge: EdgeDetector,
+}
+
+impl WishboneRegisters {
+    /// Create `2^address_width` registers of `data_width` bits, which are 0.
+    pub fn new(address_width: usize, data_width: usize) -> Self {
+        assert!(
+            address_width > 0 && address_width <= 16,
+            "address_width must be between 1 and 16"
+        );
+        let inputs = slave_inputs(address_width, data_width);
+        let outputs = slave_outputs(data_width);
+        Self {
+            input: vec![Wire::default(); inputs.width()],
+            output: vec![Wire::default(); outputs.width()],
+            inputs,
+            outputs,
+            registers: vec![0; 1 << address_width],
+            clock_edge: EdgeDetector::default(),
+        }
+    }
+
+    pub fn registers(&self) -> &[u64] {
+        &self.registers
+    }
+
+    pub fn registers_mut(&mut self) -> &mut [u64] {
+        &mut self.registers
+    }
+
+    fn value(&self, field: &str) -> u64 {
+        let bits: Vec<Potential> = self.input.iter().map(|wire| wire.output()).collect();
+        self.inputs.read(&bits, field)
+    }
+}
+
+impl Component for WishboneRegisters {
+    fn get_pin_count(&self) -> (usize, usize) {
+        (self.input.len(), self.output.len())
+    }
+    fn set_pin_input(&mut self, position: usize, value: &Potential) {
+        assert!(
+            position < self.get_pin_count().0,
+            "position must be less than {}",
+            self.get_pin_count().0
+        );
+        self.input[position].input(value);
+    }
+    fn get_pin_output(&self, position: usize) -> Potential {
+        assert!(
+            position < self.get_pin_count().1,
+            "position must be less than {}",
+            self.get_pin_count().1
+        );
+        self.output[position].output()
+    }
+    fn update_state(&mut self) {
+        self.clock_edge.input(&[self.value("clock") == 1]);
+        if !self.clock_edge.is_rising() {
+            return;
+        }
+        let acked = self.output[0].output();
+        let mut bits = vec![false; self.outputs.width()];
+        // a transfer is acked once, the master drops the strobe after the ack
+        if self.value("cyc") == 1 && self.value("stb") == 1 && !acked {
+            let address = self.value("adr") as usize;
+            if self.value("we") == 1 {
+                self.registers[address] = self.value("dat_w");
+            }
+            self.outputs.set(&mut bits, "ack", 1);
+            self.outputs
+                .set(&mut bits, "dat_r", self.registers[address]);
+        }
+        for (wire, bit) in self.output.iter_mut().zip(bits) {
+            wire.input(&bit);
+        }
+    }
+}
+
+/// the errors of a transfer of [`WishboneMaster`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WishboneError {
+    /// the slave does not ack the transfer in time.
+    Timeout { address: u64 },
+    /// the transfer breaks the bus protocol.
+    Protocol(Violation),
+}
+
+impl fmt::Display for WishboneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Timeout { address } => write!(f, "no ack for address {:#x}", address),
+            Self::Protocol(violation) => write!(f, "{}", violation),
+        }
+    }
+}
+
+impl std::error::Error for WishboneError {}
+
+/// a host-side master of a wishbone style bus, which drives a slave with the pins of
+/// [`slave_inputs`] and [`slave_outputs`], and checks the transfers with a
+/// [`BusChecker`].
+///
+/// # Examples
+///
+/// ```
+/// use simulation_computer_rust::component::wishbone::{WishboneMaster, WishboneRegisters};
+///
+/// let mut slave = WishboneRegisters::new(4, 8);
+/// let mut master = WishboneMaster::new(&mut slave, 4, 8);
+/// master.write(3, 0xa5).unwrap();
+/// assert_eq!(master.read(3), Ok(0xa5));
+/// master.checker().assert_clean();
+/// ```
+pub struct WishboneMaster<'a> {
+    slave: &'a mut dyn Component,
+    inputs: Layout,
+    outputs: Layout,
+    checker: BusChecker,
+    timeout: usize,
+}
+
+impl<'a> WishboneMaster<'a> {
+    /// the cycles to wait for an ack.
+    pub const TIMEOUT: usize = 16;
+
+    pub fn new(slave: &'a mut dyn Component, address_width: usize, data_width: usize) -> Self {
+        let inputs = slave_inputs(address_width, data_width);
+        let outputs = slave_outputs(data_width);
+        assert!(
+            slave.get_pin_count() == (inputs.width(), outputs.width()),
+            "slave must have {} inputs and {} outputs",
+            inputs.width(),
+            outputs.width()
+        );
+        Self {
+            slave,
+            inputs,
+            outputs,
+            checker: BusChecker::default(),
+            timeout: Self::TIMEOUT,
+        }
+    }
+
+    pub fn checker(&self) -> &BusChecker {
+        &self.checker
+    }
+
+    /// Run a clock cycle with the signals, and get the outputs of the slave.
+    fn clock(&mut self, values: &[(&str, u64)]) -> Result<Vec<Potential>, WishboneError> {
+        let mut bits = self.inputs.pack(values).to_endian(Endianness::Little);
+        self.slave.input(&bits);
+        self.inputs.set(&mut bits, "clock", 1);
+        self.slave.input(&bits);
+        let output = self.slave.output();
+        let sample = BusSample {
+            drivers: 1,
+            strobe: self.inputs.read(&bits, "stb") == 1,
+            write: self.inputs.read(&bits, "we") == 1,
+            address: self.inputs.read(&bits, "adr"),
+            ack: self.outputs.read(&output, "ack") == 1,
+        };
+        self.checker
+            .observe(&sample)
+            .map_err(WishboneError::Protocol)?;
+        Ok(output)
+    }
+
+    fn transfer(&mut self, write: bool, address: u64, data: u64) -> Result<u64, WishboneError> {
+        let values = [
+            ("cyc", 1),
+            ("stb", 1),
+            ("we", write as u64),
+            ("adr", address),
+            ("dat_w", data),
+        ];
+        for _ in 0..self.timeout {
+            let output = self.clock(&values)?;
+            if self.outputs.read(&output, "ack") == 1 {
+                self.clock(&[])?;
+                return Ok(self.outputs.read(&output, "dat_r"));
+            }
+        }
+        // the checker records the strobe dropped without the ack
+        let _ = self.clock(&[]);
+        Err(WishboneError::Timeout { address })
+    }
+
+    /// Read the data at the address.
+    pub fn read(&mut self, address: u64) -> Result<u64, WishboneError> {
+        self.transfer(false, address, 0)
+    }
+
+    /// Write the data at the address.
+    pub fn write(&mut self, address: u64, data: u64) -> Result<(), WishboneError> {
+        self.transfer(true, address, data).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wishbone_layout() {
+        let inputs = slave_inputs(4, 8);
+        assert_eq!(inputs.width(), 16);
+        assert_eq!(inputs.range("adr"), 4..8);
+        assert_eq!(slave_outputs(8).width(), 9);
+    }
+
+    #[test]
+    fn test_wishbone_registers() {
+        let mut slave = WishboneRegisters::new(2, 4);
+        slave.registers_mut()[1] = 0x9;
+        let mut master = WishboneMaster::new(&mut slave, 2, 4);
+        assert_eq!(master.read(1), Ok(0x9));
+        master.write(2, 0x6).unwrap();
+        master.write(3, 0xf).unwrap();
+        assert_eq!(master.read(2), Ok(0x6));
+        assert_eq!(master.checker().cycle(), 8);
+        master.checker().assert_clean();
+        assert_eq!(slave.registers(), &[0, 0x9, 0x6, 0xf]);
+    }
+
+    /// a slave which never acks.
+    #[derive(Debug, Default)]
+    struct Deaf {
+        input: [Wire; 8],
+    }
+
+    impl Component for Deaf {
+        fn get_pin_count(&self) -> (usize, usize) {
+            (8, 3)
+        }
+        fn set_pin_input(&mut self, position: usize, value: &Potential) {
+            self.input[position].input(value);
+        }
+        fn get_pin_output(&self, _position: usize) -> Potential {
+            false
+        }
+        fn update_state(&mut self) {}
+    }
+
+    #[test]
+    fn test_wishbone_timeout() {
+        let mut slave = Deaf::default();
+        let mut master = WishboneMaster::new(&mut slave, 2, 2);
+        assert_eq!(master.read(2), Err(WishboneError::Timeout { address: 2 }));
+        assert_eq!(master.checker().cycle(), WishboneMaster::TIMEOUT + 1);
+        // the master gives up the transfer without the ack
+        assert_eq!(master.checker().violations().len(), 1);
+    }
+}
@@ -1,2 +1,13 @@
+// the components keep their acronyms upper case, like ANDGate and SRLatch, so the
+// ALU and the MPU are spelled out in full
+#![allow(clippy::upper_case_acronyms)]
+// the tests written before the clippy gate keep their original form
+#![cfg_attr(
+    test,
+    allow(unused_imports, clippy::bool_assert_comparison, clippy::useless_vec)
+)]
+
 pub mod circuit;
 pub mod component;
+pub mod loader;
+pub mod simulation;
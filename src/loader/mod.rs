@@ -0,0 +1,388 @@
+//!
+//! Loader module.
+//!
+//! This module loads program images for the simulated computer from the host.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use simulation_computer_rust::{component::rom::Rom, loader::ProgramImage};
+//!
+//! let image = ProgramImage::from_elf_file("program.elf").unwrap();
+//! let rom = Rom::new(&image.to_flat(0, 4096).unwrap());
+//! ```
+
+use std::{fs, io, path::Path};
+
+/// the program header type of a loadable segment.
+const PT_LOAD: u32 = 1;
+
+/// the largest segment in memory a program image may have, a larger `p_memsz` is
+/// rejected instead of being allocated.
+pub const MAX_SEGMENT_SIZE: usize = 1 << 24;
+
+/// the largest total size in memory of the segments of a program image, checked
+/// before any segment is allocated.
+pub const MAX_IMAGE_SIZE: usize = 1 << 26;
+
+/// a loadable segment of a program image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /// the address the segment is loaded at.
+    pub address: u64,
+    /// the bytes of the segment in memory, the bytes after the file content are 0.
+    pub data: Vec<u8>,
+}
+
+/// a program image of loadable segments and an entry point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramImage {
+    /// the address of the first instruction.
+    pub entry: u64,
+    pub segments: Vec<Segment>,
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// Convert a field of the file to a host size, which fails on a 32-bit host for large elf64 fields.
+fn to_usize(value: u64) -> io::Result<usize> {
+    usize::try_from(value).map_err(|_| invalid("elf field does not fit in memory"))
+}
+
+/// a reader of the fields of an elf file, in the class and the byte order of the file.
+struct ElfReader<'a> {
+    bytes: &'a [u8],
+    is_64: bool,
+    little_endian: bool,
+}
+
+impl ElfReader<'_> {
+    fn read(&self, offset: usize, size: usize) -> io::Result<u64> {
+        let field = offset
+            .checked_add(size)
+            .and_then(|end| self.bytes.get(offset..end))
+            .ok_or_else(|| invalid("elf file is truncated"))?;
+        let mut value = 0u64;
+        for i in 0..size {
+            let byte = if self.little_endian {
+                field[size - 1 - i]
+            } else {
+                field[i]
+            };
+            value = (value << 8) | byte as u64;
+        }
+        Ok(value)
+    }
+
+    /// Read an address or offset field, which is 4 bytes in elf32 and 8 bytes in elf64.
+    fn read_word(&self, offset: usize) -> io::Result<u64> {
+        self.read(offset, if self.is_64 { 8 } else { 4 })
+    }
+}
+
+impl ProgramImage {
+    /// Parse a statically linked elf executable, only the program headers are used.
+    /// a segment larger than [MAX_SEGMENT_SIZE] in memory, or segments larger than
+    /// [MAX_IMAGE_SIZE] in total, are rejected.
+    pub fn from_elf(bytes: &[u8]) -> io::Result<Self> {
+        if bytes.len() < 16 || bytes[0..4] != [0x7f, b'E', b'L', b'F'] {
+            return Err(invalid("not an elf file"));
+        }
+        let is_64 = match bytes[4] {
+            1 => false,
+            2 => true,
+            _ => return Err(invalid("unknown elf class")),
+        };
+        let little_endian = match bytes[5] {
+            1 => true,
+            2 => false,
+            _ => return Err(invalid("unknown elf byte order")),
+        };
+        let reader = ElfReader {
+            bytes,
+            is_64,
+            little_endian,
+        };
+        // the offsets of the fields after e_entry move with the word size
+        let word = if is_64 { 8 } else { 4 };
+        let entry = reader.read_word(24)?;
+        let ph_offset = to_usize(reader.read_word(24 + word)?)?;
+        let ph_size = to_usize(reader.read(24 + word * 3 + 6, 2)?)?;
+        let ph_count = to_usize(reader.read(24 + word * 3 + 8, 2)?)?;
+        // the fields of a program header must fit in e_phentsize
+        if ph_count > 0 && ph_size < if is_64 { 56 } else { 32 } {
+            return Err(invalid("program header is too small"));
+        }
+
+        // check every loadable segment before any of them is allocated
+        let mut loads = Vec::new();
+        let mut total_size: usize = 0;
+        for i in 0..ph_count {
+            let header = i
+                .checked_mul(ph_size)
+                .and_then(|start| start.checked_add(ph_offset))
+                .ok_or_else(|| invalid("elf file is truncated"))?;
+            if reader.read(header, 4)? as u32 != PT_LOAD {
+                continue;
+            }
+            // elf64 moves p_flags after p_type, elf32 keeps it after p_memsz
+            let fields = header
+                .checked_add(if is_64 { 8 } else { 4 })
+                .ok_or_else(|| invalid("elf file is truncated"))?;
+            let field = |index: usize| {
+                fields
+                    .checked_add(word * index)
+                    .ok_or_else(|| invalid("elf file is truncated"))
+                    .and_then(|offset| reader.read_word(offset))
+            };
+            let offset = to_usize(field(0)?)?;
+            let address = field(1)?;
+            let file_size = to_usize(field(3)?)?;
+            let memory_size = to_usize(field(4)?)?;
+            if memory_size > MAX_SEGMENT_SIZE {
+                return Err(invalid("segment is too large"));
+            }
+            if file_size > memory_size {
+                return Err(invalid("segment is larger in the file than in memory"));
+            }
+            total_size = total_size
+                .checked_add(memory_size)
+                .filter(|total| *total <= MAX_IMAGE_SIZE)
+                .ok_or_else(|| invalid("program image is too large"))?;
+            let content = offset
+                .checked_add(file_size)
+                .and_then(|end| bytes.get(offset..end))
+                .ok_or_else(|| invalid("elf file is truncated"))?;
+            loads.push((address, content, memory_size));
+        }
+        let segments = loads
+            .into_iter()
+            .map(|(address, content, memory_size)| {
+                let mut data = content.to_vec();
+                data.resize(memory_size, 0);
+                Segment { address, data }
+            })
+            .collect();
+        Ok(Self { entry, segments })
+    }
+
+    /// Load a statically linked elf executable from a host file.
+    pub fn from_elf_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_elf(&fs::read(path)?)
+    }
+
+    /// Lay the segments out in a flat memory of `size` bytes starting at `base`,
+    /// the bytes not covered by a segment are 0.
+    pub fn to_flat(&self, base: u64, size: usize) -> io::Result<Vec<u8>> {
+        let mut memory = vec![0; size];
+        for segment in self.segments.iter() {
+            let (start, end) = segment
+                .address
+                .checked_sub(base)
+                .and_then(|start| usize::try_from(start).ok())
+                .and_then(|start| Some((start, start.checked_add(segment.data.len())?)))
+                .filter(|(_, end)| *end <= size)
+                .ok_or_else(|| invalid("segment is out of the memory"))?;
+            memory[start..end].copy_from_slice(&segment.data);
+        }
+        Ok(memory)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    /// build an elf executable with the segments, of the class and the byte order.
+    fn build_elf(
+        is_64: bool,
+        little_endian: bool,
+        entry: u64,
+        segments: &[(u32, u64, &[u8], u64)],
+    ) -> Vec<u8> {
+        let word = if is_64 { 8 } else { 4 };
+        let header_size = if is_64 { 64 } else { 52 };
+        let ph_size = if is_64 { 56 } else { 32 };
+        let put = |bytes: &mut Vec<u8>, value: u64, size: usize| {
+            let mut field: Vec<u8> = (0..size).map(|i| (value >> (i * 8)) as u8).collect();
+            if !little_endian {
+                field.reverse();
+            }
+            bytes.extend(field);
+        };
+        let mut bytes = vec![0x7f, b'E', b'L', b'F'];
+        bytes.push(if is_64 { 2 } else { 1 });
+        bytes.push(if little_endian { 1 } else { 2 });
+        // ei_version
+        bytes.push(1);
+        bytes.resize(16, 0);
+        // e_type, e_machine, e_version
+        put(&mut bytes, 2, 2);
+        put(&mut bytes, 0xf3, 2);
+        put(&mut bytes, 1, 4);
+        put(&mut bytes, entry, word);
+        put(&mut bytes, header_size as u64, word);
+        put(&mut bytes, 0, word);
+        // e_flags, e_ehsize, e_phentsize, e_phnum, e_shentsize, e_shnum, e_shstrndx
+        put(&mut bytes, 0, 4);
+        put(&mut bytes, header_size as u64, 2);
+        put(&mut bytes, ph_size as u64, 2);
+        put(&mut bytes, segments.len() as u64, 2);
+        put(&mut bytes, 0, 6);
+        assert_eq!(bytes.len(), header_size);
+
+        let mut offset = (header_size + ph_size * segments.len()) as u64;
+        for (kind, address, data, memory_size) in segments {
+            put(&mut bytes, *kind as u64, 4);
+            if is_64 {
+                put(&mut bytes, 5, 4);
+            }
+            put(&mut bytes, offset, word);
+            put(&mut bytes, *address, word);
+            put(&mut bytes, *address, word);
+            put(&mut bytes, data.len() as u64, word);
+            put(&mut bytes, *memory_size, word);
+            if !is_64 {
+                put(&mut bytes, 5, 4);
+            }
+            put(&mut bytes, 4, word);
+            offset += data.len() as u64;
+        }
+        for (_, _, data, _) in segments {
+            bytes.extend(data.iter());
+        }
+        bytes
+    }
+
+    #[rstest]
+    #[case(false, true)]
+    #[case(false, false)]
+    #[case(true, true)]
+    #[case(true, false)]
+    fn test_from_elf(#[case] is_64: bool, #[case] little_endian: bool) {
+        let elf = build_elf(
+            is_64,
+            little_endian,
+            0x1004,
+            &[
+                (PT_LOAD, 0x1000, &[1, 2, 3, 4, 5, 6, 7, 8], 8),
+                (4, 0x0, &[9, 9], 2),
+                (PT_LOAD, 0x2000, &[0xaa], 4),
+            ],
+        );
+        let image = ProgramImage::from_elf(&elf).unwrap();
+        assert_eq!(image.entry, 0x1004);
+        assert_eq!(
+            image.segments,
+            vec![
+                Segment {
+                    address: 0x1000,
+                    data: vec![1, 2, 3, 4, 5, 6, 7, 8],
+                },
+                Segment {
+                    address: 0x2000,
+                    data: vec![0xaa, 0, 0, 0],
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(vec![])]
+    #[case(b"\x7fELF".to_vec())]
+    #[case(b"MZ\x90\x00\x03\x00\x00\x00\x04\x00\x00\x00\xff\xff\x00\x00".to_vec())]
+    fn test_from_elf_invalid(#[case] bytes: Vec<u8>) {
+        assert!(ProgramImage::from_elf(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_from_elf_truncated() {
+        let mut elf = build_elf(false, true, 0, &[(PT_LOAD, 0, &[1, 2, 3, 4], 4)]);
+        elf.truncate(elf.len() - 1);
+        assert!(ProgramImage::from_elf(&elf).is_err());
+    }
+
+    #[test]
+    fn test_to_flat() {
+        let image = ProgramImage {
+            entry: 0x100,
+            segments: vec![
+                Segment {
+                    address: 0x100,
+                    data: vec![1, 2],
+                },
+                Segment {
+                    address: 0x104,
+                    data: vec![3],
+                },
+            ],
+        };
+        assert_eq!(image.to_flat(0x100, 6).unwrap(), vec![1, 2, 0, 0, 3, 0]);
+        assert!(image.to_flat(0x100, 4).is_err());
+        assert!(image.to_flat(0x102, 8).is_err());
+    }
+
+    #[test]
+    fn test_to_flat_address_overflow() {
+        let image = ProgramImage {
+            entry: 0,
+            segments: vec![Segment {
+                address: u64::MAX,
+                data: vec![1, 2],
+            }],
+        };
+        assert!(image.to_flat(0, 16).is_err());
+    }
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    fn test_from_elf_segment_too_large(#[case] is_64: bool) {
+        let elf = build_elf(is_64, true, 0, &[(PT_LOAD, 0, &[1], 0xffff_ffff)]);
+        assert_eq!(
+            ProgramImage::from_elf(&elf).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn test_from_elf_image_too_large() {
+        let segment: (u32, u64, &[u8], u64) = (PT_LOAD, 0, &[1], MAX_SEGMENT_SIZE as u64);
+        let count = MAX_IMAGE_SIZE / MAX_SEGMENT_SIZE;
+        let elf = build_elf(true, true, 0, &vec![segment; count]);
+        assert!(ProgramImage::from_elf(&elf).is_ok());
+        let elf = build_elf(true, true, 0, &vec![segment; count + 1]);
+        assert_eq!(
+            ProgramImage::from_elf(&elf).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[rstest]
+    #[case(false, 42, 20)]
+    #[case(true, 54, 40)]
+    fn test_from_elf_program_header_too_small(
+        #[case] is_64: bool,
+        #[case] ph_size_at: usize,
+        #[case] ph_size: u16,
+    ) {
+        let mut elf = build_elf(is_64, true, 0, &[(PT_LOAD, 0, &[1], 1)]);
+        // e_phentsize
+        elf[ph_size_at..ph_size_at + 2].copy_from_slice(&ph_size.to_le_bytes());
+        assert_eq!(
+            ProgramImage::from_elf(&elf).unwrap_err().to_string(),
+            "program header is too small"
+        );
+    }
+
+    #[test]
+    fn test_from_elf_header_offset_overflow() {
+        let mut elf = build_elf(true, true, 0, &[(PT_LOAD, 0, &[1], 1)]);
+        // e_phoff
+        elf[32..40].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(ProgramImage::from_elf(&elf).is_err());
+    }
+}
@@ -0,0 +1,361 @@
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    io::{self, Write},
+};
+
+use crate::{
+    circuit::Potential,
+    component::Component,
+    simulation::signal::{SignalDb, SignalId},
+};
+
+/// Quote a string as a json string.
+fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(quoted, "\\u{:04x}", c as u32);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// the format of an [`EventLog`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// a json object per line, see [`EventLog`].
+    JsonLines,
+    /// the json array of the chrome trace-event format, which is opened by
+    /// `about:tracing` or Perfetto. a cycle is a microsecond of the trace, a signal is
+    /// a counter, and every kind of event is a track of its own, so the activity of
+    /// the devices is shown side by side.
+    TraceEvent,
+}
+
+/// a writer of the events of a simulation as json lines.
+///
+/// every event is a json object on its own line with the `cycle` it happens in and
+/// its `kind`, so the log of a long run is streamed to the writer and read back by
+/// `jq` or pandas without a bespoke parser:
+///
+/// ```text
+/// {"cycle":3,"kind":"signal","name":"timer.irq","value":1}
+/// {"cycle":5,"kind":"bus","text":"read 0x0040"}
+/// {"cycle":6,"kind":"irq","text":"timer","cycles":4}
+/// ```
+///
+/// a signal is logged only when its value changes, the first value of a signal is
+/// always logged. the same events are written as chrome trace events by
+/// [`EventLog::trace_event`].
+///
+/// # Examples
+///
+/// ```
+/// use simulation_computer_rust::simulation::{event_log::EventLog, signal::SignalDb};
+///
+/// let mut db = SignalDb::default();
+/// let irq = db.register("timer.irq").unwrap();
+/// let mut log = EventLog::new(Vec::new());
+/// log.signal(1, &db, irq, false).unwrap();
+/// log.signal(2, &db, irq, false).unwrap();
+/// log.signal(3, &db, irq, true).unwrap();
+/// let text = String::from_utf8(log.into_inner()).unwrap();
+/// assert_eq!(text.lines().count(), 2);
+/// ```
+#[derive(Debug)]
+pub struct EventLog<W: Write> {
+    writer: W,
+    format: LogFormat,
+    values: HashMap<SignalId, Potential>,
+    // the thread ids of the tracks of the trace, by the kind of the events
+    tracks: HashMap<String, usize>,
+    written: usize,
+}
+
+impl<W: Write> EventLog<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            format: LogFormat::JsonLines,
+            values: HashMap::new(),
+            tracks: HashMap::new(),
+            written: 0,
+        }
+    }
+
+    /// Create a log of chrome trace events, the array is opened at once and closed by
+    /// [`EventLog::finish`]. a trace which is not finished is still loaded by the
+    /// viewers.
+    pub fn trace_event(mut writer: W) -> io::Result<Self> {
+        writeln!(writer, "[")?;
+        Ok(Self {
+            format: LogFormat::TraceEvent,
+            ..Self::new(writer)
+        })
+    }
+
+    /// Get the format of the log.
+    pub fn format(&self) -> LogFormat {
+        self.format
+    }
+
+    fn write_record(&mut self, record: &str) -> io::Result<()> {
+        match self.format {
+            LogFormat::JsonLines => writeln!(self.writer, "{}", record)?,
+            LogFormat::TraceEvent if self.written == 0 => write!(self.writer, "{}", record)?,
+            LogFormat::TraceEvent => write!(self.writer, ",\n{}", record)?,
+        }
+        self.written += 1;
+        Ok(())
+    }
+
+    /// Get the thread id of the track of a kind, the track is named when it is new.
+    fn track(&mut self, kind: &str) -> io::Result<usize> {
+        if let Some(tid) = self.tracks.get(kind) {
+            return Ok(*tid);
+        }
+        let tid = self.tracks.len() + 1;
+        self.tracks.insert(kind.to_string(), tid);
+        self.write_record(&format!(
+            "{{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":0,\"tid\":{},\"args\":{{\"name\":{}}}}}",
+            tid,
+            quote(kind)
+        ))?;
+        Ok(tid)
+    }
+
+    /// Log the value of a signal, when it changes.
+    pub fn signal(
+        &mut self,
+        cycle: usize,
+        db: &SignalDb,
+        id: SignalId,
+        value: Potential,
+    ) -> io::Result<()> {
+        if self.values.insert(id, value) == Some(value) {
+            return Ok(());
+        }
+        let record = match self.format {
+            LogFormat::JsonLines => format!(
+                "{{\"cycle\":{},\"kind\":\"signal\",\"name\":{},\"value\":{}}}",
+                cycle,
+                quote(db.name(id)),
+                value as u8
+            ),
+            LogFormat::TraceEvent => format!(
+                "{{\"name\":{},\"ph\":\"C\",\"ts\":{},\"pid\":0,\"args\":{{\"value\":{}}}}}",
+                quote(db.name(id)),
+                cycle,
+                value as u8
+            ),
+        };
+        self.write_record(&record)
+    }
+
+    /// Log the output pins of a component, the pin `i` is the signal `ids[i]`.
+    pub fn outputs<C: Component + ?Sized>(
+        &mut self,
+        cycle: usize,
+        db: &SignalDb,
+        component: &C,
+        ids: &[SignalId],
+    ) -> io::Result<()> {
+        assert!(
+            ids.len() <= component.get_pin_count().1,
+            "ids must not be more than the output pins"
+        );
+        for (i, id) in ids.iter().enumerate() {
+            self.signal(cycle, db, *id, component.get_pin_output(i))?;
+        }
+        Ok(())
+    }
+
+    /// Log an event of the `kind` with a text, e.g. a bus transaction or a retired
+    /// instruction.
+    pub fn event(&mut self, cycle: usize, kind: &str, text: &str) -> io::Result<()> {
+        let record = match self.format {
+            LogFormat::JsonLines => format!(
+                "{{\"cycle\":{},\"kind\":{},\"text\":{}}}",
+                cycle,
+                quote(kind),
+                quote(text)
+            ),
+            LogFormat::TraceEvent => format!(
+                "{{\"name\":{},\"cat\":{},\"ph\":\"i\",\"s\":\"t\",\"ts\":{},\"pid\":0,\"tid\":{}}}",
+                quote(text),
+                quote(kind),
+                cycle,
+                self.track(kind)?
+            ),
+        };
+        self.write_record(&record)
+    }
+
+    /// Log an event of the `kind` which lasts the `cycles` from the `cycle`, e.g. an
+    /// interrupt handler or a dma transfer.
+    pub fn span(&mut self, cycle: usize, cycles: usize, kind: &str, text: &str) -> io::Result<()> {
+        let record = match self.format {
+            LogFormat::JsonLines => format!(
+                "{{\"cycle\":{},\"kind\":{},\"text\":{},\"cycles\":{}}}",
+                cycle,
+                quote(kind),
+                quote(text),
+                cycles
+            ),
+            LogFormat::TraceEvent => format!(
+                "{{\"name\":{},\"cat\":{},\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":0,\"tid\":{}}}",
+                quote(text),
+                quote(kind),
+                cycle,
+                cycles,
+                self.track(kind)?
+            ),
+        };
+        self.write_record(&record)
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Close the log and unwrap the writer, the array of a trace is closed.
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.format == LogFormat::TraceEvent {
+            writeln!(self.writer, "\n]")?;
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Unwrap the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{component::counter::RingCounterN, simulation::Runner};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("plain", "\"plain\"")]
+    #[case("a \"b\"", "\"a \\\"b\\\"\"")]
+    #[case("c:\\dir\n", "\"c:\\\\dir\\n\"")]
+    #[case("\u{1}", "\"\\u0001\"")]
+    fn test_quote(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(quote(text), expected);
+    }
+
+    #[test]
+    fn test_event_log_changes() {
+        let mut db = SignalDb::default();
+        let a = db.register("a").unwrap();
+        let b = db.register("b").unwrap();
+        let mut log = EventLog::new(Vec::new());
+        log.signal(0, &db, a, false).unwrap();
+        log.signal(0, &db, b, false).unwrap();
+        log.signal(1, &db, a, false).unwrap();
+        log.signal(1, &db, b, true).unwrap();
+        log.event(2, "bus", "write \"x\"").unwrap();
+        assert_eq!(
+            String::from_utf8(log.into_inner()).unwrap(),
+            concat!(
+                "{\"cycle\":0,\"kind\":\"signal\",\"name\":\"a\",\"value\":0}\n",
+                "{\"cycle\":0,\"kind\":\"signal\",\"name\":\"b\",\"value\":0}\n",
+                "{\"cycle\":1,\"kind\":\"signal\",\"name\":\"b\",\"value\":1}\n",
+                "{\"cycle\":2,\"kind\":\"bus\",\"text\":\"write \\\"x\\\"\"}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_event_log_span() {
+        let mut log = EventLog::new(Vec::new());
+        log.span(6, 4, "irq", "timer").unwrap();
+        assert_eq!(log.format(), LogFormat::JsonLines);
+        assert_eq!(
+            String::from_utf8(log.finish().unwrap()).unwrap(),
+            "{\"cycle\":6,\"kind\":\"irq\",\"text\":\"timer\",\"cycles\":4}\n"
+        );
+    }
+
+    #[test]
+    fn test_event_log_trace_event() {
+        let mut db = SignalDb::default();
+        let irq = db.register("timer.irq").unwrap();
+        let mut log = EventLog::trace_event(Vec::new()).unwrap();
+        assert_eq!(log.format(), LogFormat::TraceEvent);
+        log.signal(3, &db, irq, true).unwrap();
+        log.signal(4, &db, irq, true).unwrap();
+        log.event(5, "bus", "read 0x0040").unwrap();
+        log.span(6, 4, "irq", "timer").unwrap();
+        log.event(7, "bus", "write 0x0040").unwrap();
+        assert_eq!(
+            String::from_utf8(log.finish().unwrap()).unwrap(),
+            concat!(
+                "[\n",
+                "{\"name\":\"timer.irq\",\"ph\":\"C\",\"ts\":3,\"pid\":0,\"args\":{\"value\":1}},\n",
+                "{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":0,\"tid\":1,\"args\":{\"name\":\"bus\"}},\n",
+                "{\"name\":\"read 0x0040\",\"cat\":\"bus\",\"ph\":\"i\",\"s\":\"t\",\"ts\":5,\"pid\":0,\"tid\":1},\n",
+                "{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":0,\"tid\":2,\"args\":{\"name\":\"irq\"}},\n",
+                "{\"name\":\"timer\",\"cat\":\"irq\",\"ph\":\"X\",\"ts\":6,\"dur\":4,\"pid\":0,\"tid\":2},\n",
+                "{\"name\":\"write 0x0040\",\"cat\":\"bus\",\"ph\":\"i\",\"s\":\"t\",\"ts\":7,\"pid\":0,\"tid\":1}\n",
+                "]\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_event_log_empty_trace_event() {
+        let log = EventLog::trace_event(Vec::new()).unwrap();
+        assert_eq!(
+            String::from_utf8(log.finish().unwrap()).unwrap(),
+            "[\n\n]\n"
+        );
+    }
+
+    #[test]
+    fn test_event_log_run() {
+        let mut db = SignalDb::default();
+        let ring = db.register("ring").unwrap();
+        let ids: Vec<SignalId> = (0..3)
+            .map(|i| db.register_child(ring, &format!("q{}", i)).unwrap())
+            .collect();
+        let mut counter = RingCounterN::new(3);
+        counter.input(&[false, true]);
+        counter.input(&[true, true]);
+        counter.input(&[false, false]);
+        let mut log = EventLog::new(Vec::new());
+        Runner::new(3).run(&mut counter, 0, |counter, cycle| {
+            log.outputs(cycle, &db, counter, &ids).unwrap();
+            None
+        });
+        let text = String::from_utf8(log.into_inner()).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        // the first cycle logs every signal, then the bit moves by one per cycle
+        assert_eq!(lines.len(), 3 + 2 * 2);
+        assert_eq!(
+            lines[0],
+            "{\"cycle\":1,\"kind\":\"signal\",\"name\":\"ring.q0\",\"value\":0}"
+        );
+        assert!(
+            lines
+                .iter()
+                .skip(3)
+                .all(|line| !line.contains("\"cycle\":1,"))
+        );
+    }
+}
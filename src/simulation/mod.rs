@@ -0,0 +1,319 @@
+//!
+//! Simulation module.
+//!
+//! This module drives the clock of a component with a cycle budget, so a design which
+//! never stops can not hang the caller, and reports the statistics of the run.
+//!
+//! # Examples
+//!
+//! ```
+//! use simulation_computer_rust::component::{Component, counter::CounterN};
+//! use simulation_computer_rust::simulation::{RunResult, run};
+//!
+//! // reset the counter on a rising edge, then halt when the count reaches 5
+//! let mut counter = CounterN::new(4);
+//! counter.input(&[false, true, false]);
+//! counter.input(&[true, true, false]);
+//! counter.prepare_input(&[false, false, false]);
+//! let result = run(&mut counter, 0, 100, |counter, _| {
+//!     (counter.output() == [true, false, true, false]).then_some(RunResult::Halted)
+//! });
+//! assert_eq!(result, (RunResult::Halted, 5));
+//! ```
+
+use std::time::{Duration, Instant};
+
+use crate::component::Component;
+
+pub mod event_log;
+pub mod protocol;
+pub mod signal;
+pub mod toggle;
+
+/// the reason a run stops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunResult {
+    /// the design halts by itself.
+    Halted,
+    /// a breakpoint of the caller is hit.
+    Breakpoint,
+    /// the cycle budget runs out.
+    CycleLimit,
+    /// the wall-clock budget runs out.
+    TimeLimit,
+    /// the caller aborts the run from the progress callback.
+    Aborted,
+    /// the design raises a fault.
+    Fault,
+}
+
+/// the statistics of a run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunStats {
+    /// the clock cycles run.
+    pub cycles: usize,
+    /// the calls of `update_state` on the component, two per cycle.
+    pub updates: usize,
+    /// the wall-clock time of the run.
+    pub elapsed: Duration,
+}
+
+impl RunStats {
+    /// Get the average wall-clock time of a simulated cycle.
+    pub fn per_cycle(&self) -> Duration {
+        if self.cycles == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64(self.elapsed.as_secs_f64() / self.cycles as f64)
+    }
+
+    /// Get the simulated cycles per wall-clock second.
+    pub fn cycles_per_second(&self) -> f64 {
+        if self.elapsed.is_zero() {
+            return 0.0;
+        }
+        self.cycles as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+/// the callback of the progress of a run.
+pub type Progress<'a> = Box<dyn FnMut(&RunStats) + 'a>;
+
+/// a runner of a component with a cycle budget, and an optional progress callback.
+pub struct Runner<'a> {
+    max_cycles: usize,
+    time_limit: Option<Duration>,
+    progress_every: usize,
+    progress: Option<Progress<'a>>,
+}
+
+impl<'a> Runner<'a> {
+    pub fn new(max_cycles: usize) -> Self {
+        Self {
+            max_cycles,
+            time_limit: None,
+            progress_every: 0,
+            progress: None,
+        }
+    }
+
+    /// Call `progress` with the statistics so far after every `every` cycles.
+    pub fn on_progress<P: FnMut(&RunStats) + 'a>(mut self, every: usize, progress: P) -> Self {
+        assert!(every > 0, "every must be greater than 0");
+        self.progress_every = every;
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    /// Stop the run with [`RunResult::TimeLimit`] after the wall-clock time, so a
+    /// runaway design does not freeze the caller.
+    pub fn with_time_limit(mut self, limit: Duration) -> Self {
+        self.time_limit = Some(limit);
+        self
+    }
+
+    /// Run the component for at most `max_cycles` clock cycles.
+    ///
+    /// every cycle drives the `clock` pin low and then high, and then calls `check` with
+    /// the component and the cycles run so far. the run stops when `check` returns a
+    /// result, with [`RunResult::CycleLimit`] when the budget runs out, or with
+    /// [`RunResult::TimeLimit`] when the time limit passes.
+    ///
+    /// # Returns
+    /// the reason the run stops, and the statistics of the run.
+    pub fn run<C, F>(
+        &mut self,
+        component: &mut C,
+        clock: usize,
+        mut check: F,
+    ) -> (RunResult, RunStats)
+    where
+        C: Component,
+        F: FnMut(&C, usize) -> Option<RunResult>,
+    {
+        let start = Instant::now();
+        let mut stats = RunStats::default();
+        for cycle in 1..=self.max_cycles {
+            component.set_pin_input(clock, &false);
+            component.update_state();
+            component.set_pin_input(clock, &true);
+            component.update_state();
+            stats.cycles = cycle;
+            stats.updates += 2;
+            if let Some(progress) = self.progress.as_mut()
+                && cycle % self.progress_every == 0
+            {
+                stats.elapsed = start.elapsed();
+                progress(&stats);
+            }
+            if let Some(result) = check(component, cycle) {
+                stats.elapsed = start.elapsed();
+                return (result, stats);
+            }
+            if let Some(limit) = self.time_limit
+                && start.elapsed() >= limit
+            {
+                stats.elapsed = start.elapsed();
+                return (RunResult::TimeLimit, stats);
+            }
+        }
+        stats.elapsed = start.elapsed();
+        (RunResult::CycleLimit, stats)
+    }
+}
+
+/// Run the component for at most `max_cycles` clock cycles, see [`Runner::run`].
+///
+/// # Returns
+/// the reason the run stops, and the cycles run.
+pub fn run<C, F>(component: &mut C, clock: usize, max_cycles: usize, check: F) -> (RunResult, usize)
+where
+    C: Component,
+    F: FnMut(&C, usize) -> Option<RunResult>,
+{
+    let (result, stats) = Runner::new(max_cycles).run(component, clock, check);
+    (result, stats.cycles)
+}
+
+/// the cycles between the progress callbacks of [`run_with_limits`].
+pub const LIMITS_PROGRESS_EVERY: usize = 1000;
+
+/// Run the component with a cycle budget and a wall-clock budget, see [`Runner::run`].
+///
+/// `progress` is called with the statistics every [`LIMITS_PROGRESS_EVERY`] cycles,
+/// so a user interface stays responsive, and returns false to abort the run with
+/// [`RunResult::Aborted`]. the component keeps the state where the run stops.
+///
+/// # Returns
+/// the reason the run stops, and the statistics of the run.
+pub fn run_with_limits<C, P>(
+    component: &mut C,
+    clock: usize,
+    cycle_limit: usize,
+    wall_clock_limit: Duration,
+    mut progress: P,
+) -> (RunResult, RunStats)
+where
+    C: Component,
+    P: FnMut(&RunStats) -> bool,
+{
+    let aborted = std::cell::Cell::new(false);
+    Runner::new(cycle_limit)
+        .with_time_limit(wall_clock_limit)
+        .on_progress(LIMITS_PROGRESS_EVERY, |stats| {
+            if !progress(stats) {
+                aborted.set(true);
+            }
+        })
+        .run(component, clock, |_, _| {
+            aborted.get().then_some(RunResult::Aborted)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::{
+        counter::{CounterN, RingCounterN},
+        timer::TimerN,
+    };
+    use rstest::rstest;
+
+    #[test]
+    fn test_run_cycle_limit() {
+        // a ring counter never stops by itself
+        let mut counter = RingCounterN::new(4);
+        counter.input(&[false, true]);
+        counter.input(&[true, true]);
+        counter.input(&[false, false]);
+        let result = run(&mut counter, 0, 10, |_, _| None);
+        assert_eq!(result, (RunResult::CycleLimit, 10));
+        // 10 cycles moves the bit 0 to the bit 2
+        assert_eq!(counter.output(), vec![false, false, true, false]);
+    }
+
+    #[test]
+    fn test_run_zero_cycles() {
+        let mut counter = RingCounterN::new(4);
+        let result = run(&mut counter, 0, 0, |_, _| Some(RunResult::Halted));
+        assert_eq!(result, (RunResult::CycleLimit, 0));
+    }
+
+    #[rstest]
+    #[case(RunResult::Halted)]
+    #[case(RunResult::Breakpoint)]
+    #[case(RunResult::Fault)]
+    fn test_run_stops_on_check(#[case] reason: RunResult) {
+        let mut counter = CounterN::new(4);
+        counter.input(&[false, true, false]);
+        counter.input(&[true, true, false]);
+        counter.input(&[false, false, false]);
+        let result = run(&mut counter, 0, 100, |counter, cycle| {
+            assert_eq!(counter.get_pin_output(0), cycle % 2 == 1);
+            (cycle == 7).then_some(reason)
+        });
+        assert_eq!(result, (reason, 7));
+    }
+
+    #[test]
+    fn test_runner_stats() {
+        let mut counter = RingCounterN::new(4);
+        let (result, stats) = Runner::new(25).run(&mut counter, 0, |_, _| None);
+        assert_eq!(result, RunResult::CycleLimit);
+        assert_eq!((stats.cycles, stats.updates), (25, 50));
+        assert!(stats.per_cycle() <= stats.elapsed);
+        assert_eq!(RunStats::default().per_cycle(), Duration::ZERO);
+        assert_eq!(RunStats::default().cycles_per_second(), 0.0);
+    }
+
+    #[test]
+    fn test_run_stats_per_cycle_large() {
+        let stats = RunStats {
+            cycles: 1 << 32,
+            elapsed: Duration::from_secs(1 << 32),
+            ..Default::default()
+        };
+        assert_eq!(stats.per_cycle(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_runner_progress() {
+        let mut reports = Vec::new();
+        let mut timer = TimerN::new(4);
+        let (result, stats) = Runner::new(100)
+            .on_progress(3, |stats| reports.push(stats.cycles))
+            .run(&mut timer, 0, |_, cycle| {
+                (cycle == 10).then_some(RunResult::Halted)
+            });
+        assert_eq!((result, stats.cycles), (RunResult::Halted, 10));
+        assert_eq!(reports, vec![3, 6, 9]);
+    }
+
+    #[test]
+    fn test_runner_time_limit() {
+        let mut counter = RingCounterN::new(4);
+        let (result, stats) = Runner::new(usize::MAX)
+            .with_time_limit(Duration::from_millis(20))
+            .run(&mut counter, 0, |_, _| None);
+        assert_eq!(result, RunResult::TimeLimit);
+        assert!(stats.cycles > 0);
+        assert!(stats.elapsed >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn test_run_with_limits() {
+        let mut counter = RingCounterN::new(4);
+        let second = Duration::from_secs(60);
+        let (result, stats) = run_with_limits(&mut counter, 0, 2500, second, |_| true);
+        assert_eq!((result, stats.cycles), (RunResult::CycleLimit, 2500));
+
+        // the progress aborts the run on its second call
+        let mut calls = 0;
+        let (result, stats) = run_with_limits(&mut counter, 0, 10_000, second, |stats| {
+            calls += 1;
+            stats.cycles < 2000
+        });
+        assert_eq!((result, stats.cycles), (RunResult::Aborted, 2000));
+        assert_eq!(calls, 2);
+    }
+}
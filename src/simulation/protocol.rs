@@ -0,0 +1,212 @@
+use std::fmt;
+
+/// a sample of the signals of a bus in a clock cycle.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BusSample {
+    /// the drivers which drive the data lines.
+    pub drivers: usize,
+    /// the strobe of the master, high while a transfer waits for its ack.
+    pub strobe: bool,
+    /// whether the transfer is a write.
+    pub write: bool,
+    /// the address of the transfer.
+    pub address: u64,
+    /// the ack of the slave, which ends the transfer.
+    pub ack: bool,
+}
+
+/// the rules of the bus protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// more than one driver drives the data lines.
+    Contention,
+    /// the slave acks without a strobe.
+    AckWithoutStrobe,
+    /// the master drops the strobe before the ack.
+    StrobeDropped,
+    /// the address changes before the ack.
+    AddressChanged,
+    /// the write bit changes before the ack.
+    WriteChanged,
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Contention => write!(f, "more than one driver on the bus"),
+            Self::AckWithoutStrobe => write!(f, "ack without strobe"),
+            Self::StrobeDropped => write!(f, "strobe dropped before ack"),
+            Self::AddressChanged => write!(f, "address changed before ack"),
+            Self::WriteChanged => write!(f, "write changed before ack"),
+        }
+    }
+}
+
+/// a violation of a rule, in a clock cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub cycle: usize,
+    pub rule: Rule,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cycle {}: {}", self.cycle, self.rule)
+    }
+}
+
+impl std::error::Error for Violation {}
+
+/// a monitor of the protocol of a bus, which checks a [`BusSample`] every clock cycle
+/// and records the violations of the [`Rule`]s.
+///
+/// a transfer starts when the strobe is high, and ends with the ack of the slave. the
+/// master must keep the strobe, the address and the write bit until the ack.
+///
+/// # Examples
+///
+/// ```
+/// use simulation_computer_rust::simulation::protocol::{BusChecker, BusSample, Rule};
+///
+/// let mut checker = BusChecker::default();
+/// let read = BusSample { drivers: 1, strobe: true, address: 0x40, ..Default::default() };
+/// checker.observe(&read).unwrap();
+/// let moved = BusSample { address: 0x44, ..read };
+/// let violation = checker.observe(&moved).unwrap_err();
+/// assert_eq!((violation.cycle, violation.rule), (1, Rule::AddressChanged));
+/// assert_eq!(violation.to_string(), "cycle 1: address changed before ack");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BusChecker {
+    cycle: usize,
+    pending: Option<BusSample>,
+    violations: Vec<Violation>,
+}
+
+impl BusChecker {
+    /// Check the sample of the current cycle, and move to the next cycle.
+    ///
+    /// # Returns
+    /// the first violation of the cycle, all of them are recorded.
+    pub fn observe(&mut self, sample: &BusSample) -> Result<(), Violation> {
+        let mut rules = Vec::new();
+        if sample.drivers > 1 {
+            rules.push(Rule::Contention);
+        }
+        if sample.ack && !sample.strobe {
+            rules.push(Rule::AckWithoutStrobe);
+        }
+        if let Some(pending) = self.pending {
+            if !sample.strobe {
+                rules.push(Rule::StrobeDropped);
+            } else {
+                if sample.address != pending.address {
+                    rules.push(Rule::AddressChanged);
+                }
+                if sample.write != pending.write {
+                    rules.push(Rule::WriteChanged);
+                }
+            }
+        }
+        self.pending = (sample.strobe && !sample.ack).then_some(*sample);
+        let cycle = self.cycle;
+        self.cycle += 1;
+        let start = self.violations.len();
+        self.violations
+            .extend(rules.into_iter().map(|rule| Violation { cycle, rule }));
+        match self.violations.get(start) {
+            Some(violation) => Err(*violation),
+            None => Ok(()),
+        }
+    }
+
+    /// Get the cycles checked.
+    pub fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    /// Get the violations so far.
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+
+    /// Panic with the violations, if any.
+    pub fn assert_clean(&self) {
+        if self.violations.is_empty() {
+            return;
+        }
+        let lines: Vec<String> = self.violations.iter().map(|v| v.to_string()).collect();
+        panic!("bus protocol violated:\n{}", lines.join("\n"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn transfer(address: u64, write: bool, ack: bool) -> BusSample {
+        BusSample {
+            drivers: 1,
+            strobe: true,
+            write,
+            address,
+            ack,
+        }
+    }
+
+    #[test]
+    fn test_bus_checker_clean() {
+        let mut checker = BusChecker::default();
+        let samples = [
+            BusSample::default(),
+            transfer(0x10, false, false),
+            transfer(0x10, false, false),
+            transfer(0x10, false, true),
+            // a transfer may start right after the ack
+            transfer(0x20, true, true),
+            BusSample::default(),
+        ];
+        for sample in samples.iter() {
+            checker.observe(sample).unwrap();
+        }
+        assert_eq!(checker.cycle(), 6);
+        checker.assert_clean();
+    }
+
+    #[rstest]
+    #[case(BusSample { drivers: 2, ..Default::default() }, Rule::Contention)]
+    #[case(BusSample { ack: true, ..Default::default() }, Rule::AckWithoutStrobe)]
+    #[case(BusSample::default(), Rule::StrobeDropped)]
+    #[case(transfer(0x11, false, false), Rule::AddressChanged)]
+    #[case(transfer(0x10, true, true), Rule::WriteChanged)]
+    fn test_bus_checker_violation(#[case] sample: BusSample, #[case] rule: Rule) {
+        let mut checker = BusChecker::default();
+        checker.observe(&transfer(0x10, false, false)).unwrap();
+        assert_eq!(checker.observe(&sample), Err(Violation { cycle: 1, rule }));
+        assert_eq!(checker.violations()[0], Violation { cycle: 1, rule });
+    }
+
+    #[test]
+    fn test_bus_checker_all_violations() {
+        let mut checker = BusChecker::default();
+        checker.observe(&transfer(0x10, false, false)).unwrap();
+        let sample = BusSample {
+            drivers: 2,
+            ..transfer(0x12, true, false)
+        };
+        assert_eq!(checker.observe(&sample).unwrap_err().rule, Rule::Contention);
+        assert_eq!(checker.violations().len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "cycle 0: more than one driver on the bus")]
+    fn test_bus_checker_assert() {
+        let mut checker = BusChecker::default();
+        let _ = checker.observe(&BusSample {
+            drivers: 3,
+            ..Default::default()
+        });
+        checker.assert_clean();
+    }
+}
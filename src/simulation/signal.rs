@@ -0,0 +1,191 @@
+use std::{collections::HashMap, fmt};
+
+/// the separator of the levels of a hierarchical signal name.
+pub const SIGNAL_SEPARATOR: char = '.';
+
+/// the id of a signal in [`SignalDb`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SignalId(u32);
+
+impl SignalId {
+    /// Get the index of the signal, ids are given out in order from 0.
+    pub fn index(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// the error of registering a name which is already registered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateSignal {
+    pub name: String,
+    pub id: SignalId,
+}
+
+impl fmt::Display for DuplicateSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "signal `{}` is already registered", self.name)
+    }
+}
+
+impl std::error::Error for DuplicateSignal {}
+
+/// a registry of hierarchical signal names.
+///
+/// every name is stored once and gets a small [`SignalId`], so the users of the
+/// signals, e.g. probes and trace writers, carry ids and only look the names up
+/// when they print them.
+///
+/// # Examples
+///
+/// ```
+/// use simulation_computer_rust::simulation::signal::SignalDb;
+///
+/// let mut db = SignalDb::default();
+/// let adder = db.register("adder").unwrap();
+/// let carry = db.register_child(adder, "carry").unwrap();
+/// assert_eq!(db.name(carry), "adder.carry");
+/// assert_eq!(db.id("adder.carry"), Some(carry));
+/// assert!(db.register("adder.carry").is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SignalDb {
+    names: Vec<Box<str>>,
+    ids: HashMap<Box<str>, SignalId>,
+}
+
+impl SignalDb {
+    /// Register a new name.
+    ///
+    /// # Returns
+    /// the id of the name, or an error when the name is already registered.
+    pub fn register(&mut self, name: &str) -> Result<SignalId, DuplicateSignal> {
+        assert!(!name.is_empty(), "name must not be empty");
+        if let Some(id) = self.ids.get(name) {
+            return Err(DuplicateSignal {
+                name: name.to_string(),
+                id: *id,
+            });
+        }
+        assert!(
+            self.names.len() < u32::MAX as usize,
+            "too many signals are registered"
+        );
+        let id = SignalId(self.names.len() as u32);
+        self.names.push(name.into());
+        self.ids.insert(name.into(), id);
+        Ok(id)
+    }
+
+    /// Register a name under the name of `parent`, joined by [`SIGNAL_SEPARATOR`].
+    pub fn register_child(
+        &mut self,
+        parent: SignalId,
+        name: &str,
+    ) -> Result<SignalId, DuplicateSignal> {
+        let full = format!("{}{}{}", self.name(parent), SIGNAL_SEPARATOR, name);
+        self.register(&full)
+    }
+
+    /// Get the id of a name, register the name when it is new.
+    pub fn intern(&mut self, name: &str) -> SignalId {
+        match self.register(name) {
+            Ok(id) => id,
+            Err(duplicate) => duplicate.id,
+        }
+    }
+
+    /// Get the id of a name.
+    pub fn id(&self, name: &str) -> Option<SignalId> {
+        self.ids.get(name).copied()
+    }
+
+    /// Get the name of an id.
+    pub fn name(&self, id: SignalId) -> &str {
+        &self.names[id.index()]
+    }
+
+    /// Get the ids of the names under `prefix`, in the order they are registered.
+    pub fn children(&self, prefix: &str) -> Vec<SignalId> {
+        self.iter()
+            .filter(|(_, name)| {
+                name.strip_prefix(prefix)
+                    .is_some_and(|rest| rest.starts_with(SIGNAL_SEPARATOR))
+            })
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// Iterate the ids and the names, in the order they are registered.
+    pub fn iter(&self) -> impl Iterator<Item = (SignalId, &str)> {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (SignalId(i as u32), name.as_ref()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signal_db_register() {
+        let mut db = SignalDb::default();
+        assert!(db.is_empty());
+        let a = db.register("a").unwrap();
+        let b = db.register("b").unwrap();
+        assert_eq!((a.index(), b.index()), (0, 1));
+        assert_eq!(db.len(), 2);
+        assert_eq!(db.name(b), "b");
+        assert_eq!(db.id("a"), Some(a));
+        assert_eq!(db.id("c"), None);
+    }
+
+    #[test]
+    fn test_signal_db_duplicate() {
+        let mut db = SignalDb::default();
+        let clock = db.register("cpu.clock").unwrap();
+        let error = db.register("cpu.clock").unwrap_err();
+        assert_eq!(error.id, clock);
+        assert_eq!(
+            error.to_string(),
+            "signal `cpu.clock` is already registered"
+        );
+        assert_eq!(db.len(), 1);
+        assert_eq!(db.intern("cpu.clock"), clock);
+        assert_eq!(db.intern("cpu.reset").index(), 1);
+    }
+
+    #[test]
+    fn test_signal_db_hierarchy() {
+        let mut db = SignalDb::default();
+        let cpu = db.register("cpu").unwrap();
+        let alu = db.register_child(cpu, "alu").unwrap();
+        let carry = db.register_child(alu, "carry").unwrap();
+        db.register("cpu2").unwrap();
+        let pc = db.register_child(cpu, "pc").unwrap();
+        assert_eq!(db.name(carry), "cpu.alu.carry");
+        assert!(db.register_child(cpu, "alu").is_err());
+        assert_eq!(db.children("cpu"), vec![alu, carry, pc]);
+        assert_eq!(db.children("cpu.alu"), vec![carry]);
+        assert!(db.children("cpu.pc").is_empty());
+    }
+
+    #[test]
+    fn test_signal_db_iter() {
+        let mut db = SignalDb::default();
+        for name in ["x", "y", "z"] {
+            db.register(name).unwrap();
+        }
+        let names: Vec<&str> = db.iter().map(|(_, name)| name).collect();
+        assert_eq!(names, vec!["x", "y", "z"]);
+    }
+}
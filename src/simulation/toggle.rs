@@ -0,0 +1,54 @@
+use crate::circuit::Potential;
+
+/// a counter of the toggles of a group of signals, the measure of the dynamic power
+/// of a design.
+///
+/// # Examples
+///
+/// ```
+/// use simulation_computer_rust::simulation::toggle::ToggleCounter;
+///
+/// let mut counter = ToggleCounter::default();
+/// counter.observe(&[false, false]);
+/// counter.observe(&[true, false]);
+/// counter.observe(&[false, true]);
+/// assert_eq!(counter.toggles(), 3);
+/// assert_eq!(counter.per_signal(), &[2, 1]);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ToggleCounter {
+    last: Vec<Potential>,
+    toggles: Vec<usize>,
+}
+
+impl ToggleCounter {
+    /// Observe the signals, the first observation sets the start levels.
+    pub fn observe(&mut self, signals: &[Potential]) {
+        if self.last.is_empty() {
+            self.last = signals.to_vec();
+            self.toggles = vec![0; signals.len()];
+            return;
+        }
+        assert!(
+            signals.len() == self.last.len(),
+            "signals must be {} bits",
+            self.last.len()
+        );
+        for (i, signal) in signals.iter().enumerate() {
+            if *signal != self.last[i] {
+                self.toggles[i] += 1;
+                self.last[i] = *signal;
+            }
+        }
+    }
+
+    /// Get the toggles of all the signals.
+    pub fn toggles(&self) -> usize {
+        self.toggles.iter().sum()
+    }
+
+    /// Get the toggles of every signal.
+    pub fn per_signal(&self) -> &[usize] {
+        &self.toggles
+    }
+}